@@ -0,0 +1,169 @@
+use std::collections::{BTreeSet, HashMap};
+use std::error::Error;
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use crate::services::websocket::Trace;
+
+// Notes and tags attached to traces, keyed by `trace_key`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Annotations {
+    pub notes: HashMap<String, String>,
+    pub tags: HashMap<String, BTreeSet<String>>,
+}
+
+impl Annotations {
+    pub fn is_annotated(&self, id: &str) -> bool {
+        self.notes.contains_key(id) || self.tags.contains_key(id)
+    }
+
+    // Words of the input starting with `#` become tags, the rest is the note.
+    // An empty input removes the annotation.
+    pub fn set(&mut self, id: &str, input: &str) {
+        let (tags, words): (Vec<&str>, Vec<&str>) = input
+            .split_whitespace()
+            .partition(|word| word.len() > 1 && word.starts_with('#'));
+
+        let note = words.join(" ");
+        let tags: BTreeSet<String> = tags
+            .iter()
+            .map(|tag| tag.trim_start_matches('#').to_string())
+            .collect();
+
+        if note.is_empty() {
+            self.notes.remove(id);
+        } else {
+            self.notes.insert(id.to_string(), note);
+        }
+
+        if tags.is_empty() {
+            self.tags.remove(id);
+        } else {
+            self.tags.insert(id.to_string(), tags);
+        }
+    }
+
+    pub fn format_tags(&self, id: &str) -> Option<String> {
+        let tags = self.tags.get(id)?;
+
+        Some(
+            tags.iter()
+                .map(|tag| format!("#{}", tag))
+                .collect::<Vec<_>>()
+                .join(" "),
+        )
+    }
+
+    // The annotation as it would be typed into the prompt.
+    pub fn to_input(&self, id: &str) -> String {
+        [self.format_tags(id), self.notes.get(id).cloned()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+// Trace ids start over with every collector session, the timestamp and uri
+// keep an annotation on the trace it was written for.
+pub fn trace_key(trace: &Trace) -> String {
+    let uri = trace.http.as_ref().map_or("", |http| http.uri.as_str());
+
+    format!("{} {} {}", trace.id, trace.timestamp, uri)
+}
+
+pub fn parse(contents: &str) -> Result<Annotations, Box<dyn Error>> {
+    let annotations = serde_yaml::from_str::<Annotations>(contents)?;
+    Ok(annotations)
+}
+
+pub fn load(path: &str) -> Result<Annotations, Box<dyn Error>> {
+    let contents = fs::read_to_string(path)?;
+    parse(&contents)
+}
+
+pub fn save(path: &str, annotations: &Annotations) -> Result<(), Box<dyn Error>> {
+    let contents = serde_yaml::to_string(annotations)?;
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::services::websocket::HTTPTrace;
+
+    #[test]
+    fn test_set_annotation() {
+        let mut annotations = Annotations::default();
+
+        annotations.set("7", "#flaky this is the failing one #auth");
+
+        assert!(annotations.is_annotated("7"));
+        assert!(!annotations.is_annotated("8"));
+        assert_eq!(
+            Some(&"this is the failing one".to_string()),
+            annotations.notes.get("7")
+        );
+        assert_eq!(
+            Some("#auth #flaky".to_string()),
+            annotations.format_tags("7")
+        );
+        assert_eq!(
+            "#auth #flaky this is the failing one",
+            annotations.to_input("7")
+        );
+
+        annotations.set("7", "  ");
+
+        assert!(!annotations.is_annotated("7"));
+    }
+
+    #[test]
+    fn test_trace_key() {
+        let trace = |id: &str, timestamp: i64, uri: &str| Trace {
+            id: id.to_string(),
+            timestamp,
+            http: Some(HTTPTrace {
+                uri: uri.to_string(),
+                ..HTTPTrace::default()
+            }),
+            ..Trace::default()
+        };
+
+        let mut annotations = Annotations::default();
+
+        annotations.set(&trace_key(&trace("1", 10, "/a")), "#flaky");
+
+        assert!(annotations.is_annotated(&trace_key(&trace("1", 10, "/a"))));
+        // the same id in a later session is another trace
+        assert!(!annotations.is_annotated(&trace_key(&trace("1", 20, "/a"))));
+        assert!(!annotations.is_annotated(&trace_key(&trace("1", 10, "/b"))));
+    }
+
+    #[test]
+    fn test_annotations_round_trip() -> Result<(), Box<dyn Error>> {
+        let mut annotations = Annotations::default();
+
+        annotations.set("7", "#flaky this is the failing one");
+        annotations.set("16", "retried twice");
+
+        let path = std::env::temp_dir().join("envy-test-annotations.yml");
+        let path = path.to_str().unwrap();
+
+        save(path, &annotations)?;
+
+        let loaded = load(path)?;
+
+        fs::remove_file(path)?;
+
+        assert_eq!(annotations, loaded);
+
+        Ok(())
+    }
+}
@@ -2,13 +2,15 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use crossterm::event::KeyEvent;
 use http::Method;
 use ratatui::widgets::ScrollbarState;
 use serde::{Deserialize, Serialize};
-use strum_macros::{Display, EnumIs, EnumIter};
+use strum_macros::{Display, EnumIs, EnumIter, EnumString};
 use tokio::sync::mpsc;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::Mutex;
@@ -16,6 +18,7 @@ use tokio::sync::Mutex;
 use crate::components::component::Component;
 use crate::components::handlers::HandlerMetadata;
 use crate::components::home::Home;
+use crate::presets::FilterPreset;
 use crate::services::websocket::{Client, Trace};
 use crate::tui::{Event, Tui};
 use crate::wss::client;
@@ -38,6 +41,10 @@ pub enum DetailsPane {
     ResponseHeaders,
     #[strum(serialize = "TIMING")]
     Timing,
+    #[strum(serialize = "CURL")]
+    Curl,
+    #[strum(serialize = "EXTRA")]
+    Extra,
 }
 
 #[derive(Clone, Copy, Default, PartialEq, Debug)]
@@ -47,13 +54,18 @@ pub enum Mode {
     Normal,
 }
 
-#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum FilterScreen {
     #[default]
     Main,
     Method,
     Source,
     Status,
+    Key,
+    StatusRange,
+    ContentType,
+    Presets,
+    SavePreset,
     Actions,
 }
 
@@ -71,7 +83,16 @@ pub enum SortScreen {
     Actions,
 }
 
-#[derive(Clone, Copy, Default, Debug, Eq, PartialEq, Serialize, Deserialize, strum_macros::EnumIs)]
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BodyScreen {
+    #[default]
+    Request,
+    Response,
+}
+
+#[derive(
+    Clone, Copy, Default, Debug, Eq, PartialEq, Serialize, Deserialize, strum_macros::EnumIs,
+)]
 pub enum ActiveBlock {
     #[default]
     Traces,
@@ -83,6 +104,11 @@ pub enum ActiveBlock {
     Filter(FilterScreen),
     Sort(SortScreen),
     SearchQuery,
+    BodyZoom(BodyScreen),
+    Annotate,
+    ContextMenu,
+    GoTo,
+    LargestResponses,
 }
 
 #[derive(Default, Clone)]
@@ -96,7 +122,16 @@ pub struct UIState {
     pub horizontal_scroll_state: ScrollbarState,
 }
 
-#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+impl UIState {
+    pub fn reset_scroll(&mut self) {
+        self.offset = 0;
+        self.horizontal_offset = 0;
+        self.scroll_state = self.scroll_state.position(0);
+        self.horizontal_scroll_state = self.horizontal_scroll_state.position(0);
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, EnumIter)]
 pub enum Action {
     #[serde(skip)]
     Error(String),
@@ -122,17 +157,60 @@ pub enum Action {
     NewSearch,
     UpdateSearchQuery(char),
     UpdateFilter,
+    UpdateFilterInput(char),
+    DeleteFilterInput,
     UpdateSort,
     SelectSortSource(SortSource),
     SelectSortDirection(SortDirection),
     DeleteSearchQuery,
     ExitSearch,
+    SearchHistoryPrevious,
+    SearchHistoryNext,
+    NextMatch,
+    PreviousMatch,
+    NextError,
+    PreviousError,
+    ToggleShowHidden,
+    ToggleFreeze,
+    ToggleCorrelationFilter,
+    ToggleFilterInvert,
+    ToggleTimeColumn,
+    ToggleCompact,
+    CycleDetailsSort,
+    ToggleCurlQueryFlags,
+    ToggleRedaction,
+    ExportKeyMap,
+    ExportMockStub,
+    CopyAsMarkdown,
+    CopyUrl,
+    OpenExportMenu,
+    CopyAs(ExportFormat),
+    PageUp,
+    PageDown,
+    SaveFilterPreset,
+    AnnotateTrace,
+    SaveAnnotation,
+    UpdateAnnotationInput(char),
+    DeleteAnnotationInput,
+    OpenGoTo,
+    GoToTrace,
+    SaveTrace(String),
+    OpenLargestResponses,
+    GoToLargestResponse,
+    ApplyFilterPreset(String),
+    DeleteFilterPreset(String),
     Help,
     ToggleDebug,
+    CycleLogLevel,
+    ToggleLogTimestamps,
     DeleteItem,
     FocusOnTraces,
     SelectTrace(Option<Trace>),
     UpdateTraceIndex(usize),
+    #[serde(skip)]
+    OpenContextMenu(usize),
+    FilterByMethod(String),
+    FilterByStatus(String),
     ShowTraceDetails,
     NextDetailsTab,
     PreviousDetailsTab,
@@ -149,16 +227,35 @@ pub enum Action {
     SetWebsocketStatus(WebSocketInternalState),
     #[serde(skip)]
     MarkTraceAsTimedOut(String),
+    Tick,
     #[serde(skip)]
     ClearStatusMessage,
     #[serde(skip)]
+    ClearAlert,
+    #[serde(skip)]
     AddTrace(Trace),
     AddTraceError,
     ExpandAll,
     CollapseAll,
+    CollapseNode,
+    ToggleBodyZoom,
+    ToggleDetails,
+    ToggleWrap,
+    ToggleRawBody,
+    ToggleFlattenBody,
+    ToggleTypeHints,
+    JumpToMatchingBracket,
+    GoToParent,
+    ToggleLineNumbers,
+    IncreaseIndent,
+    DecreaseIndent,
     ActivateBlock(ActiveBlock),
     PopOutDetailsTab(DetailsPane),
+    MoveDetailsTabLeft,
+    MoveDetailsTabRight,
     CloseDetailsPane(DetailsPane),
+    PopOutAllDetailsTabs,
+    CloseAllDetailsPanes,
 }
 
 #[derive(Default, PartialEq, Eq, Debug, Clone)]
@@ -169,18 +266,25 @@ pub enum WebSocketInternalState {
     Closed,
 }
 
-#[derive(Clone, PartialEq, Debug, Eq, Default)]
+#[derive(Clone, PartialEq, Debug, Eq, Default, Serialize, Deserialize)]
 pub enum SourceFilter {
     #[default]
     All,
     Applied(HashSet<String>),
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(from = "FilterPreset", into = "FilterPreset")]
 pub struct TraceFilter {
     pub source: SourceFilter,
     pub method: HashMap<Method, MethodFilter>,
     pub status: HashMap<String, StatusFilter>,
+    pub key: String,
+    pub status_range: String,
+    pub content_type: SourceFilter,
+    pub combination: FilterCombination,
+    pub time_window: TimeWindow,
+    pub inverted: HashSet<FilterScreen>,
 }
 
 impl Default for TraceFilter {
@@ -188,16 +292,18 @@ impl Default for TraceFilter {
         let mut method: HashMap<Method, MethodFilter> = HashMap::new();
         let mut status: HashMap<String, StatusFilter> = HashMap::new();
 
-        vec!["1xx", "2xx", "3xx", "4xx", "5xx"].iter().for_each(|http_status| {
-            status.insert(
-                http_status.to_string(),
-                StatusFilter {
-                    status: http_status.to_string(),
-                    selected: false,
-                    name: http_status.to_string(),
-                },
-            );
-        });
+        vec!["1xx", "2xx", "3xx", "4xx", "5xx"]
+            .iter()
+            .for_each(|http_status| {
+                status.insert(
+                    http_status.to_string(),
+                    StatusFilter {
+                        status: http_status.to_string(),
+                        selected: false,
+                        name: http_status.to_string(),
+                    },
+                );
+            });
 
         vec![
             Method::POST,
@@ -206,7 +312,9 @@ impl Default for TraceFilter {
             Method::PUT,
             Method::PATCH,
             Method::OPTIONS,
-        ].iter().for_each(|http_method| {
+        ]
+        .iter()
+        .for_each(|http_method| {
             method.insert(
                 http_method.clone(),
                 MethodFilter {
@@ -221,6 +329,60 @@ impl Default for TraceFilter {
             source: SourceFilter::default(),
             method,
             status,
+            key: String::new(),
+            status_range: String::new(),
+            content_type: SourceFilter::default(),
+            combination: FilterCombination::default(),
+            time_window: TimeWindow::default(),
+            inverted: HashSet::new(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Display, Serialize, Deserialize)]
+pub enum FilterCombination {
+    #[default]
+    #[strum(serialize = "AND")]
+    And,
+    #[strum(serialize = "OR")]
+    Or,
+}
+
+// Keeps only the traces received within the last seconds.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimeWindow {
+    #[default]
+    All,
+    LastSeconds(u64),
+}
+
+// The windows the filter modal cycles through.
+pub const TIME_WINDOWS: [TimeWindow; 4] = [
+    TimeWindow::All,
+    TimeWindow::LastSeconds(30),
+    TimeWindow::LastSeconds(60),
+    TimeWindow::LastSeconds(300),
+];
+
+impl TimeWindow {
+    pub fn next(self) -> TimeWindow {
+        TIME_WINDOWS
+            .iter()
+            .position(|&window| window == self)
+            .and_then(|index| TIME_WINDOWS.get(index + 1))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+impl Display for TimeWindow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimeWindow::All => write!(f, "all"),
+            TimeWindow::LastSeconds(seconds) if seconds % 60 == 0 => {
+                write!(f, "last {}m", seconds / 60)
+            }
+            TimeWindow::LastSeconds(seconds) => write!(f, "last {}s", seconds),
         }
     }
 }
@@ -239,42 +401,146 @@ pub struct StatusFilter {
     pub selected: bool,
 }
 
-#[derive(
-    Default,
-    PartialEq,
-    Eq,
-    Debug,
-    Clone,
-    Serialize,
-    Deserialize,
-    strum_macros::AsRefStr,
-)]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StatusRange {
+    pub include: Vec<RangeInclusive<u16>>,
+    pub exclude: Vec<u16>,
+}
+
+impl StatusRange {
+    pub fn contains(&self, code: u16) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|range| range.contains(&code)))
+            && !self.exclude.contains(&code)
+    }
+}
+
+#[derive(Default, PartialEq, Eq, Debug, Clone, Serialize, Deserialize, strum_macros::AsRefStr)]
 pub enum SortDirection {
     Ascending,
     #[default]
     Descending,
 }
 
-#[derive(
-    Default,
-    PartialEq,
-    Eq,
-    Debug,
-    Clone,
-    Serialize,
-    Deserialize,
-    strum_macros::AsRefStr,
-)]
+#[derive(Default, PartialEq, Eq, Debug, Clone, Serialize, Deserialize, strum_macros::AsRefStr)]
 pub enum SortSource {
     Method,
     Status,
     Source,
     Url,
     Duration,
+    #[strum(serialize = "Header count")]
+    HeaderCount,
     #[default]
     Timestamp,
 }
 
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeColumn {
+    Hidden,
+    #[default]
+    Relative,
+    Absolute,
+}
+
+// Order of the headers and query params in the details panes, unsorted keeps
+// the order the trace was received in.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Serialize, Deserialize, Display)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum DetailsSort {
+    #[default]
+    NameAsc,
+    NameDesc,
+    Unsorted,
+}
+
+// Ordered from least to most verbose, a level shows every line at or below it.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, PartialOrd, Ord, Display, EnumString)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    #[default]
+    Debug,
+}
+
+impl LogLevel {
+    // Reads a leading `ERROR`, `[warn]` or `info:` style prefix, lines without
+    // one are info.
+    pub fn from_line(line: &str) -> LogLevel {
+        line.trim_start()
+            .trim_start_matches('[')
+            .split(|c: char| !c.is_ascii_alphabetic())
+            .next()
+            .and_then(|prefix| LogLevel::from_str(prefix).ok())
+            .unwrap_or(LogLevel::Info)
+    }
+
+    pub fn next(self) -> LogLevel {
+        match self {
+            LogLevel::Error => LogLevel::Warn,
+            LogLevel::Warn => LogLevel::Info,
+            LogLevel::Info => LogLevel::Debug,
+            LogLevel::Debug => LogLevel::Error,
+        }
+    }
+}
+
+// Formats offered by the "copy as" menu, in menu order.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Serialize, Deserialize, Display, EnumIter)]
+pub enum ExportFormat {
+    #[default]
+    #[strum(serialize = "cURL")]
+    Curl,
+    #[strum(serialize = "HTTPie")]
+    Httpie,
+    #[strum(serialize = "fetch")]
+    Fetch,
+    #[strum(serialize = ".http")]
+    HttpFile,
+    Markdown,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, EnumString)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum TraceColumn {
+    Kind,
+    Method,
+    Status,
+    Url,
+    Duration,
+    Host,
+    Time,
+    Size,
+}
+
+impl TraceColumn {
+    pub fn title(&self) -> &'static str {
+        match self {
+            TraceColumn::Kind => "Kind",
+            TraceColumn::Method => "Method",
+            TraceColumn::Status => "Status",
+            TraceColumn::Url => "Request",
+            TraceColumn::Duration => "Duration",
+            TraceColumn::Host => "Host",
+            TraceColumn::Time => "Time",
+            TraceColumn::Size => "Size",
+        }
+    }
+}
+
+pub const DEFAULT_TRACE_COLUMNS: [TraceColumn; 6] = [
+    TraceColumn::Kind,
+    TraceColumn::Method,
+    TraceColumn::Status,
+    TraceColumn::Url,
+    TraceColumn::Duration,
+    TraceColumn::Time,
+];
+
 #[derive(PartialEq, Eq, Debug, Clone, Default)]
 pub struct TraceSort {
     pub source: SortSource,
@@ -302,10 +568,10 @@ impl Display for SortSource {
             SortSource::Timestamp => write!(f, "Timestamp"),
             SortSource::Method => write!(f, "Method"),
             SortSource::Status => write!(f, "Status"),
-            SortSource::Duration  => write!(f, "Duration"),
-            SortSource::Source =>write!(f, "Source"),
+            SortSource::Duration => write!(f, "Duration"),
+            SortSource::Source => write!(f, "Source"),
             SortSource::Url => write!(f, "Url"),
-
+            SortSource::HeaderCount => write!(f, "Header count"),
         }
     }
 }
@@ -325,6 +591,8 @@ pub struct App {
     pub mode: Mode,
     pub key_map: HashMap<KeyEvent, Action>,
     pub should_quit: bool,
+    pub uds_path: Option<String>,
+    pub max_body_bytes: usize,
 }
 
 impl App {
@@ -339,6 +607,7 @@ impl App {
             components: vec![home],
             services: Services { websocket_client },
             key_map: config.mapping.0,
+            max_body_bytes: config.max_body_bytes,
             ..Self::default()
         };
 
@@ -381,16 +650,31 @@ impl App {
         self.services.websocket_client.lock().await.init();
 
         let action_to_clone = self.action_tx.as_ref().unwrap().clone();
+        let max_body_bytes = self.max_body_bytes;
 
         tokio::spawn(async move {
             // TODO(vandosant) Propagate errors with a Result type to update the connection status
             // and optionally retry connecting
             // https://users.rust-lang.org/t/propagating-errors-from-tokio-tasks/41723/4
-            client(Some(action_to_clone))
+            client(Some(action_to_clone), max_body_bytes)
                 .await
                 .expect("Failed to broadcast action");
         });
 
+        #[cfg(unix)]
+        if let Some(path) = self.uds_path.clone() {
+            let uds_tx = action_tx.clone();
+
+            tokio::spawn(async move {
+                if let Err(e) = crate::uds::listen(&path, uds_tx.clone(), max_body_bytes).await {
+                    let _ = uds_tx.send(Action::Error(format!(
+                        "Failed to listen on {}: {:?}",
+                        path, e
+                    )));
+                }
+            });
+        }
+
         loop {
             let event = t.next().await;
 
@@ -408,6 +692,10 @@ impl App {
                 }
             };
 
+            if let Some(Event::Tick) = event {
+                action_tx.send(Action::Tick)?;
+            };
+
             if let Some(Event::OnMount) = event {
                 for component in self.components.iter() {
                     if let Some(action) = component.lock().await.on_mount()? {
@@ -1,5 +1,7 @@
+use std::collections::HashSet;
+
 use derive_new::new;
-use ratatui::widgets::ListState;
+use ratatui::widgets::{ListState, ScrollbarState};
 
 use crate::app::Action;
 
@@ -38,6 +40,11 @@ pub struct ActionableList {
     pub scroll_state: ListState,
     pub select_state: ListState,
     pub show_select_labels: bool,
+    #[new(default)]
+    pub selected: HashSet<usize>,
+    // Characters scrolled off the start of the values, labels stay in place.
+    #[new(default)]
+    pub horizontal_offset: usize,
 }
 
 impl ActionableList {
@@ -47,6 +54,8 @@ impl ActionableList {
             scroll_state: ListState::default(),
             select_state: ListState::default(),
             show_select_labels: false,
+            selected: HashSet::new(),
+            horizontal_offset: 0,
         }
     }
 
@@ -67,12 +76,67 @@ impl ActionableList {
     pub fn reset(&mut self) {
         self.scroll_state.select(None);
         self.select_state.select(None);
+        self.selected.clear();
+        self.horizontal_offset = 0;
+    }
+
+    // Stops with the last character of the longest value still in view.
+    pub fn scroll_right(&mut self, step: usize) {
+        let longest = self
+            .items
+            .iter()
+            .filter_map(|item| item.value.as_ref())
+            .map(|value| value.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        self.horizontal_offset = self
+            .horizontal_offset
+            .saturating_add(step)
+            .min(longest.saturating_sub(1));
+    }
+
+    pub fn scroll_left(&mut self, step: usize) {
+        self.horizontal_offset = self.horizontal_offset.saturating_sub(step);
+    }
+
+    pub fn toggle_selected(&mut self) {
+        if let Some(i) = self.scroll_state.selected() {
+            if i < self.items.len() && !self.selected.remove(&i) {
+                self.selected.insert(i);
+            }
+        }
+    }
+
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selected.contains(&index)
+    }
+
+    pub fn selected_items(&self) -> Vec<&ActionableListItem> {
+        self.items
+            .iter()
+            .enumerate()
+            .filter(|(i, _item)| self.selected.contains(i))
+            .map(|(_i, item)| item)
+            .collect()
     }
 
     pub fn top(&mut self, index: usize) {
         self.scroll_state.select(Some(index));
     }
 
+    pub fn bottom(&mut self) {
+        if !self.items.is_empty() {
+            self.scroll_state.select(Some(self.items.len() - 1));
+        }
+    }
+
+    // The scrollbar follows the selected row, so the thumb can never drift
+    // from the highlighted item.
+    pub fn scrollbar_state(&self) -> ScrollbarState {
+        ScrollbarState::new(self.items.len()).position(self.scroll_state.selected().unwrap_or(0))
+    }
+
     pub fn select(&mut self, index: usize) {
         self.select_state.select(Some(index));
     }
@@ -118,3 +182,46 @@ impl ActionableList {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn labels(items: Vec<&ActionableListItem>) -> Vec<String> {
+        items.iter().map(|item| item.label.clone()).collect()
+    }
+
+    #[test]
+    fn test_toggle_selected() {
+        let mut list = ActionableList::with_items(vec![
+            ActionableListItem::with_label("a"),
+            ActionableListItem::with_label("b"),
+            ActionableListItem::with_label("c"),
+        ]);
+
+        list.toggle_selected();
+
+        assert!(list.selected_items().is_empty());
+
+        list.next();
+        list.toggle_selected();
+        list.next();
+        list.next();
+        list.toggle_selected();
+
+        assert!(list.is_selected(0));
+        assert!(!list.is_selected(1));
+        assert_eq!(vec!["a", "c"], labels(list.selected_items()));
+        assert_eq!(Some(2), list.scroll_state.selected());
+
+        list.toggle_selected();
+
+        assert_eq!(vec!["a"], labels(list.selected_items()));
+
+        list.reset();
+
+        assert!(list.selected_items().is_empty());
+    }
+}
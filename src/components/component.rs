@@ -1,6 +1,6 @@
 use std::error::Error;
 
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, MouseEvent};
 use tokio::sync::mpsc::UnboundedSender;
 
 use crate::{
@@ -19,6 +19,7 @@ pub trait Component {
     fn handle_events(&mut self, event: Option<Event>) -> Result<Option<Action>, Box<dyn Error>> {
         let r = match event {
             Some(Event::Key(key_event)) => self.handle_key_events(key_event)?,
+            Some(Event::Mouse(mouse_event)) => self.handle_mouse_events(mouse_event)?,
             _ => None,
         };
         Ok(r)
@@ -28,6 +29,10 @@ pub trait Component {
         Ok(None)
     }
     #[allow(unused_variables)]
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Option<Action>, Box<dyn Error>> {
+        Ok(None)
+    }
+    #[allow(unused_variables)]
     fn update(&mut self, action: Action) -> Result<Option<Action>, Box<dyn Error>> {
         Ok(None)
     }
@@ -1,27 +1,41 @@
+use crate::annotations;
 use crate::app::{
-    Action, ActiveBlock, DetailsPane, FilterScreen, MethodFilter, SortScreen, SourceFilter,
-    StatusFilter,
+    Action, ActiveBlock, BodyScreen, DetailsPane, DetailsSort, ExportFormat, FilterCombination,
+    FilterScreen, MethodFilter, SortScreen, SourceFilter, StatusFilter, TimeColumn,
 };
+use crate::components::actionable_list::{ActionableList, ActionableListItem};
 use crate::components::home::Home;
+use crate::config::AlertConfig;
 use crate::consts::{
-    NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE, REQUEST_HEADERS_UNUSABLE_VERTICAL_SPACE,
-    RESPONSE_BODY_UNUSABLE_VERTICAL_SPACE, RESPONSE_HEADERS_UNUSABLE_VERTICAL_SPACE,
+    ANNOTATIONS_FILE, FILTER_PRESETS_FILE, FILTER_SOURCES, JSON_VIEWER_HORIZONTAL_SCROLL_STEP,
+    KEY_MAP_EXPORT_FILE, LARGEST_RESPONSES_COUNT, MOCK_STUB_EXPORT_FILE,
+    RESPONSE_BODY_UNUSABLE_VERTICAL_SPACE, SEARCH_HISTORY_LIMIT,
 };
-use crate::parser::{generate_curl_command, pretty_parse_body};
-use crate::render::get_services_from_traces;
-use crate::services::websocket::Trace;
+use crate::export::to_mock_stub;
+use crate::parser::{generate_curl_command, generate_markdown_summary, redact_raw_trace};
+use crate::presets;
+use crate::render::{
+    format_key_map, get_content_types_from_traces, get_filter_preset_names, get_help_rows,
+    get_services_from_traces, get_unmapped_actions,
+};
+use crate::services::websocket::{State, Trace};
 use crate::utils::{
-    calculate_scrollbar_position, get_content_length, get_currently_selected_trace,
-    get_rendered_items, set_content_length,
+    calculate_scrollbar_position, correlation_id, get_content_length, get_copied_response_body,
+    get_currently_selected_trace, get_filter_screen, get_focused_headers, get_largest_responses,
+    get_rendered_items, get_selected_trace_export, get_selected_trace_url, is_error_trace,
+    matches_search, search_regex, set_content_length,
 };
 use crossterm::event::{KeyEvent, KeyModifiers};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::fs;
+use std::io::{stdout, ErrorKind, Write};
 use std::time::Duration;
+use strum::IntoEnumIterator;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::time::sleep;
 
-#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
 pub struct HandlerMetadata {
     pub main_height: u16,
     pub response_body_rectangle_height: u16,
@@ -38,36 +52,11 @@ enum Direction {
     Left,
 }
 
-fn reset_request_and_response_body_ui_state(app: &mut Home) {
-    app.response_body.offset = 0;
-    app.response_body.horizontal_offset = 0;
-
-    app.request_body.offset = 0;
-    app.request_body.horizontal_offset = 0;
-
-    app.response_body.horizontal_scroll_state =
-        app.response_body.horizontal_scroll_state.position(0);
-    app.response_body.scroll_state = app.response_body.scroll_state.position(0);
-
-    app.request_body.offset = 0;
-    app.request_body.horizontal_offset = 0;
-
-    app.request_body.offset = 0;
-    app.request_body.horizontal_offset = 0;
-
-    app.request_body.horizontal_scroll_state = app.request_body.horizontal_scroll_state.position(0);
-    app.request_body.scroll_state = app.request_body.scroll_state.position(0);
-
-    app.response_details.offset = 0;
-
-    app.response_details.scroll_state = app.response_details.scroll_state.position(0);
+pub fn reset_request_and_response_body_ui_state(app: &mut Home) {
+    app.response_body.reset_scroll();
+    app.request_body.reset_scroll();
 
     app.response_headers_list.reset();
-
-    app.request_details.offset = 0;
-
-    app.request_details.scroll_state = app.request_details.scroll_state.position(0);
-
     app.request_headers_list.reset();
 }
 
@@ -81,6 +70,26 @@ pub fn handle_debug(app: &mut Home) -> Option<Action> {
     None
 }
 
+pub fn handle_cycle_log_level(app: &mut Home) -> Option<Action> {
+    if app.active_block != ActiveBlock::Debug {
+        return None;
+    }
+
+    app.debug_level = app.debug_level.next();
+
+    None
+}
+
+pub fn handle_toggle_log_timestamps(app: &mut Home) -> Option<Action> {
+    if app.active_block != ActiveBlock::Debug {
+        return None;
+    }
+
+    app.hide_log_timestamps = !app.hide_log_timestamps;
+
+    None
+}
+
 pub fn handle_help(app: &mut Home) -> Option<Action> {
     let current_block = app.active_block;
 
@@ -106,6 +115,27 @@ pub fn handle_up(
             _ => None,
         },
         _ => match (app.active_block, app.details_block) {
+            (ActiveBlock::SearchQuery, _) => None,
+            (ActiveBlock::ContextMenu, _) => {
+                app.context_menu.previous();
+
+                None
+            }
+            (ActiveBlock::LargestResponses, _) => {
+                let length = get_largest_responses(app, LARGEST_RESPONSES_COUNT).len();
+
+                app.largest_responses_index = app
+                    .largest_responses_index
+                    .min(length.saturating_sub(1))
+                    .saturating_sub(1);
+
+                None
+            }
+            (ActiveBlock::Help, _) => {
+                app.help_offset = app.help_offset.saturating_sub(1);
+
+                None
+            }
             (ActiveBlock::Filter(FilterScreen::Main), _) => {
                 match app.filter_source_index.checked_sub(1) {
                     Some(v) => {
@@ -158,13 +188,13 @@ pub fn handle_up(
 
                     let usable_height = additinal_metadata
                         .main_height
-                        .saturating_sub(NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE as u16);
+                        .saturating_sub(app.traces_unusable_vertical_space() as u16);
 
                     if usable_height < number_of_lines {
                         let overflown_number_count: u16 = number_of_lines
                             - (additinal_metadata
                                 .main_height
-                                .saturating_sub(NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE as u16));
+                                .saturating_sub(app.traces_unusable_vertical_space() as u16));
 
                         let position = calculate_scrollbar_position(
                             number_of_lines,
@@ -214,6 +244,16 @@ pub fn handle_up(
 
                 None
             }
+            (ActiveBlock::Details, DetailsPane::Extra) => {
+                app.extra_list.previous();
+
+                None
+            }
+            (ActiveBlock::Details, DetailsPane::Curl) => {
+                app.curl.offset = app.curl.offset.saturating_sub(1);
+
+                None
+            }
             _ => None,
         },
     }
@@ -227,7 +267,7 @@ pub fn handle_adjust_scroll_bar(
 
     let usable_height = additinal_metadata
         .main_height
-        .saturating_sub(NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE.try_into().unwrap());
+        .saturating_sub(app.traces_unusable_vertical_space().try_into().unwrap());
 
     let number_of_lines: u16 = length.try_into().unwrap();
 
@@ -239,7 +279,7 @@ pub fn handle_adjust_scroll_bar(
         let overflown_number_count: u16 = number_of_lines.saturating_sub(
             additinal_metadata
                 .main_height
-                .saturating_sub(NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE as u16),
+                .saturating_sub(app.traces_unusable_vertical_space() as u16),
         );
 
         let position =
@@ -269,6 +309,29 @@ pub fn handle_down(
             _ => None,
         },
         _ => match (app.active_block, app.details_block) {
+            (ActiveBlock::SearchQuery, _) => None,
+            (ActiveBlock::ContextMenu, _) => {
+                app.context_menu.next();
+
+                None
+            }
+            (ActiveBlock::LargestResponses, _) => {
+                let length = get_largest_responses(app, LARGEST_RESPONSES_COUNT).len();
+
+                app.largest_responses_index =
+                    (app.largest_responses_index + 1).min(length.saturating_sub(1));
+
+                None
+            }
+            (ActiveBlock::Help, _) => {
+                let length = get_help_rows(app).len() + get_unmapped_actions(app).len();
+
+                if app.help_offset + 1 < length {
+                    app.help_offset += 1;
+                }
+
+                None
+            }
             (ActiveBlock::Filter(FilterScreen::Method), _) => {
                 if app.filter_value_index + 1 < app.selected_filters.method.len() {
                     app.filter_value_index += 1;
@@ -283,8 +346,22 @@ pub fn handle_down(
 
                 None
             }
+            (ActiveBlock::Filter(FilterScreen::ContentType), _) => {
+                if app.filter_value_index + 1 < get_content_types_from_traces(app).len() + 1 {
+                    app.filter_value_index += 1;
+                }
+
+                None
+            }
+            (ActiveBlock::Filter(FilterScreen::Presets), _) => {
+                if app.filter_value_index < get_filter_preset_names(app).len() {
+                    app.filter_value_index += 1;
+                }
+
+                None
+            }
             (ActiveBlock::Filter(FilterScreen::Main), _) => {
-                if app.filter_source_index + 1 < 3 {
+                if app.filter_source_index + 1 < FILTER_SOURCES.len() {
                     app.filter_source_index += 1;
                 }
 
@@ -324,13 +401,13 @@ pub fn handle_down(
 
                 let usable_height = additinal_metadata
                     .main_height
-                    .saturating_sub(NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE.try_into().unwrap());
+                    .saturating_sub(app.traces_unusable_vertical_space().try_into().unwrap());
 
                 if app.main.index + 1 < length {
                     if app.main.index > {
                         additinal_metadata
                             .main_height
-                            .saturating_sub(NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE as u16)
+                            .saturating_sub(app.traces_unusable_vertical_space() as u16)
                             .saturating_sub(2)
                     } as usize
                         && app.main.offset as u16 + usable_height < number_of_lines
@@ -349,7 +426,7 @@ pub fn handle_down(
                     let overflown_number_count: u16 = number_of_lines.saturating_sub(
                         additinal_metadata
                             .main_height
-                            .saturating_sub(NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE as u16),
+                            .saturating_sub(app.traces_unusable_vertical_space() as u16),
                     );
 
                     let position = calculate_scrollbar_position(
@@ -395,241 +472,794 @@ pub fn handle_down(
 
                 None
             }
+            (ActiveBlock::Details, DetailsPane::Extra) => {
+                app.extra_list.next();
+
+                None
+            }
+            (ActiveBlock::Details, DetailsPane::Curl) => {
+                // Clamped to the wrapped command height while rendering.
+                app.curl.offset = app.curl.offset.saturating_add(1);
+
+                None
+            }
             _ => None,
         },
     }
 }
 
 pub fn handle_esc(app: &mut Home) -> Option<Action> {
+    if app.active_block.is_body_zoom() {
+        return handle_body_zoom(app);
+    }
+
     app.active_block = ActiveBlock::Traces;
 
     None
 }
 
-pub fn handle_new_search(app: &mut Home) -> Option<Action> {
-    app.search_query.clear();
-    app.active_block = ActiveBlock::SearchQuery;
+pub fn handle_body_zoom(app: &mut Home) -> Option<Action> {
+    app.active_block = match app.active_block {
+        ActiveBlock::RequestBody => ActiveBlock::BodyZoom(BodyScreen::Request),
+        ActiveBlock::ResponseBody => ActiveBlock::BodyZoom(BodyScreen::Response),
+        ActiveBlock::BodyZoom(BodyScreen::Request) => ActiveBlock::RequestBody,
+        ActiveBlock::BodyZoom(BodyScreen::Response) => ActiveBlock::ResponseBody,
+        block => block,
+    };
 
     None
 }
 
-pub fn handle_search_push(app: &mut Home, c: char) -> Option<Action> {
-    app.search_query.push(c);
+pub fn handle_toggle_details(app: &mut Home) -> Option<Action> {
+    if !matches!(
+        app.active_block,
+        ActiveBlock::Traces
+            | ActiveBlock::Details
+            | ActiveBlock::RequestBody
+            | ActiveBlock::ResponseBody
+    ) {
+        return None;
+    }
 
-    None
-}
+    app.details_hidden = !app.details_hidden;
 
-pub fn handle_search_pop(app: &mut Home) -> Option<Action> {
-    app.search_query.pop();
-    if app.search_query.is_empty() {
-        handle_search_exit(app);
+    // the focused pane may have just been hidden
+    if app.details_hidden {
+        app.active_block = ActiveBlock::Traces;
     }
 
     None
 }
 
-pub fn handle_search_exit(app: &mut Home) -> Option<Action> {
-    app.active_block = ActiveBlock::Traces;
+// Bodies and headers panes are searched in place, anywhere else the search
+// filters the traces list.
+pub fn handle_new_search(app: &mut Home) -> Option<Action> {
+    app.search_origin = match app.active_block {
+        ActiveBlock::RequestBody | ActiveBlock::ResponseBody | ActiveBlock::BodyZoom(_) => {
+            app.active_block
+        }
+        ActiveBlock::Details
+            if matches!(
+                app.details_block,
+                DetailsPane::RequestHeaders | DetailsPane::ResponseHeaders
+            ) =>
+        {
+            ActiveBlock::Details
+        }
+        _ => ActiveBlock::Traces,
+    };
+
+    if app.search_origin == ActiveBlock::Traces {
+        app.search_query = app.last_search.clone().unwrap_or_default();
+        app.search_history_index = None;
+    }
+
+    app.active_block = ActiveBlock::SearchQuery;
 
     None
 }
 
-pub fn handle_tab(app: &mut Home) -> Option<Action> {
-    if app.active_block == ActiveBlock::Traces {
-        return select_active_details_block(app);
-    }
-
-    if app.active_block == ActiveBlock::Details {
-        return select_next_details_block(app);
+// Headers and bodies keep their own query.
+fn panel_search(app: &mut Home) -> &mut String {
+    match app.search_origin {
+        ActiveBlock::Details => &mut app.headers_search,
+        _ => &mut app.body_search,
     }
+}
 
-    let next_block = match app.active_block {
-        ActiveBlock::Traces => ActiveBlock::Details,
-        ActiveBlock::Details => ActiveBlock::ResponseBody,
-        ActiveBlock::ResponseBody => ActiveBlock::RequestBody,
-        ActiveBlock::RequestBody => ActiveBlock::Traces,
-        ActiveBlock::Filter(screen) => match screen {
-            FilterScreen::Main => ActiveBlock::Filter(FilterScreen::Actions),
-            FilterScreen::Source => ActiveBlock::Filter(FilterScreen::Actions),
-            FilterScreen::Method => ActiveBlock::Filter(FilterScreen::Actions),
-            FilterScreen::Status => ActiveBlock::Filter(FilterScreen::Actions),
-            FilterScreen::Actions => ActiveBlock::Filter(FilterScreen::Main),
-        },
-        ActiveBlock::Sort(screen) => match screen {
-            SortScreen::Source => ActiveBlock::Sort(SortScreen::Direction),
-            SortScreen::Direction => ActiveBlock::Sort(SortScreen::Actions),
-            SortScreen::Actions => ActiveBlock::Sort(SortScreen::Source),
-        },
-        _ => app.active_block,
+fn apply_panel_search(app: &mut Home) -> Option<Action> {
+    let result = match app.search_origin {
+        ActiveBlock::RequestBody | ActiveBlock::BodyZoom(BodyScreen::Request) => {
+            app.request_json_viewer.set_search(&app.body_search)
+        }
+        ActiveBlock::ResponseBody | ActiveBlock::BodyZoom(BodyScreen::Response) => {
+            app.response_json_viewer.set_search(&app.body_search)
+        }
+        // rebuild the headers panes
+        ActiveBlock::Details => return Some(Action::SelectTrace(app.selected_trace.clone())),
+        _ => Ok(()),
     };
 
-    if next_block != app.active_block {
-        app.active_block = next_block;
-
-        Some(Action::ActivateBlock(next_block))
-    } else {
-        None
+    match result {
+        Ok(_) => None,
+        Err(e) => Some(Action::Error(format!("Failed to search: {:?}", e))),
     }
 }
 
-pub fn handle_back_tab(app: &mut Home) -> Option<Action> {
-    if app.active_block == ActiveBlock::Details {
-        return select_previous_details_block(app);
+pub fn handle_search_history_previous(app: &mut Home) -> Option<Action> {
+    if app.search_history.is_empty() {
+        return None;
     }
 
-    let next_block = match app.active_block {
-        ActiveBlock::Traces => ActiveBlock::RequestBody,
-        ActiveBlock::Details => ActiveBlock::Traces,
-        ActiveBlock::RequestBody => ActiveBlock::ResponseBody,
-        ActiveBlock::ResponseBody => ActiveBlock::Details,
-        ActiveBlock::Filter(screen) => match screen {
-            FilterScreen::Main => ActiveBlock::Filter(FilterScreen::Actions),
-            FilterScreen::Source => ActiveBlock::Filter(FilterScreen::Main),
-            FilterScreen::Method => ActiveBlock::Filter(FilterScreen::Main),
-            FilterScreen::Status => ActiveBlock::Filter(FilterScreen::Main),
-            FilterScreen::Actions => ActiveBlock::Filter(FilterScreen::Main),
-        },
-        ActiveBlock::Sort(screen) => match screen {
-            SortScreen::Source => ActiveBlock::Sort(SortScreen::Actions),
-            SortScreen::Direction => ActiveBlock::Sort(SortScreen::Source),
-            SortScreen::Actions => ActiveBlock::Sort(SortScreen::Direction),
-        },
-        _ => app.active_block,
+    let index = match app.search_history_index {
+        Some(index) => index.saturating_sub(1),
+        None => app.search_history.len().saturating_sub(1),
     };
 
-    if next_block != app.active_block {
-        app.active_block = next_block;
+    app.search_history_index = Some(index);
+    app.search_query = app.search_history[index].clone();
 
-        Some(Action::ActivateBlock(next_block))
-    } else {
-        None
-    }
+    None
 }
 
-pub fn select_active_details_block(app: &mut Home) -> Option<Action> {
-    if let Some(active_tab) = app.details_tabs.get(app.details_tab_index) {
-        app.details_block = *active_tab;
-    } else {
-        if let Some(first_tab) = app.details_tabs.first() {
-            app.details_block = *first_tab;
+pub fn handle_search_history_next(app: &mut Home) -> Option<Action> {
+    match app.search_history_index {
+        Some(index) if index.saturating_add(1) < app.search_history.len() => {
+            app.search_history_index = Some(index.saturating_add(1));
+            app.search_query = app.search_history[index.saturating_add(1)].clone();
         }
+        Some(_) => {
+            app.search_history_index = None;
+            app.search_query.clear();
+        }
+        None => {}
     }
-    app.active_block = ActiveBlock::Details;
 
     None
 }
 
-pub fn select_next_details_block(app: &mut Home) -> Option<Action> {
-    // the tabs are selected, so advance to the first pane
-    if app.details_tabs.contains(&app.details_block) {
-        if let Some(first_pane) = app.details_panes.first() {
-            app.details_block = *first_pane;
+pub fn handle_search_push(app: &mut Home, c: char) -> Option<Action> {
+    if app.active_block == ActiveBlock::Help {
+        app.help_filter.push(c);
+        app.help_offset = 0;
 
-            return None;
-        }
+        return None;
     }
 
-    let mut iter = app.details_panes.iter();
+    if app.search_origin != ActiveBlock::Traces {
+        panel_search(app).push(c);
 
-    // advance iterator to the current block
-    iter.find(|&&v| app.details_block == v);
+        return apply_panel_search(app);
+    }
 
-    if let Some(next_pane) = iter.next() {
-        app.details_block = *next_pane;
+    app.search_query.push(c);
 
-        None
-    } else {
-        app.active_block = ActiveBlock::ResponseBody;
+    None
+}
 
-        Some(Action::ActivateBlock(ActiveBlock::ResponseBody))
+pub fn handle_search_pop(app: &mut Home) -> Option<Action> {
+    if app.active_block == ActiveBlock::Help {
+        app.help_filter.pop();
+        app.help_offset = 0;
+
+        return None;
     }
-}
 
-pub fn select_previous_details_block(app: &mut Home) -> Option<Action> {
-    if app.details_panes.len() == 0 {
-        app.active_block = ActiveBlock::Traces;
+    if app.search_origin != ActiveBlock::Traces {
+        panel_search(app).pop();
+        if panel_search(app).is_empty() {
+            handle_search_exit(app);
+        }
 
-        return Some(Action::ActivateBlock(ActiveBlock::Traces));
+        return apply_panel_search(app);
     }
 
-    if app.details_tabs.contains(&app.details_block) {
+    app.search_query.pop();
+    if app.search_query.is_empty() {
+        handle_search_exit(app);
+    }
+
+    None
+}
+
+pub fn handle_search_exit(app: &mut Home) -> Option<Action> {
+    if app.active_block == ActiveBlock::Help {
+        app.help_filter.clear();
+        app.help_offset = 0;
         app.active_block = ActiveBlock::Traces;
 
-        return Some(Action::ActivateBlock(ActiveBlock::Traces));
+        return None;
     }
 
-    let mut iter = app.details_panes.iter().rev();
-
-    // advance iterator to the current block
-    iter.find(|&&v| app.details_block == v);
+    // the query stays applied and is shown again when reopened
+    if app.search_origin != ActiveBlock::Traces {
+        app.active_block = app.search_origin;
 
-    if let Some(next_pane) = iter.next() {
-        app.details_block = *next_pane;
+        return None;
+    }
 
-        None
+    if app.search_query.is_empty() {
+        app.last_search = None;
     } else {
-        app.details_block = *app
-            .details_tabs
-            .get(app.details_tab_index)
-            .unwrap_or(&DetailsPane::RequestDetails);
+        let query = app.search_query.clone();
 
-        None
+        app.last_search = Some(query.clone());
+        app.search_history.retain(|previous| previous != &query);
+        app.search_history.push(query);
+
+        if app.search_history.len() > SEARCH_HISTORY_LIMIT {
+            app.search_history.remove(0);
+        }
     }
+
+    app.active_block = ActiveBlock::Traces;
+
+    None
 }
 
-pub fn handle_details_tab_next(app: &mut Home) -> Option<Action> {
-    if app.details_tab_index == app.details_tabs.len() - 1 {
-        app.details_tab_index = 0;
-    } else {
-        app.details_tab_index += 1;
-    }
+pub fn handle_export_key_map(app: &mut Home) -> Option<Action> {
+    let message = match fs::write(KEY_MAP_EXPORT_FILE, format_key_map(&app.key_map)) {
+        Ok(_) => format!("Exported key map to {}", KEY_MAP_EXPORT_FILE),
+        Err(e) => format!("Failed to export key map: {}", e),
+    };
 
-    app.details_block = *app
-        .details_tabs
-        .get(app.details_tab_index)
-        .unwrap_or(&DetailsPane::RequestDetails);
+    app.status_message = Some(message);
 
     None
 }
 
-pub fn handle_details_tab_prev(app: &mut Home) -> Option<Action> {
-    if app.details_tab_index == 0 {
-        app.details_tab_index = app.details_tabs.len() - 1;
-    } else {
-        app.details_tab_index -= 1;
-    }
+pub fn handle_export_mock_stub(app: &mut Home) -> Option<Action> {
+    let trace = app.selected_trace.as_ref()?;
 
-    app.details_block = *app
-        .details_tabs
-        .get(app.details_tab_index)
-        .unwrap_or(&DetailsPane::RequestDetails);
+    let message = match fs::write(MOCK_STUB_EXPORT_FILE, to_mock_stub(trace)) {
+        Ok(_) => format!("Exported mock stub to {}", MOCK_STUB_EXPORT_FILE),
+        Err(e) => format!("Failed to export mock stub: {}", e),
+    };
+
+    app.status_message = Some(message);
 
     None
 }
 
-pub fn handle_yank(app: &mut Home, sender: Option<UnboundedSender<Action>>) -> Option<Action> {
-    if let Some(trace) = app.selected_trace.clone() {
-        match app.active_block {
-            ActiveBlock::Traces => {
-                let cmd = generate_curl_command(&trace);
+pub fn handle_toggle_time_column(app: &mut Home) -> Option<Action> {
+    app.time_column = match app.time_column {
+        TimeColumn::Relative => TimeColumn::Absolute,
+        TimeColumn::Absolute | TimeColumn::Hidden => TimeColumn::Relative,
+    };
 
-                match clippers::Clipboard::get().write_text(cmd) {
-                    Ok(_) => {
-                        app.status_message = Some(String::from("Request copied as cURL command!"));
-                    }
-                    Err(_) => {
-                        app.status_message = Some(String::from(
+    None
+}
+
+pub fn handle_toggle_compact(
+    app: &mut Home,
+    additional_metadata: HandlerMetadata,
+) -> Option<Action> {
+    app.compact = !app.compact;
+
+    // leaving compact mode shows a row less, keep the selection in view
+    let usable_height = page_height(app, &additional_metadata);
+
+    if app.main.index >= app.main.offset + usable_height {
+        app.main.offset = app.main.index + 1 - usable_height;
+    }
+
+    None
+}
+
+pub fn handle_cycle_details_sort(app: &mut Home) -> Option<Action> {
+    app.details_sort = match app.details_sort {
+        DetailsSort::NameAsc => DetailsSort::NameDesc,
+        DetailsSort::NameDesc => DetailsSort::Unsorted,
+        DetailsSort::Unsorted => DetailsSort::NameAsc,
+    };
+
+    app.status_message = Some(format!("Headers and query params: {}", app.details_sort));
+
+    // rebuild the details panes
+    Some(Action::SelectTrace(app.selected_trace.clone()))
+}
+
+pub fn handle_toggle_curl_query_flags(app: &mut Home) -> Option<Action> {
+    app.curl_query_flags = !app.curl_query_flags;
+
+    // rebuild the cURL pane
+    Some(Action::SelectTrace(app.selected_trace.clone()))
+}
+
+pub fn handle_toggle_redaction(app: &mut Home) -> Option<Action> {
+    app.redact.enabled = !app.redact.enabled;
+
+    // rebuild the cURL pane
+    Some(Action::SelectTrace(app.selected_trace.clone()))
+}
+
+// Only advances while something animates, so idle sessions stay unchanged.
+pub fn handle_tick(app: &mut Home) -> Option<Action> {
+    let is_animating = app.items.iter().any(|trace| {
+        trace
+            .http
+            .as_ref()
+            .is_some_and(|http| http.state == State::Sent)
+    });
+
+    if is_animating {
+        app.tick = app.tick.wrapping_add(1);
+    }
+
+    None
+}
+
+pub fn handle_show_hidden(app: &mut Home, additional_metadata: HandlerMetadata) -> Option<Action> {
+    app.show_hidden = !app.show_hidden;
+
+    let last_index = get_rendered_items(app).len().saturating_sub(1);
+
+    select_trace_at(app, app.main.index.min(last_index), additional_metadata)
+}
+
+// Pins the rendered list so incoming traces don't move it while inspecting.
+pub fn handle_toggle_freeze(
+    app: &mut Home,
+    additional_metadata: HandlerMetadata,
+) -> Option<Action> {
+    if !app.frozen {
+        app.frozen_ids = get_rendered_items(app)
+            .iter()
+            .map(|trace| trace.id.clone())
+            .collect();
+        app.frozen = true;
+
+        return None;
+    }
+
+    app.frozen = false;
+    app.frozen_ids.clear();
+
+    let selected_id = app.selected_trace.as_ref().map(|trace| trace.id.clone());
+
+    let index = get_rendered_items(app)
+        .iter()
+        .position(|trace| Some(&trace.id) == selected_id.as_ref())
+        .unwrap_or(0);
+
+    select_trace_at(app, index, additional_metadata)
+}
+
+// Narrows the list to the traces sharing the selected trace's correlation id,
+// toggling again lists every trace. The selected trace stays selected.
+pub fn handle_toggle_correlation_filter(
+    app: &mut Home,
+    additional_metadata: HandlerMetadata,
+) -> Option<Action> {
+    if !is_on_main_screen(app) {
+        return None;
+    }
+
+    if app.correlation_id.take().is_none() {
+        match app.selected_trace.as_ref().and_then(correlation_id) {
+            Some(id) => app.correlation_id = Some(id),
+            None => {
+                app.status_message = Some("No correlation id on the selected trace".to_string());
+
+                return None;
+            }
+        }
+    }
+
+    let selected_id = app.selected_trace.as_ref().map(|trace| trace.id.clone());
+
+    let index = get_rendered_items(app)
+        .iter()
+        .position(|trace| Some(&trace.id) == selected_id.as_ref())
+        .unwrap_or(0);
+
+    select_trace_at(app, index, additional_metadata)
+}
+
+fn select_trace_at(
+    app: &mut Home,
+    index: usize,
+    additional_metadata: HandlerMetadata,
+) -> Option<Action> {
+    let number_of_lines: u16 = get_rendered_items(app).len().try_into().unwrap_or(u16::MAX);
+
+    let usable_height = additional_metadata
+        .main_height
+        .saturating_sub(app.traces_unusable_vertical_space() as u16)
+        .max(1) as usize;
+
+    app.main.index = index;
+
+    if index < app.main.offset {
+        app.main.offset = index;
+    } else if index >= app.main.offset + usable_height {
+        app.main.offset = index + 1 - usable_height;
+    }
+
+    reset_request_and_response_body_ui_state(app);
+
+    set_content_length(app);
+
+    if (usable_height as u16) < number_of_lines {
+        let position = calculate_scrollbar_position(
+            number_of_lines,
+            app.main.offset,
+            number_of_lines.saturating_sub(usable_height as u16),
+        );
+
+        app.main.scroll_state = app.main.scroll_state.position(position.into());
+    } else {
+        app.main.scroll_state = app.main.scroll_state.position(0);
+    }
+
+    app.query_params_list.reset();
+
+    Some(Action::SelectTrace(get_currently_selected_trace(app)))
+}
+
+fn matching_indexes(app: &Home) -> Vec<usize> {
+    let maybe_re = search_regex(&app.search_query);
+
+    if maybe_re.is_none() {
+        return vec![];
+    }
+
+    get_rendered_items(app)
+        .iter()
+        .enumerate()
+        .filter(|(_, trace)| matches_search(trace, maybe_re.as_ref()))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+pub fn handle_next_match(app: &mut Home, additional_metadata: HandlerMetadata) -> Option<Action> {
+    if app.active_block != ActiveBlock::Traces {
+        return None;
+    }
+
+    let indexes = matching_indexes(app);
+
+    let next = indexes
+        .iter()
+        .find(|&&index| index > app.main.index)
+        .or(indexes.first())
+        .copied()?;
+
+    select_trace_at(app, next, additional_metadata)
+}
+
+pub fn handle_previous_match(
+    app: &mut Home,
+    additional_metadata: HandlerMetadata,
+) -> Option<Action> {
+    if app.active_block != ActiveBlock::Traces {
+        return None;
+    }
+
+    let indexes = matching_indexes(app);
+
+    let previous = indexes
+        .iter()
+        .rev()
+        .find(|&&index| index < app.main.index)
+        .or(indexes.last())
+        .copied()?;
+
+    select_trace_at(app, previous, additional_metadata)
+}
+
+fn error_indexes(app: &Home) -> Vec<usize> {
+    get_rendered_items(app)
+        .iter()
+        .enumerate()
+        .filter(|(_, trace)| is_error_trace(trace))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+fn is_on_main_screen(app: &Home) -> bool {
+    matches!(
+        app.active_block,
+        ActiveBlock::Traces
+            | ActiveBlock::Details
+            | ActiveBlock::RequestBody
+            | ActiveBlock::ResponseBody
+    )
+}
+
+pub fn handle_next_error(app: &mut Home, additional_metadata: HandlerMetadata) -> Option<Action> {
+    if !is_on_main_screen(app) {
+        return None;
+    }
+
+    let indexes = error_indexes(app);
+
+    let next = indexes
+        .iter()
+        .find(|&&index| index > app.main.index)
+        .or(indexes.first())
+        .copied()?;
+
+    select_trace_at(app, next, additional_metadata)
+}
+
+pub fn handle_previous_error(
+    app: &mut Home,
+    additional_metadata: HandlerMetadata,
+) -> Option<Action> {
+    if !is_on_main_screen(app) {
+        return None;
+    }
+
+    let indexes = error_indexes(app);
+
+    let previous = indexes
+        .iter()
+        .rev()
+        .find(|&&index| index < app.main.index)
+        .or(indexes.last())
+        .copied()?;
+
+    select_trace_at(app, previous, additional_metadata)
+}
+
+fn filter_input(app: &mut Home) -> Option<&mut String> {
+    match app.active_block {
+        ActiveBlock::Filter(FilterScreen::Key) => Some(&mut app.selected_filters.key),
+        ActiveBlock::Filter(FilterScreen::StatusRange) => {
+            Some(&mut app.selected_filters.status_range)
+        }
+        ActiveBlock::Filter(FilterScreen::SavePreset) => Some(&mut app.preset_name),
+        ActiveBlock::GoTo => Some(&mut app.goto_input),
+        _ => None,
+    }
+}
+
+pub fn handle_filter_input_push(app: &mut Home, c: char) -> Option<Action> {
+    if let Some(input) = filter_input(app) {
+        input.push(c);
+    }
+
+    None
+}
+
+pub fn handle_filter_input_pop(app: &mut Home) -> Option<Action> {
+    if let Some(input) = filter_input(app) {
+        input.pop();
+    }
+
+    None
+}
+
+pub fn handle_tab(app: &mut Home) -> Option<Action> {
+    if app.active_block == ActiveBlock::Traces && app.details_hidden {
+        return None;
+    }
+
+    if app.active_block == ActiveBlock::Traces {
+        return select_active_details_block(app);
+    }
+
+    if app.active_block == ActiveBlock::Details {
+        return select_next_details_block(app);
+    }
+
+    let next_block = match app.active_block {
+        ActiveBlock::Traces => ActiveBlock::Details,
+        ActiveBlock::Details => ActiveBlock::ResponseBody,
+        ActiveBlock::ResponseBody => ActiveBlock::RequestBody,
+        ActiveBlock::RequestBody => ActiveBlock::Traces,
+        ActiveBlock::Filter(screen) => match screen {
+            FilterScreen::Main => ActiveBlock::Filter(FilterScreen::Actions),
+            FilterScreen::Source => ActiveBlock::Filter(FilterScreen::Actions),
+            FilterScreen::Method => ActiveBlock::Filter(FilterScreen::Actions),
+            FilterScreen::Status => ActiveBlock::Filter(FilterScreen::Actions),
+            FilterScreen::Key => ActiveBlock::Filter(FilterScreen::Actions),
+            FilterScreen::StatusRange => ActiveBlock::Filter(FilterScreen::Actions),
+            FilterScreen::ContentType => ActiveBlock::Filter(FilterScreen::Actions),
+            FilterScreen::Presets => ActiveBlock::Filter(FilterScreen::Actions),
+            FilterScreen::SavePreset => ActiveBlock::Filter(FilterScreen::Presets),
+            FilterScreen::Actions => ActiveBlock::Filter(FilterScreen::Main),
+        },
+        ActiveBlock::Sort(screen) => match screen {
+            SortScreen::Source => ActiveBlock::Sort(SortScreen::Direction),
+            SortScreen::Direction => ActiveBlock::Sort(SortScreen::Actions),
+            SortScreen::Actions => ActiveBlock::Sort(SortScreen::Source),
+        },
+        _ => app.active_block,
+    };
+
+    if next_block != app.active_block {
+        app.active_block = next_block;
+
+        Some(Action::ActivateBlock(next_block))
+    } else {
+        None
+    }
+}
+
+pub fn handle_back_tab(app: &mut Home) -> Option<Action> {
+    if app.active_block == ActiveBlock::Traces && app.details_hidden {
+        return None;
+    }
+
+    if app.active_block == ActiveBlock::Details {
+        return select_previous_details_block(app);
+    }
+
+    let next_block = match app.active_block {
+        ActiveBlock::Traces => ActiveBlock::RequestBody,
+        ActiveBlock::Details => ActiveBlock::Traces,
+        ActiveBlock::RequestBody => ActiveBlock::ResponseBody,
+        ActiveBlock::ResponseBody => ActiveBlock::Details,
+        ActiveBlock::Filter(screen) => match screen {
+            FilterScreen::Main => ActiveBlock::Filter(FilterScreen::Actions),
+            FilterScreen::Source => ActiveBlock::Filter(FilterScreen::Main),
+            FilterScreen::Method => ActiveBlock::Filter(FilterScreen::Main),
+            FilterScreen::Status => ActiveBlock::Filter(FilterScreen::Main),
+            FilterScreen::Key => ActiveBlock::Filter(FilterScreen::Main),
+            FilterScreen::StatusRange => ActiveBlock::Filter(FilterScreen::Main),
+            FilterScreen::ContentType => ActiveBlock::Filter(FilterScreen::Main),
+            FilterScreen::Presets => ActiveBlock::Filter(FilterScreen::Main),
+            FilterScreen::SavePreset => ActiveBlock::Filter(FilterScreen::Presets),
+            FilterScreen::Actions => ActiveBlock::Filter(FilterScreen::Main),
+        },
+        ActiveBlock::Sort(screen) => match screen {
+            SortScreen::Source => ActiveBlock::Sort(SortScreen::Actions),
+            SortScreen::Direction => ActiveBlock::Sort(SortScreen::Source),
+            SortScreen::Actions => ActiveBlock::Sort(SortScreen::Direction),
+        },
+        _ => app.active_block,
+    };
+
+    if next_block != app.active_block {
+        app.active_block = next_block;
+
+        Some(Action::ActivateBlock(next_block))
+    } else {
+        None
+    }
+}
+
+pub fn select_active_details_block(app: &mut Home) -> Option<Action> {
+    // return to the pane that was focused last, if it is still shown
+    if app.details_panes.contains(&app.details_block) {
+        app.active_block = ActiveBlock::Details;
+
+        return None;
+    }
+
+    if let Some(active_tab) = app.details_tabs.get(app.details_tab_index) {
+        app.details_block = *active_tab;
+    } else {
+        if let Some(first_tab) = app.details_tabs.first() {
+            app.details_block = *first_tab;
+        }
+    }
+    app.active_block = ActiveBlock::Details;
+
+    None
+}
+
+pub fn select_next_details_block(app: &mut Home) -> Option<Action> {
+    // the tabs are selected, so advance to the first pane
+    if app.details_tabs.contains(&app.details_block) {
+        if let Some(first_pane) = app.details_panes.first() {
+            app.details_block = *first_pane;
+
+            return None;
+        }
+    }
+
+    let mut iter = app.details_panes.iter();
+
+    // advance iterator to the current block
+    iter.find(|&&v| app.details_block == v);
+
+    if let Some(next_pane) = iter.next() {
+        app.details_block = *next_pane;
+
+        None
+    } else {
+        app.active_block = ActiveBlock::ResponseBody;
+
+        Some(Action::ActivateBlock(ActiveBlock::ResponseBody))
+    }
+}
+
+pub fn select_previous_details_block(app: &mut Home) -> Option<Action> {
+    if app.details_panes.len() == 0 {
+        app.active_block = ActiveBlock::Traces;
+
+        return Some(Action::ActivateBlock(ActiveBlock::Traces));
+    }
+
+    if app.details_tabs.contains(&app.details_block) {
+        app.active_block = ActiveBlock::Traces;
+
+        return Some(Action::ActivateBlock(ActiveBlock::Traces));
+    }
+
+    let mut iter = app.details_panes.iter().rev();
+
+    // advance iterator to the current block
+    iter.find(|&&v| app.details_block == v);
+
+    if let Some(next_pane) = iter.next() {
+        app.details_block = *next_pane;
+
+        None
+    } else {
+        app.details_block = *app
+            .details_tabs
+            .get(app.details_tab_index)
+            .unwrap_or(&DetailsPane::RequestDetails);
+
+        None
+    }
+}
+
+pub fn handle_details_tab_next(app: &mut Home) -> Option<Action> {
+    if app.details_tabs.is_empty() {
+        return None;
+    }
+
+    if app.details_tab_index == app.details_tabs.len() - 1 {
+        app.details_tab_index = 0;
+    } else {
+        app.details_tab_index += 1;
+    }
+
+    app.details_block = *app
+        .details_tabs
+        .get(app.details_tab_index)
+        .unwrap_or(&DetailsPane::RequestDetails);
+
+    None
+}
+
+pub fn handle_details_tab_prev(app: &mut Home) -> Option<Action> {
+    if app.details_tabs.is_empty() {
+        return None;
+    }
+
+    if app.details_tab_index == 0 {
+        app.details_tab_index = app.details_tabs.len() - 1;
+    } else {
+        app.details_tab_index -= 1;
+    }
+
+    app.details_block = *app
+        .details_tabs
+        .get(app.details_tab_index)
+        .unwrap_or(&DetailsPane::RequestDetails);
+
+    None
+}
+
+pub fn handle_yank(app: &mut Home, sender: Option<UnboundedSender<Action>>) -> Option<Action> {
+    if let Some(trace) = app.selected_trace.clone() {
+        match app.active_block {
+            ActiveBlock::Traces => {
+                let cmd = generate_curl_command(&trace, app.curl_query_flags, app.redacted_keys());
+
+                match clippers::Clipboard::get().write_text(cmd) {
+                    Ok(_) => {
+                        app.status_message = Some(String::from("Request copied as cURL command!"));
+                    }
+                    Err(_) => {
+                        app.status_message = Some(String::from(
                             "Something went wrong while copying to the clipboard!",
                         ));
                     }
                 }
             }
-            ActiveBlock::ResponseBody => match trace.http.unwrap_or_default().response_body {
-                Some(body) => {
-                    match clippers::Clipboard::get().write_text(pretty_parse_body(&body).unwrap()) {
+            ActiveBlock::Details if app.details_block == DetailsPane::Curl => {
+                if !app.curl_command.is_empty() {
+                    match clippers::Clipboard::get().write_text(app.curl_command.clone()) {
                         Ok(_) => {
                             app.status_message =
-                                Some(String::from("Response body copied to clipboard."));
+                                Some(String::from("Request copied as cURL command!"));
                         }
                         Err(_) => {
                             app.status_message = Some(String::from(
@@ -638,8 +1268,23 @@ pub fn handle_yank(app: &mut Home, sender: Option<UnboundedSender<Action>>) -> O
                         }
                     }
                 }
-                None => {}
-            },
+            }
+            ActiveBlock::ResponseBody | ActiveBlock::BodyZoom(BodyScreen::Response) => {
+                match get_copied_response_body(&trace) {
+                    Some(body) => match clippers::Clipboard::get().write_text(body) {
+                        Ok(_) => {
+                            app.status_message =
+                                Some(String::from("Response body copied to clipboard."));
+                        }
+                        Err(_) => {
+                            app.status_message = Some(String::from(
+                                "Something went wrong while copying to the clipboard!",
+                            ));
+                        }
+                    },
+                    None => {}
+                }
+            }
             _ => {}
         };
 
@@ -647,48 +1292,286 @@ pub fn handle_yank(app: &mut Home, sender: Option<UnboundedSender<Action>>) -> O
             handler.abort();
         });
 
-        app.abort_handlers.clear();
+        app.abort_handlers.clear();
+
+        if let Some(s) = sender {
+            let thread_handler = tokio::spawn(async move {
+                sleep(Duration::from_millis(5000)).await;
+
+                s.send(Action::ClearStatusMessage)
+            });
+            app.abort_handlers.push(thread_handler.abort_handle());
+        }
+    }
+
+    None
+}
+
+pub fn handle_copy_as_markdown(
+    app: &mut Home,
+    sender: Option<UnboundedSender<Action>>,
+) -> Option<Action> {
+    if let Some(headers) = get_focused_headers(app) {
+        return copy_with_status_message(app, headers, "Headers copied to clipboard!", sender);
+    }
+
+    let trace = app.selected_trace.as_ref()?;
+
+    let summary = generate_markdown_summary(trace, app.redacted_keys());
+
+    copy_with_status_message(app, summary, "Request copied as Markdown summary!", sender)
+}
+
+pub fn handle_copy_url(app: &mut Home, sender: Option<UnboundedSender<Action>>) -> Option<Action> {
+    if app.active_block != ActiveBlock::Traces {
+        return None;
+    }
+
+    let url = get_selected_trace_url(app)?;
+
+    copy_with_status_message(app, url, "Request URL copied to clipboard!", sender)
+}
+
+// Lists every export format in the context menu, next to the selected trace.
+pub fn handle_open_export_menu(app: &mut Home) -> Option<Action> {
+    if app.active_block != ActiveBlock::Traces {
+        return None;
+    }
+
+    app.selected_trace.as_ref()?;
+
+    let items = ExportFormat::iter()
+        .map(|format| {
+            ActionableListItem::with_label(&format!("copy as {}", format))
+                .with_action(Action::CopyAs(format))
+        })
+        .collect();
+
+    let row = app.traces_area.y + app.traces_unusable_vertical_space() as u16 - 1
+        + app.main.index.saturating_sub(app.main.offset) as u16;
+
+    app.context_menu = ActionableList::with_items(items);
+    app.context_menu.next();
+    app.context_menu_anchor = (app.traces_area.x + 2, row);
+    app.active_block = ActiveBlock::ContextMenu;
+
+    None
+}
+
+pub fn handle_copy_as(
+    app: &mut Home,
+    format: ExportFormat,
+    sender: Option<UnboundedSender<Action>>,
+) -> Option<Action> {
+    let text = get_selected_trace_export(app, format)?;
+
+    let message = match format {
+        ExportFormat::Curl => "Request copied as cURL command!",
+        ExportFormat::Httpie => "Request copied as HTTPie command!",
+        ExportFormat::Fetch => "Request copied as fetch call!",
+        ExportFormat::HttpFile => "Request copied as .http file!",
+        ExportFormat::Markdown => "Request copied as Markdown summary!",
+    };
+
+    copy_with_status_message(app, text, message, sender)
+}
+
+fn copy_with_status_message(
+    app: &mut Home,
+    text: String,
+    message: &str,
+    sender: Option<UnboundedSender<Action>>,
+) -> Option<Action> {
+    match clippers::Clipboard::get().write_text(text) {
+        Ok(_) => {
+            app.status_message = Some(String::from(message));
+        }
+        Err(_) => {
+            app.status_message = Some(String::from(
+                "Something went wrong while copying to the clipboard!",
+            ));
+        }
+    }
+
+    app.abort_handlers.iter().for_each(|handler| {
+        handler.abort();
+    });
+
+    app.abort_handlers.clear();
+
+    if let Some(s) = sender {
+        let thread_handler = tokio::spawn(async move {
+            sleep(Duration::from_millis(5000)).await;
+
+            s.send(Action::ClearStatusMessage)
+        });
+        app.abort_handlers.push(thread_handler.abort_handle());
+    }
+
+    None
+}
+
+fn get_alert_message(trace: &Trace, alerts: &AlertConfig) -> Option<String> {
+    let http = trace.http.as_ref()?;
+
+    if alerts.on_server_error {
+        if let Some(status) = http.status {
+            if status.is_server_error() {
+                return Some(format!("⚠ {} {}", status.as_str(), http.uri));
+            }
+        }
+    }
+
+    match (alerts.slow_response_ms, http.duration) {
+        (Some(threshold), Some(duration)) if duration > threshold => {
+            Some(format!("⚠ slow response ({}ms) {}", duration, http.uri))
+        }
+        _ => None,
+    }
+}
+
+pub fn handle_alert(
+    app: &mut Home,
+    trace: &Trace,
+    sender: Option<UnboundedSender<Action>>,
+) -> Option<Action> {
+    let message = get_alert_message(trace, &app.alerts)?;
+
+    app.alert_message = Some(message);
+
+    if app.alerts.bell {
+        let mut out = stdout();
+
+        let _ = out.write_all(b"\x07").and_then(|_| out.flush());
+    }
+
+    app.alert_abort_handlers.iter().for_each(|handler| {
+        handler.abort();
+    });
+
+    app.alert_abort_handlers.clear();
+
+    if let Some(s) = sender {
+        let thread_handler = tokio::spawn(async move {
+            sleep(Duration::from_millis(5000)).await;
+
+            s.send(Action::ClearAlert)
+        });
+        app.alert_abort_handlers.push(thread_handler.abort_handle());
+    }
+
+    None
+}
+
+// Schedules a timeout for requests still waiting on a response. The timeout
+// is cancelled as soon as any later version of the trace arrives.
+pub fn handle_request_timeout(
+    app: &mut Home,
+    trace: &Trace,
+    sender: Option<UnboundedSender<Action>>,
+) {
+    let is_sent = trace
+        .http
+        .as_ref()
+        .is_some_and(|http| http.state == State::Sent);
+
+    if !is_sent {
+        if let Some(handler) = app.timeout_abort_handlers.remove(&trace.id) {
+            handler.abort();
+        }
+
+        return;
+    }
+
+    if app.timeout_abort_handlers.contains_key(&trace.id) {
+        return;
+    }
+
+    if let (Some(timeout), Some(s)) = (app.request_timeout, sender) {
+        let id = trace.id.clone();
+
+        let thread_handler = tokio::spawn(async move {
+            sleep(timeout).await;
+
+            s.send(Action::MarkTraceAsTimedOut(id))
+        });
+        app.timeout_abort_handlers
+            .insert(trace.id.clone(), thread_handler.abort_handle());
+    }
+}
+
+fn page_height(app: &Home, additional_metadata: &HandlerMetadata) -> usize {
+    additional_metadata
+        .main_height
+        .saturating_sub(app.traces_unusable_vertical_space() as u16)
+        .max(1) as usize
+}
 
-        if let Some(s) = sender {
-            let thread_handler = tokio::spawn(async move {
-                sleep(Duration::from_millis(5000)).await;
+pub fn handle_page_up(app: &mut Home, additional_metadata: HandlerMetadata) -> Option<Action> {
+    if app.active_block == ActiveBlock::Help {
+        app.help_offset = app
+            .help_offset
+            .saturating_sub(page_height(app, &additional_metadata));
 
-                s.send(Action::ClearStatusMessage)
-            });
-            app.abort_handlers.push(thread_handler.abort_handle());
-        }
+        return None;
     }
 
-    None
+    if app.active_block != ActiveBlock::Traces || get_rendered_items(app).is_empty() {
+        return None;
+    }
+
+    let page = page_height(app, &additional_metadata);
+
+    app.main.offset = app.main.offset.saturating_sub(page);
+
+    select_trace_at(
+        app,
+        app.main.index.saturating_sub(page),
+        additional_metadata,
+    )
 }
 
-pub fn handle_go_to_end(app: &mut Home, additional_metadata: HandlerMetadata) -> Option<Action> {
-    match app.active_block {
-        ActiveBlock::Traces => {
-            let number_of_lines: u16 = app.items.len().try_into().unwrap();
+pub fn handle_page_down(app: &mut Home, additional_metadata: HandlerMetadata) -> Option<Action> {
+    if app.active_block == ActiveBlock::Help {
+        let length = get_help_rows(app).len() + get_unmapped_actions(app).len();
 
-            let usubale_rect_space =
-                additional_metadata.main_height - NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE as u16;
+        app.help_offset = (app.help_offset + page_height(app, &additional_metadata))
+            .min(length.saturating_sub(1));
 
-            app.main.index = number_of_lines as usize - 1;
+        return None;
+    }
 
-            let has_overflown = number_of_lines > usubale_rect_space;
+    let length = get_rendered_items(app).len();
 
-            if has_overflown {
-                app.main.offset = (number_of_lines - usubale_rect_space) as usize;
+    if app.active_block != ActiveBlock::Traces || length == 0 {
+        return None;
+    }
 
-                let position = calculate_scrollbar_position(
-                    number_of_lines,
-                    app.main.offset,
-                    number_of_lines - usubale_rect_space,
-                );
+    let page = page_height(app, &additional_metadata);
 
-                app.main.scroll_state = app.main.scroll_state.position(position.into());
+    app.main.offset = (app.main.offset + page).min(length.saturating_sub(page));
 
-                reset_request_and_response_body_ui_state(app);
+    select_trace_at(
+        app,
+        (app.main.index + page).min(length - 1),
+        additional_metadata,
+    )
+}
+
+pub fn handle_go_to_end(app: &mut Home, additional_metadata: HandlerMetadata) -> Option<Action> {
+    match app.active_block {
+        ActiveBlock::Traces => {
+            let number_of_lines = get_rendered_items(app).len();
+
+            if number_of_lines == 0 {
+                return None;
             }
 
-            Some(Action::SelectTrace(get_currently_selected_trace(app)))
+            // Pin the last row to the bottom of the viewport.
+            app.main.offset =
+                number_of_lines.saturating_sub(page_height(app, &additional_metadata));
+
+            select_trace_at(app, number_of_lines - 1, additional_metadata)
         }
         ActiveBlock::RequestBody => {
             let content = get_content_length(app);
@@ -748,94 +1631,13 @@ pub fn handle_go_to_end(app: &mut Home, additional_metadata: HandlerMetadata) ->
             None
         }
         ActiveBlock::Details => match app.details_block {
-            DetailsPane::RequestDetails => {
-                if let Some(item) = app.selected_trace.clone() {
-                    if item.http.clone().unwrap_or_default().duration.is_some() {
-                        let item_length = item.http.unwrap_or_default().request_headers.len();
-
-                        let usable_height = additional_metadata
-                            .request_body_rectangle_height
-                            .checked_sub(REQUEST_HEADERS_UNUSABLE_VERTICAL_SPACE as u16)
-                            .unwrap_or_default();
-
-                        let requires_scrollbar = item_length as u16 >= usable_height;
-
-                        app.request_headers_list.previous();
-
-                        if requires_scrollbar {
-                            let current_index_hit_viewport_end =
-                                app.request_headers_list.scroll_state.offset() >= {
-                                    usable_height as usize
-                                };
-
-                            let offset_does_not_intersects_bottom_of_rect =
-                                (app.request_details.offset as u16 + usable_height)
-                                    < item_length as u16;
-
-                            if current_index_hit_viewport_end
-                                && offset_does_not_intersects_bottom_of_rect
-                            {
-                                app.request_details.offset = item_length - usable_height as usize;
-                            }
-
-                            let next_position = calculate_scrollbar_position(
-                                item_length as u16,
-                                app.request_details.offset,
-                                item_length as u16 - (usable_height),
-                            );
-
-                            app.request_details.scroll_state = app
-                                .request_details
-                                .scroll_state
-                                .position(next_position.into());
-                        }
-                    }
-                }
+            DetailsPane::RequestHeaders => {
+                app.request_headers_list.bottom();
 
                 None
             }
-            DetailsPane::ResponseDetails => {
-                if let Some(item) = &app.selected_trace {
-                    if item.http.clone().unwrap_or_default().duration.is_some() {
-                        let item_length =
-                            item.http.clone().unwrap_or_default().response_headers.len();
-
-                        let usable_height = additional_metadata.response_body_rectangle_height
-                            - RESPONSE_HEADERS_UNUSABLE_VERTICAL_SPACE as u16;
-
-                        let requires_scrollbar = item_length as u16 >= usable_height;
-
-                        app.response_headers_list.previous();
-
-                        if requires_scrollbar {
-                            let current_index_hit_viewport_end =
-                                app.response_headers_list.scroll_state.offset() >= {
-                                    usable_height as usize
-                                };
-
-                            let offset_does_not_intersects_bottom_of_rect =
-                                (app.response_details.offset as u16 + usable_height)
-                                    < item_length as u16;
-
-                            if current_index_hit_viewport_end
-                                && offset_does_not_intersects_bottom_of_rect
-                            {
-                                app.response_details.offset = item_length - usable_height as usize;
-                            }
-
-                            let next_position = calculate_scrollbar_position(
-                                item_length as u16,
-                                app.response_details.offset,
-                                item_length as u16 - (usable_height),
-                            );
-
-                            app.response_details.scroll_state = app
-                                .response_details
-                                .scroll_state
-                                .position(next_position.into());
-                        }
-                    }
-                }
+            DetailsPane::ResponseHeaders => {
+                app.response_headers_list.bottom();
 
                 None
             }
@@ -845,18 +1647,43 @@ pub fn handle_go_to_end(app: &mut Home, additional_metadata: HandlerMetadata) ->
     }
 }
 
-pub fn handle_go_to_start(app: &mut Home) -> Option<Action> {
-    match app.active_block {
-        ActiveBlock::Traces => {
-            app.main.index = 0;
+// The focused headers or query params table, the panes whose values get
+// clipped the most.
+fn focused_details_table(app: &mut Home) -> Option<&mut ActionableList> {
+    if app.active_block != ActiveBlock::Details {
+        return None;
+    }
 
-            app.main.offset = 0;
+    match app.details_block {
+        DetailsPane::QueryParams => Some(&mut app.query_params_list),
+        DetailsPane::RequestHeaders => Some(&mut app.request_headers_list),
+        DetailsPane::ResponseHeaders => Some(&mut app.response_headers_list),
+        _ => None,
+    }
+}
 
-            app.main.scroll_state = app.main.scroll_state.position(0);
+pub fn handle_left(app: &mut Home) -> Option<Action> {
+    if let Some(table) = focused_details_table(app) {
+        table.scroll_left(JSON_VIEWER_HORIZONTAL_SCROLL_STEP);
+    }
 
-            reset_request_and_response_body_ui_state(app);
+    None
+}
+
+pub fn handle_right(app: &mut Home) -> Option<Action> {
+    if let Some(table) = focused_details_table(app) {
+        table.scroll_right(JSON_VIEWER_HORIZONTAL_SCROLL_STEP);
+    }
+
+    None
+}
 
-            return Some(Action::SelectTrace(get_currently_selected_trace(app)));
+pub fn handle_go_to_start(app: &mut Home, additional_metadata: HandlerMetadata) -> Option<Action> {
+    match app.active_block {
+        ActiveBlock::Traces => {
+            app.main.offset = 0;
+
+            select_trace_at(app, 0, additional_metadata)
         }
         ActiveBlock::ResponseBody => {
             let content = get_content_length(app);
@@ -881,24 +1708,13 @@ pub fn handle_go_to_start(app: &mut Home) -> Option<Action> {
             None
         }
         ActiveBlock::Details => match app.details_block {
-            DetailsPane::RequestDetails => {
-                app.request_details.offset = 0;
-                app.request_headers_list.reset();
-
-                app.request_details.scroll_state = app.request_details.scroll_state.position(0);
+            DetailsPane::RequestHeaders => {
+                app.request_headers_list.top(0);
 
                 None
             }
-            DetailsPane::ResponseDetails => {
-                let c = get_content_length(app);
-
-                if c.response_headers.is_some() {
-                    app.response_details.offset = 0;
-                    app.response_headers_list.reset();
-
-                    app.response_details.scroll_state =
-                        app.response_details.scroll_state.position(0);
-                }
+            DetailsPane::ResponseHeaders => {
+                app.response_headers_list.top(0);
 
                 None
             }
@@ -909,38 +1725,364 @@ pub fn handle_go_to_start(app: &mut Home) -> Option<Action> {
 }
 
 pub fn handle_delete_item(app: &mut Home) -> Option<Action> {
-    let cloned_items = app.items.clone();
-    let items_as_vector = cloned_items.iter().collect::<Vec<&Trace>>();
-    let current_trace = items_as_vector.get(app.main.index).copied().unwrap();
-    let _ = &app.items.remove(current_trace);
+    if app.active_block == ActiveBlock::Filter(FilterScreen::Presets) {
+        return app
+            .filter_value_index
+            .checked_sub(1)
+            .and_then(|index| get_filter_preset_names(app).get(index).cloned())
+            .map(Action::DeleteFilterPreset);
+    }
+
+    if let Some(trace) = get_currently_selected_trace(app) {
+        app.items.remove(&trace);
+
+        // a deleted trace can't time out anymore
+        if let Some(handler) = app.timeout_abort_handlers.remove(&trace.id) {
+            handler.abort();
+        }
+    }
 
     None
 }
 
+// The trace under a position in the terminal, if it hits a row of the traces
+// list.
+pub fn get_trace_index_at(app: &Home, column: u16, row: u16) -> Option<usize> {
+    let area = app.traces_area;
+
+    // below the top border, the header and its margin
+    let first_row = area.y + app.traces_unusable_vertical_space() as u16 - 1;
+    let last_row = (area.y + area.height).saturating_sub(1);
+
+    if column < area.x || column >= area.x + area.width || row < first_row || row >= last_row {
+        return None;
+    }
+
+    let index = app.main.offset + usize::from(row - first_row);
+
+    (index < get_rendered_items(app).len()).then_some(index)
+}
+
+pub fn handle_open_context_menu(
+    app: &mut Home,
+    index: usize,
+    additional_metadata: HandlerMetadata,
+) -> Option<Action> {
+    let action = select_trace_at(app, index, additional_metadata);
+
+    let http = get_currently_selected_trace(app)?.http?;
+
+    let mut items = vec![
+        ActionableListItem::with_label(&format!("filter to {}", http.method))
+            .with_action(Action::FilterByMethod(http.method.to_string())),
+    ];
+
+    if let Some(status) = http.status {
+        let status_class = format!("{}xx", status.as_u16() / 100);
+
+        items.push(
+            ActionableListItem::with_label(&format!("filter to {}", status_class))
+                .with_action(Action::FilterByStatus(status_class)),
+        );
+    }
+
+    items.push(ActionableListItem::with_label("copy cURL").with_action(Action::CopyToClipBoard));
+    items.push(ActionableListItem::with_label("delete").with_action(Action::DeleteItem));
+
+    app.context_menu = ActionableList::with_items(items);
+    app.context_menu.next();
+    app.active_block = ActiveBlock::ContextMenu;
+
+    action
+}
+
+// Narrows both the applied and the edited filters to a single method.
+pub fn handle_filter_by_method(
+    app: &mut Home,
+    method: String,
+    additional_metadata: HandlerMetadata,
+) -> Option<Action> {
+    for filters in [&mut app.filters, &mut app.selected_filters] {
+        for method_filter in filters.method.values_mut() {
+            method_filter.selected = method_filter.method.as_str() == method;
+        }
+    }
+
+    app.main.offset = 0;
+
+    select_trace_at(app, 0, additional_metadata)
+}
+
+// Narrows both the applied and the edited filters to a single status class.
+pub fn handle_filter_by_status(
+    app: &mut Home,
+    status: String,
+    additional_metadata: HandlerMetadata,
+) -> Option<Action> {
+    for filters in [&mut app.filters, &mut app.selected_filters] {
+        for status_filter in filters.status.values_mut() {
+            status_filter.selected = status_filter.status == status;
+        }
+    }
+
+    app.main.offset = 0;
+
+    select_trace_at(app, 0, additional_metadata)
+}
+
 pub fn handle_general_status(app: &mut Home, s: String) -> Option<Action> {
     app.status_message = Some(s);
 
     None
 }
 
+fn toggle_applied_set(filter: &SourceFilter, value: &str, total: usize) -> SourceFilter {
+    match (value, filter) {
+        ("All", _) => SourceFilter::All,
+        (value, SourceFilter::All) => SourceFilter::Applied(HashSet::from([value.to_string()])),
+        (value, SourceFilter::Applied(applied)) => {
+            let mut set = applied.clone();
+
+            if !set.remove(value) {
+                set.insert(value.to_string());
+            }
+
+            if set.len() == total {
+                SourceFilter::All
+            } else {
+                SourceFilter::Applied(set)
+            }
+        }
+    }
+}
+
+pub fn handle_filter_invert(app: &mut Home) -> Option<Action> {
+    let maybe_screen = match app.active_block {
+        ActiveBlock::Filter(FilterScreen::Main) => FILTER_SOURCES
+            .get(app.filter_source_index)
+            .and_then(|source| get_filter_screen(source)),
+        ActiveBlock::Filter(screen) => Some(screen),
+        _ => None,
+    };
+
+    if let Some(
+        screen @ (FilterScreen::Method
+        | FilterScreen::Source
+        | FilterScreen::Status
+        | FilterScreen::Key
+        | FilterScreen::StatusRange
+        | FilterScreen::ContentType),
+    ) = maybe_screen
+    {
+        if !app.selected_filters.inverted.remove(&screen) {
+            app.selected_filters.inverted.insert(screen);
+        }
+    }
+
+    None
+}
+
+pub fn handle_save_filter_preset(app: &mut Home) -> Option<Action> {
+    let name = app.preset_name.trim().to_string();
+
+    if name.is_empty() {
+        app.status_message = Some("Preset name cannot be empty".to_string());
+
+        return None;
+    }
+
+    app.filter_presets
+        .insert(name.clone(), app.selected_filters.clone());
+    app.active_block = ActiveBlock::Filter(FilterScreen::Presets);
+
+    match presets::save(FILTER_PRESETS_FILE, &app.filter_presets) {
+        Ok(_) => app.status_message = Some(format!("Saved filter preset \"{}\"", name)),
+        Err(e) => app.status_message = Some(format!("Failed to save filter presets: {}", e)),
+    }
+
+    None
+}
+
+pub fn handle_annotate_trace(app: &mut Home) -> Option<Action> {
+    let trace = app.selected_trace.as_ref()?;
+
+    app.annotation_input = app.annotations.to_input(&annotations::trace_key(trace));
+    app.active_block = ActiveBlock::Annotate;
+
+    None
+}
+
+pub fn handle_annotation_input_push(app: &mut Home, c: char) -> Option<Action> {
+    app.annotation_input.push(c);
+
+    None
+}
+
+pub fn handle_annotation_input_pop(app: &mut Home) -> Option<Action> {
+    app.annotation_input.pop();
+
+    None
+}
+
+pub fn handle_save_annotation(app: &mut Home) -> Option<Action> {
+    let trace = app.selected_trace.clone()?;
+
+    app.annotations
+        .set(&annotations::trace_key(&trace), &app.annotation_input);
+    app.active_block = ActiveBlock::Traces;
+
+    if let Err(e) = annotations::save(ANNOTATIONS_FILE, &app.annotations) {
+        app.status_message = Some(format!("Failed to save annotations: {}", e));
+    }
+
+    // rebuild the details panes
+    Some(Action::SelectTrace(Some(trace)))
+}
+
+pub fn handle_open_go_to(app: &mut Home) -> Option<Action> {
+    app.goto_input.clear();
+    app.active_block = ActiveBlock::GoTo;
+
+    None
+}
+
+pub fn handle_go_to_trace(app: &mut Home, additional_metadata: HandlerMetadata) -> Option<Action> {
+    let id = app.goto_input.trim().to_string();
+
+    app.active_block = ActiveBlock::Traces;
+
+    let index = get_rendered_items(app)
+        .iter()
+        .position(|trace| trace.id == id);
+
+    match index {
+        Some(index) => select_trace_at(app, index, additional_metadata),
+        None => {
+            app.status_message = Some(format!("No trace with id \"{}\"", id));
+
+            None
+        }
+    }
+}
+
+pub fn handle_open_largest_responses(app: &mut Home) -> Option<Action> {
+    if !is_on_main_screen(app) {
+        return None;
+    }
+
+    app.largest_responses_index = 0;
+    app.active_block = ActiveBlock::LargestResponses;
+
+    None
+}
+
+pub fn handle_go_to_largest_response(
+    app: &mut Home,
+    additional_metadata: HandlerMetadata,
+) -> Option<Action> {
+    app.active_block = ActiveBlock::Traces;
+
+    let largest = get_largest_responses(app, LARGEST_RESPONSES_COUNT);
+
+    // The list changes as traces arrive or get deleted.
+    let position = app
+        .largest_responses_index
+        .min(largest.len().saturating_sub(1));
+    let (index, _, _) = largest.get(position).copied()?;
+
+    select_trace_at(app, index, additional_metadata)
+}
+
+// Writes the selected trace as received but redacted like the other exports,
+// it can be loaded back with `parse_raw_trace`. An existing file is kept.
+pub fn handle_save_trace(app: &mut Home, path: String) -> Option<Action> {
+    app.active_block = ActiveBlock::Traces;
+
+    let trace = match app.selected_trace.as_ref() {
+        Some(trace) if trace.http.is_some() => trace,
+        _ => {
+            app.status_message = Some("No trace selected to save".to_string());
+
+            return None;
+        }
+    };
+    let raw = trace.http.as_ref().map_or("", |http| http.raw.as_str());
+
+    let saved = redact_raw_trace(raw, app.redacted_keys())
+        .map_err(|e| e.to_string())
+        .and_then(|redacted| {
+            fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+                .and_then(|mut file| file.write_all(redacted.as_bytes()))
+                .map_err(|e| match e.kind() {
+                    ErrorKind::AlreadyExists => format!("{} already exists", path),
+                    _ => e.to_string(),
+                })
+        });
+
+    app.status_message = Some(match saved {
+        Ok(_) => format!("Saved trace {} to {}", trace.id, path),
+        Err(e) => format!("Failed to save trace: {}", e),
+    });
+
+    None
+}
+
+pub fn handle_apply_filter_preset(app: &mut Home, name: String) -> Option<Action> {
+    let preset = app.filter_presets.get(&name)?;
+
+    app.selected_filters = preset.clone();
+
+    Some(Action::UpdateFilter)
+}
+
+pub fn handle_delete_filter_preset(app: &mut Home, name: String) -> Option<Action> {
+    app.filter_presets.remove(&name)?;
+
+    app.filter_value_index = app.filter_value_index.min(app.filter_presets.len());
+
+    if let Err(e) = presets::save(FILTER_PRESETS_FILE, &app.filter_presets) {
+        app.status_message = Some(format!("Failed to save filter presets: {}", e));
+    }
+
+    None
+}
+
 pub fn handle_select(app: &mut Home) -> Option<Action> {
     match app.active_block {
+        ActiveBlock::ContextMenu => {
+            app.active_block = ActiveBlock::Traces;
+
+            app.context_menu.action()
+        }
         ActiveBlock::Sort(SortScreen::Source) => app.sort_sources.action(),
         ActiveBlock::Sort(SortScreen::Direction) => app.sort_directions.action(),
         ActiveBlock::Sort(SortScreen::Actions) => app.sort_actions.action(),
         ActiveBlock::Filter(FilterScreen::Actions) => app.filter_actions.action(),
         ActiveBlock::Filter(FilterScreen::Main) => {
-            let blocks = vec!["method", "source", "status"];
-
-            let maybe_selected_filter = blocks.iter().nth(app.filter_source_index).cloned();
+            let maybe_selected_filter = FILTER_SOURCES.iter().nth(app.filter_source_index).cloned();
 
             if let Some(selected_filter) = maybe_selected_filter {
-                let screen = match selected_filter {
-                    "method" => FilterScreen::Method,
-                    "source" => FilterScreen::Source,
-                    "status" => FilterScreen::Status,
-                    _ => FilterScreen::default(),
-                };
+                if selected_filter == "combine" {
+                    let combination = match app.selected_filters.combination {
+                        FilterCombination::And => FilterCombination::Or,
+                        FilterCombination::Or => FilterCombination::And,
+                    };
+
+                    app.selected_filters.combination = combination;
+
+                    return None;
+                }
+
+                if selected_filter == "window" {
+                    app.selected_filters.time_window = app.selected_filters.time_window.next();
+
+                    return None;
+                }
+
+                let screen = get_filter_screen(selected_filter).unwrap_or_default();
 
                 app.filter_value_screen = screen;
                 app.filter_value_index = 0;
@@ -950,6 +2092,53 @@ pub fn handle_select(app: &mut Home) -> Option<Action> {
             None
         }
 
+        ActiveBlock::Filter(FilterScreen::ContentType) => {
+            let mut content_types = vec!["All".to_string()];
+
+            content_types.extend(get_content_types_from_traces(app));
+
+            if let Some(filter) = content_types.get(app.filter_value_index).cloned() {
+                app.selected_filters.content_type = toggle_applied_set(
+                    &app.selected_filters.content_type,
+                    &filter,
+                    content_types.len() - 1,
+                );
+            };
+
+            reset_request_and_response_body_ui_state(app);
+
+            app.main.index = 0;
+
+            app.main.offset = 0;
+
+            app.main.scroll_state = app.main.scroll_state.position(0);
+
+            None
+        }
+        ActiveBlock::Filter(FilterScreen::Presets) => match app.filter_value_index.checked_sub(1) {
+            Some(index) => get_filter_preset_names(app)
+                .get(index)
+                .cloned()
+                .map(Action::ApplyFilterPreset),
+            None => {
+                app.preset_name.clear();
+                app.active_block = ActiveBlock::Filter(FilterScreen::SavePreset);
+
+                None
+            }
+        },
+        ActiveBlock::Filter(FilterScreen::SavePreset) => Some(Action::SaveFilterPreset),
+        ActiveBlock::Annotate => Some(Action::SaveAnnotation),
+        ActiveBlock::LargestResponses => Some(Action::GoToLargestResponse),
+        ActiveBlock::GoTo => match app.goto_input.trim().split_once(' ') {
+            Some(("save", path)) => Some(Action::SaveTrace(path.trim().to_string())),
+            _ => Some(Action::GoToTrace),
+        },
+        ActiveBlock::Filter(FilterScreen::Key) | ActiveBlock::Filter(FilterScreen::StatusRange) => {
+            app.active_block = ActiveBlock::Filter(FilterScreen::Main);
+
+            None
+        }
         ActiveBlock::Filter(FilterScreen::Status) => {
             let current_service = app
                 .selected_filters
@@ -1032,37 +2221,11 @@ pub fn handle_select(app: &mut Home) -> Option<Action> {
             let selected_filter = services.iter().nth(app.filter_value_index).cloned();
 
             if let Some(filter) = selected_filter {
-                match filter.as_str() {
-                    "All" => app.selected_filters.source = SourceFilter::All,
-                    source => match &app.selected_filters.source {
-                        SourceFilter::All => {
-                            let mut set = HashSet::new();
-
-                            set.insert(source.to_string());
-
-                            app.selected_filters.source = SourceFilter::Applied(set)
-                        }
-                        SourceFilter::Applied(applied_sources) => {
-                            if applied_sources.contains(&source.to_string()) {
-                                let mut set = applied_sources.clone();
-
-                                set.remove(source);
-
-                                app.selected_filters.source = SourceFilter::Applied(set)
-                            } else {
-                                let mut set = applied_sources.clone();
-
-                                set.insert(source.to_string());
-
-                                if set.len() == get_services_from_traces(app).len() {
-                                    app.selected_filters.source = SourceFilter::All
-                                } else {
-                                    app.selected_filters.source = SourceFilter::Applied(set)
-                                }
-                            }
-                        }
-                    },
-                }
+                app.selected_filters.source = toggle_applied_set(
+                    &app.selected_filters.source,
+                    &filter,
+                    get_services_from_traces(app).len(),
+                );
             };
 
             reset_request_and_response_body_ui_state(app);
@@ -1080,22 +2243,25 @@ pub fn handle_select(app: &mut Home) -> Option<Action> {
             app.main.scroll_state = app.main.scroll_state.content_length(length.into());
 
             None
-        },
-        ActiveBlock::Traces => {
-            app.active_block = ActiveBlock::Details;
+        }
+        ActiveBlock::Traces => match &app.traces_enter_action {
+            Some(action) => Some(action.clone()),
+            None => {
+                app.active_block = ActiveBlock::Details;
 
-            None
-        },
-        ActiveBlock::Details => {
-            match app.details_block {
-                DetailsPane::RequestDetails => app.request_details_list.action(),
-                DetailsPane::QueryParams => app.query_params_list.action(),
-                DetailsPane::RequestHeaders => app.request_headers_list.action(),
-                DetailsPane::ResponseDetails => app.response_details_list.action(),
-                DetailsPane::ResponseHeaders => app.response_headers_list.action(),
-                DetailsPane::Timing => app.timing_list.action(),
+                None
             }
         },
+        ActiveBlock::Details => match app.details_block {
+            DetailsPane::RequestDetails => app.request_details_list.action(),
+            DetailsPane::QueryParams => app.query_params_list.action(),
+            DetailsPane::RequestHeaders => app.request_headers_list.action(),
+            DetailsPane::ResponseDetails => app.response_details_list.action(),
+            DetailsPane::ResponseHeaders => app.response_headers_list.action(),
+            DetailsPane::Timing => app.timing_list.action(),
+            DetailsPane::Curl => app.curl_list.action(),
+            DetailsPane::Extra => app.extra_list.action(),
+        },
         _ => None,
     }
 }
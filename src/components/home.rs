@@ -1,32 +1,50 @@
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeSet, HashMap, VecDeque};
 use std::error::Error;
+use std::mem;
+use std::time::{Duration, Instant};
 
-use chrono::prelude::DateTime;
-use crossterm::event::{KeyCode, KeyEvent};
+use chrono::{DateTime, Local};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use http::{HeaderName, HeaderValue};
 use ratatui::{
     layout::Layout,
     prelude::{Constraint, Direction, Rect},
-    widgets::ListState,
+    widgets::{BorderType, ListState},
 };
+use regex::Regex;
+use serde_json::Value;
 use strum::IntoEnumIterator;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::task::AbortHandle;
 
 use crate::{
+    annotations::{self, Annotations},
     app::{
-        Action, ActiveBlock, DetailsPane, FilterScreen, Mode, SortDirection, SortScreen,
-        SortSource, TraceFilter, TraceSort, UIState, WebSocketInternalState,
+        Action, ActiveBlock, BodyScreen, DetailsPane, DetailsSort, FilterScreen, LogLevel, Mode,
+        SortDirection, SortScreen, SortSource, TimeColumn, TraceColumn, TraceFilter, TraceSort,
+        UIState, WebSocketInternalState,
     },
     components::actionable_list::{ActionableList, ActionableListItem},
     components::component::Component,
     components::handlers,
     components::jsonviewer,
-    config::{Colors, Config},
+    config::{
+        block_border, parse_hide_patterns, parse_trace_columns, AlertConfig, Colors, Config,
+        RedactConfig,
+    },
+    consts::{
+        ANNOTATIONS_FILE, COMPACT_NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE, FILTER_PRESETS_FILE,
+        NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE, TRACE_RATE_WINDOW,
+    },
+    parser::generate_curl_command,
+    presets::{self, FilterPresets},
     render,
-    services::websocket::{State, Trace},
+    services::websocket::{State, Trace, TraceKind},
     tui::{Event, Frame},
-    utils::parse_query_params,
+    utils::{
+        cache_status, content_type_mismatch, format_timestamp, parse_query_params,
+        parse_status_range, sort_details,
+    },
 };
 #[derive(Default)]
 pub struct Home {
@@ -36,30 +54,82 @@ pub struct Home {
     pub items: BTreeSet<Trace>,
     pub abort_handlers: Vec<AbortHandle>,
     pub search_query: String,
+    pub last_search: Option<String>,
+    pub search_history: Vec<String>,
+    pub search_history_index: Option<usize>,
+    // The block a search was opened from, searches outside the traces list
+    // edit `headers_search` or `body_search` instead of `search_query`.
+    pub search_origin: ActiveBlock,
+    pub headers_search: String,
+    pub body_search: String,
+    pub help_filter: String,
+    pub help_offset: usize,
+    pub hide_patterns: Vec<Regex>,
+    pub mask_query_params: Vec<String>,
+    pub show_hidden: bool,
+    pub frozen: bool,
+    pub frozen_ids: Vec<String>,
+    pub correlation_id: Option<String>,
+    // Newest trace timestamp when the traces list was last focused.
+    pub last_viewed_timestamp: Option<i64>,
+    pub time_column: TimeColumn,
+    pub compact: bool,
+    pub details_sort: DetailsSort,
+    pub time_format: String,
+    pub trace_columns: Vec<TraceColumn>,
+    pub border_type: BorderType,
+    pub curl_query_flags: bool,
+    pub redact: RedactConfig,
     pub main: UIState,
     pub response_body: UIState,
     pub request_body: UIState,
-    pub request_details: UIState,
-    pub response_details: UIState,
+    pub curl: UIState,
     pub is_first_render: bool,
-    pub logs: Vec<String>,
+    pub tick: usize,
+    // Debug log lines with the time they were logged at.
+    pub logs: Vec<(DateTime<Local>, String)>,
+    pub hide_log_timestamps: bool,
+    pub debug_level: LogLevel,
     pub mode: Mode,
     pub key_map: HashMap<KeyEvent, Action>,
     pub colors: Colors,
     pub status_message: Option<String>,
+    pub alerts: AlertConfig,
+    pub alert_message: Option<String>,
+    pub alert_abort_handlers: Vec<AbortHandle>,
+    pub request_timeout: Option<Duration>,
+    pub traces_enter_action: Option<Action>,
+    pub timeout_abort_handlers: HashMap<String, AbortHandle>,
     pub ws_status: String,
     pub wss_connected: bool,
     pub wss_connection_count: usize,
     pub wss_state: WebSocketInternalState,
+    // When the first client of the current connection connected.
+    pub connected_since: Option<Instant>,
+    // Arrival times of the traces within the rate window, oldest first.
+    pub trace_arrivals: VecDeque<Instant>,
     pub request_json_viewer: jsonviewer::JSONViewer,
     pub response_json_viewer: jsonviewer::JSONViewer,
     pub selected_trace: Option<Trace>,
     pub filter_actions: ActionableList,
+    pub context_menu: ActionableList,
+    // Where the context menu was opened, its top left corner.
+    pub context_menu_anchor: (u16, u16),
+    // Last rendered area of the traces list, for mouse hit testing.
+    pub traces_area: Rect,
+    // Gives the traces list the whole screen.
+    pub details_hidden: bool,
     pub filters: TraceFilter,
     pub selected_filters: TraceFilter,
     pub filter_source_index: usize,
     pub filter_value_index: usize,
     pub filter_value_screen: FilterScreen,
+    pub filter_presets: FilterPresets,
+    pub preset_name: String,
+    pub annotations: Annotations,
+    pub annotation_input: String,
+    pub goto_input: String,
+    pub largest_responses_index: usize,
     pub sort: TraceSort,
     pub selected_sort: TraceSort,
     pub sort_actions: ActionableList,
@@ -76,27 +146,86 @@ pub struct Home {
     pub response_details_list: ActionableList,
     pub response_headers_list: ActionableList,
     pub timing_list: ActionableList,
+    pub curl_list: ActionableList,
+    pub extra_list: ActionableList,
+    pub curl_command: String,
+}
+
+// Splits the panes into tabs and popped-out panes, keeping the configured
+// order of the popped-out ones and dropping duplicates.
+fn split_details_panes(popped_out: &[DetailsPane]) -> (Vec<DetailsPane>, Vec<DetailsPane>) {
+    let mut details_panes: Vec<DetailsPane> = vec![];
+
+    for pane in popped_out {
+        if !details_panes.contains(pane) {
+            details_panes.push(*pane);
+        }
+    }
+
+    // the extra pane is added once a trace with extra fields is selected
+    let details_tabs = DetailsPane::iter()
+        .filter(|pane| *pane != DetailsPane::Extra && !details_panes.contains(pane))
+        .collect();
+
+    (details_tabs, details_panes)
 }
 
 impl Home {
     pub fn new() -> Result<Home, Box<dyn Error>> {
         let config = Config::new()?;
+        let border_type = block_border(&config);
+        let (details_tabs, details_panes) = split_details_panes(&config.details_panes);
+        let (trace_columns, mut logs) = parse_trace_columns(&config.columns);
+        let (hide_patterns, hide_warnings) = parse_hide_patterns(&config.hide_patterns);
+
+        logs.extend(hide_warnings);
+        logs.extend(config.warnings.iter().cloned());
+
+        let logs = logs
+            .iter()
+            .map(|warning| (Local::now(), format!("WARN {}", warning)))
+            .collect();
 
         let home = Home {
+            trace_columns,
+            logs,
             key_map: config.mapping.0,
             colors: config.colors.clone(),
+            alerts: config.alerts.clone(),
+            redact: config.redact.clone(),
+            mask_query_params: config.mask_query_params.clone(),
+            traces_enter_action: config.traces_enter_action.clone(),
+            request_timeout: match config.request_timeout_seconds {
+                0 => None,
+                seconds => Some(Duration::from_secs(seconds)),
+            },
+            time_column: config.time_column,
+            compact: config.compact,
+            details_sort: config.details_sort,
+            time_format: config.time_format.clone(),
+            border_type,
+            hide_patterns,
             request_json_viewer: jsonviewer::JSONViewer::new(
                 ActiveBlock::RequestBody,
                 4,
                 "Request body",
                 config.colors.clone(),
-            )?,
+            )?
+            .with_line_numbers(config.json_viewer.line_numbers)
+            .with_auto_expand_nodes(config.json_viewer.auto_expand_nodes)
+            .with_border_type(border_type),
             response_json_viewer: jsonviewer::JSONViewer::new(
                 ActiveBlock::ResponseBody,
                 4,
                 "Response body",
                 config.colors.clone(),
-            )?,
+            )?
+            .with_line_numbers(config.json_viewer.line_numbers)
+            .with_auto_expand_nodes(config.json_viewer.auto_expand_nodes)
+            .with_focus_envelope_data(config.json_viewer.focus_envelope_data)
+            .with_border_type(border_type),
+            filter_presets: presets::load(FILTER_PRESETS_FILE).unwrap_or_default(),
+            annotations: annotations::load(ANNOTATIONS_FILE).unwrap_or_default(),
             filter_actions: ActionableList::with_items(vec![ActionableListItem::with_label(
                 "apply",
             )
@@ -112,6 +241,8 @@ impl Home {
                     .with_action(Action::SelectSortSource(SortSource::Url)),
                 ActionableListItem::with_label(SortSource::Duration.as_ref())
                     .with_action(Action::SelectSortSource(SortSource::Duration)),
+                ActionableListItem::with_label(SortSource::HeaderCount.as_ref())
+                    .with_action(Action::SelectSortSource(SortSource::HeaderCount)),
                 ActionableListItem::with_label(SortSource::Timestamp.as_ref())
                     .with_action(Action::SelectSortSource(SortSource::Timestamp)),
             ])
@@ -126,14 +257,26 @@ impl Home {
             sort_actions: ActionableList::with_items(vec![
                 ActionableListItem::with_label("apply").with_action(Action::UpdateSort)
             ]),
-            details_tabs: DetailsPane::iter().collect(),
-            details_panes: vec![],
+            details_tabs,
+            details_panes,
             ..Self::default()
         };
 
         Ok(home)
     }
 
+    fn record_trace_arrival(&mut self, now: Instant) {
+        self.trace_arrivals.push_back(now);
+
+        while self
+            .trace_arrivals
+            .front()
+            .is_some_and(|arrival| now.saturating_duration_since(*arrival) > TRACE_RATE_WINDOW)
+        {
+            self.trace_arrivals.pop_front();
+        }
+    }
+
     fn mark_trace_as_timed_out(&mut self, id: String) {
         let selected_trace = self.items.iter().find(|trace| trace.id == id);
 
@@ -147,11 +290,31 @@ impl Home {
                 http_trace.status = None;
                 http_trace.response_body = Some("TIMEOUT WAITING FOR RESPONSE".to_string());
                 http_trace.pretty_response_body = Some("TIMEOUT WAITING FOR RESPONSE".to_string());
-                self.items.replace(selected_trace);
+                self.items.replace(Trace {
+                    http: Some(http_trace),
+                    ..selected_trace
+                });
             };
         }
     }
 
+    // Rows of the traces table taken up by its borders and header.
+    pub fn traces_unusable_vertical_space(&self) -> usize {
+        if self.compact {
+            COMPACT_NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE
+        } else {
+            NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE
+        }
+    }
+
+    pub fn redacted_keys(&self) -> &[String] {
+        if self.redact.enabled {
+            &self.redact.keys
+        } else {
+            &[]
+        }
+    }
+
     fn reset_active_pane(&mut self, pane: DetailsPane) {
         match pane {
             DetailsPane::QueryParams => self.query_params_list.reset(),
@@ -160,6 +323,71 @@ impl Home {
             DetailsPane::ResponseDetails => self.response_details_list.reset(),
             DetailsPane::ResponseHeaders => self.response_headers_list.reset(),
             DetailsPane::Timing => {}
+            DetailsPane::Curl => self.curl.reset_scroll(),
+            DetailsPane::Extra => self.extra_list.reset(),
+        }
+    }
+
+    fn details_lists(&mut self) -> [&mut ActionableList; 8] {
+        [
+            &mut self.request_details_list,
+            &mut self.query_params_list,
+            &mut self.request_headers_list,
+            &mut self.response_details_list,
+            &mut self.response_headers_list,
+            &mut self.timing_list,
+            &mut self.curl_list,
+            &mut self.extra_list,
+        ]
+    }
+
+    fn details_list_cursors(&mut self) -> Vec<(ListState, usize)> {
+        self.details_lists()
+            .iter()
+            .map(|list| (list.scroll_state.clone(), list.horizontal_offset))
+            .collect()
+    }
+
+    fn restore_details_list_cursors(&mut self, cursors: Vec<(ListState, usize)>) {
+        for (list, (cursor, horizontal_offset)) in self.details_lists().into_iter().zip(cursors) {
+            if cursor
+                .selected()
+                .is_some_and(|index| index < list.items.len())
+            {
+                list.scroll_state = cursor;
+            }
+            list.horizontal_offset = horizontal_offset;
+        }
+    }
+
+    fn is_typing_filter(&self) -> bool {
+        self.active_block == ActiveBlock::Filter(FilterScreen::Key)
+            || self.active_block == ActiveBlock::Filter(FilterScreen::StatusRange)
+            || self.active_block == ActiveBlock::Filter(FilterScreen::SavePreset)
+    }
+
+    // The extra pane is only listed while the selected trace has extra fields.
+    fn sync_extra_pane(&mut self) {
+        let has_extra = self
+            .selected_trace
+            .as_ref()
+            .is_some_and(|trace| !trace.extra.is_empty());
+        let is_listed = self.details_tabs.contains(&DetailsPane::Extra)
+            || self.details_panes.contains(&DetailsPane::Extra);
+
+        if has_extra && !is_listed {
+            self.details_tabs.push(DetailsPane::Extra);
+        } else if !has_extra && is_listed {
+            self.details_tabs.retain(|&pane| pane != DetailsPane::Extra);
+            self.details_panes
+                .retain(|&pane| pane != DetailsPane::Extra);
+            self.details_tab_index = self
+                .details_tab_index
+                .min(self.details_tabs.len().saturating_sub(1));
+
+            if self.details_block == DetailsPane::Extra {
+                self.details_block = self.details_tabs.first().copied().unwrap_or_default();
+            }
         }
     }
 
@@ -168,10 +396,7 @@ impl Home {
             // REQUEST DETAILS PANE
             let mut rows: Vec<ActionableListItem> = vec![];
 
-            let sent = DateTime::from_timestamp(trace.timestamp, 0)
-                .unwrap_or_default()
-                .format("%Y-%m-%d @ %H:%M:%S")
-                .to_string();
+            let sent = format_timestamp(trace.timestamp, &self.time_format);
             let host = trace.service_name.clone().unwrap_or(format!(""));
             let path = trace.http.clone().map_or("".to_string(), |http| http.path);
             let port = trace.http.clone().map_or("".to_string(), |http| http.port);
@@ -180,6 +405,28 @@ impl Home {
             rows.push(ActionableListItem::with_labelled_value("host", &host));
             rows.push(ActionableListItem::with_labelled_value("path", &path));
             rows.push(ActionableListItem::with_labelled_value("port", &port));
+            if trace.kind() == TraceKind::Grpc {
+                if let Some(method) = trace.grpc_method() {
+                    rows.push(ActionableListItem::with_labelled_value(
+                        "grpc method",
+                        &method,
+                    ));
+                }
+            }
+            if let Some(remote_address) = trace.http.clone().and_then(|http| http.remote_address) {
+                rows.push(ActionableListItem::with_labelled_value(
+                    "remote address",
+                    &remote_address,
+                ));
+            }
+            let annotation_key = annotations::trace_key(trace);
+
+            if let Some(note) = self.annotations.notes.get(&annotation_key) {
+                rows.push(ActionableListItem::with_labelled_value("note", note));
+            }
+            if let Some(tags) = self.annotations.format_tags(&annotation_key) {
+                rows.push(ActionableListItem::with_labelled_value("tags", &tags));
+            }
             // add available actions to the item list
             if self.details_tabs.contains(&DetailsPane::RequestDetails) {
                 rows.push(
@@ -196,21 +443,10 @@ impl Home {
             self.request_details_list = ActionableList::with_items(rows);
 
             // QUERY PARAMS PANE
-            let mut raw_params = parse_query_params(
-                trace
-                    .http
-                    .clone()
-                    .expect("Missing http from trace")
-                    .uri
-                    .to_string(),
-            );
+            let mut raw_params =
+                parse_query_params(trace.http.clone().map_or("".to_string(), |http| http.uri));
 
-            raw_params.sort_by(|a, b| {
-                let (name_a, _) = a;
-                let (name_b, _) = b;
-
-                name_a.cmp(name_b)
-            });
+            sort_details(&mut raw_params, self.details_sort, |(name, _)| name.clone());
 
             let mut next_items: Vec<ActionableListItem> = raw_params
                 .into_iter()
@@ -235,10 +471,7 @@ impl Home {
             // RESPONSE DETAILS PANE
             let mut items: Vec<ActionableListItem> = vec![];
 
-            let received = DateTime::from_timestamp(trace.timestamp, 0)
-                .unwrap_or_default()
-                .format("%Y-%m-%d @ %H:%M:%S")
-                .to_string();
+            let received = format_timestamp(trace.timestamp, &self.time_format);
             let status = trace.http.clone().map_or(None, |http| http.status).map_or(
                 "".to_string(),
                 |status| {
@@ -262,6 +495,39 @@ impl Home {
             items.push(ActionableListItem::with_labelled_value(
                 "duration", &duration,
             ));
+            if let Some(explanation) = trace
+                .http
+                .as_ref()
+                .and_then(|http| http.state.explanation())
+            {
+                items.push(ActionableListItem::with_labelled_value(
+                    "state",
+                    explanation,
+                ));
+            }
+            if let Some(tls_version) = trace.http.clone().and_then(|http| http.tls_version) {
+                items.push(ActionableListItem::with_labelled_value("tls", &tls_version));
+            }
+            if let Some(cache) = trace
+                .http
+                .as_ref()
+                .and_then(|http| cache_status(&http.response_headers))
+            {
+                items.push(ActionableListItem::with_labelled_value("cache", &cache));
+            }
+            if let Some(mismatch) = content_type_mismatch(trace) {
+                items.push(ActionableListItem::with_labelled_value(
+                    "warning", &mismatch,
+                ));
+            }
+            if trace.kind() == TraceKind::Grpc {
+                if let Some(grpc_status) = trace.grpc_status() {
+                    items.push(ActionableListItem::with_labelled_value(
+                        "grpc status",
+                        &grpc_status,
+                    ));
+                }
+            }
 
             if self.details_tabs.contains(&DetailsPane::ResponseDetails) {
                 items.push(
@@ -277,14 +543,26 @@ impl Home {
 
             self.response_details_list = ActionableList::with_items(items);
 
+            // a search from a headers pane narrows both headers panes
+            let headers_search = self.headers_search.to_lowercase();
+            let matches_headers_search = |name: &HeaderName, value: &HeaderValue| {
+                headers_search.is_empty()
+                    || name.as_str().contains(&headers_search)
+                    || value
+                        .to_str()
+                        .unwrap_or_default()
+                        .to_lowercase()
+                        .contains(&headers_search)
+            };
+
             // REQUEST HEADERS PANE
             let headers = trace.http.clone().unwrap_or_default().request_headers;
-            let mut parsed_headers = headers.iter().collect::<Vec<(&HeaderName, &HeaderValue)>>();
-            parsed_headers.sort_by(|a, b| {
-                let (name_a, _) = a;
-                let (name_b, _) = b;
-
-                name_a.to_string().cmp(&name_b.to_string())
+            let mut parsed_headers = headers
+                .iter()
+                .filter(|(name, value)| matches_headers_search(name, value))
+                .collect::<Vec<(&HeaderName, &HeaderValue)>>();
+            sort_details(&mut parsed_headers, self.details_sort, |(name, _)| {
+                name.to_string()
             });
             let mut next_items: Vec<ActionableListItem> = parsed_headers
                 .into_iter()
@@ -313,12 +591,12 @@ impl Home {
 
             // RESPONSE HEADERS PANE
             let headers = trace.http.clone().unwrap_or_default().response_headers;
-            let mut parsed_headers = headers.iter().collect::<Vec<(&HeaderName, &HeaderValue)>>();
-            parsed_headers.sort_by(|a, b| {
-                let (name_a, _) = a;
-                let (name_b, _) = b;
-
-                name_a.to_string().cmp(&name_b.to_string())
+            let mut parsed_headers = headers
+                .iter()
+                .filter(|(name, value)| matches_headers_search(name, value))
+                .collect::<Vec<(&HeaderName, &HeaderValue)>>();
+            sort_details(&mut parsed_headers, self.details_sort, |(name, _)| {
+                name.to_string()
             });
             let mut next_items: Vec<ActionableListItem> = parsed_headers
                 .into_iter()
@@ -347,7 +625,7 @@ impl Home {
             self.response_headers_list = ActionableList::with_items(next_items);
 
             // TIMING PANE
-            let next_items: Vec<ActionableListItem> = vec![
+            let mut next_items: Vec<ActionableListItem> = vec![
                 ActionableListItem::with_label("blocked"),
                 ActionableListItem::with_label("DNS"),
                 ActionableListItem::with_label("connecting"),
@@ -357,7 +635,61 @@ impl Home {
                 ActionableListItem::with_label("receiving"),
             ];
 
+            if let Some(timings) = trace.http.as_ref().and_then(|http| http.timings.as_ref()) {
+                next_items.push(ActionableListItem::with_labelled_value(
+                    "total",
+                    &format!("{:.2}", timings.duration()),
+                ));
+            }
+
             self.timing_list = ActionableList::with_items(next_items);
+
+            // CURL PANE
+            self.curl_command = if trace.http.is_some() {
+                generate_curl_command(trace, self.curl_query_flags, self.redacted_keys())
+            } else {
+                String::new()
+            };
+            self.curl.reset_scroll();
+
+            // the command itself is scrolled, so the actions are always selected
+            let next_items = vec![if self.details_tabs.contains(&DetailsPane::Curl) {
+                ActionableListItem::with_labelled_value("actions", "pop-out [↗]")
+                    .with_action(Action::PopOutDetailsTab(DetailsPane::Curl))
+            } else {
+                ActionableListItem::with_labelled_value("actions", "close [x]")
+                    .with_action(Action::CloseDetailsPane(DetailsPane::Curl))
+            }];
+
+            self.curl_list = ActionableList::with_items(next_items)
+                .with_scroll_state(ListState::default().with_selected(Some(0)));
+
+            // EXTRA PANE
+            let mut extra = trace.extra.iter().collect::<Vec<_>>();
+
+            sort_details(&mut extra, self.details_sort, |(name, _)| name.to_string());
+
+            let mut next_items: Vec<ActionableListItem> = extra
+                .into_iter()
+                .map(|(label, value)| match value {
+                    Value::String(value) => ActionableListItem::with_labelled_value(label, value),
+                    value => ActionableListItem::with_labelled_value(label, &value.to_string()),
+                })
+                .collect();
+
+            if self.details_tabs.contains(&DetailsPane::Extra) {
+                next_items.push(
+                    ActionableListItem::with_labelled_value("actions", "pop-out [↗]")
+                        .with_action(Action::PopOutDetailsTab(DetailsPane::Extra)),
+                )
+            } else {
+                next_items.push(
+                    ActionableListItem::with_labelled_value("actions", "close [x]")
+                        .with_action(Action::CloseDetailsPane(DetailsPane::Extra)),
+                )
+            };
+
+            self.extra_list = ActionableList::with_items(next_items);
         }
     }
 }
@@ -393,14 +725,103 @@ impl Component for Home {
             match key.code {
                 KeyCode::Enter | KeyCode::Esc => return Ok(Some(Action::ExitSearch)),
                 KeyCode::Backspace => return Ok(Some(Action::DeleteSearchQuery)),
+                KeyCode::Up => return Ok(Some(Action::SearchHistoryPrevious)),
+                KeyCode::Down => return Ok(Some(Action::SearchHistoryNext)),
+                KeyCode::Char(char) => return Ok(Some(Action::UpdateSearchQuery(char))),
+                _ => return Ok(None),
+            }
+        }
+        if self.active_block == ActiveBlock::Help {
+            match key.code {
+                KeyCode::Esc => return Ok(Some(Action::ExitSearch)),
+                KeyCode::Backspace => return Ok(Some(Action::DeleteSearchQuery)),
                 KeyCode::Char(char) => return Ok(Some(Action::UpdateSearchQuery(char))),
                 _ => return Ok(None),
             }
         }
+        if self.is_typing_filter() {
+            match key.code {
+                KeyCode::Esc => {
+                    let screen = match self.active_block {
+                        ActiveBlock::Filter(FilterScreen::SavePreset) => FilterScreen::Presets,
+                        _ => FilterScreen::Main,
+                    };
+
+                    return Ok(Some(Action::ActivateBlock(ActiveBlock::Filter(screen))));
+                }
+                KeyCode::Backspace => return Ok(Some(Action::DeleteFilterInput)),
+                KeyCode::Char(char) => return Ok(Some(Action::UpdateFilterInput(char))),
+                _ => return Ok(None),
+            }
+        }
+        if self.active_block == ActiveBlock::Annotate {
+            match key.code {
+                KeyCode::Esc => return Ok(Some(Action::ActivateBlock(ActiveBlock::Traces))),
+                KeyCode::Backspace => return Ok(Some(Action::DeleteAnnotationInput)),
+                KeyCode::Char(char) => return Ok(Some(Action::UpdateAnnotationInput(char))),
+                _ => return Ok(None),
+            }
+        }
+        if self.active_block == ActiveBlock::GoTo {
+            match key.code {
+                KeyCode::Esc => return Ok(Some(Action::ActivateBlock(ActiveBlock::Traces))),
+                KeyCode::Backspace => return Ok(Some(Action::DeleteFilterInput)),
+                KeyCode::Char(char) => return Ok(Some(Action::UpdateFilterInput(char))),
+                _ => return Ok(None),
+            }
+        }
         Ok(None)
     }
 
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Option<Action>, Box<dyn Error>> {
+        let on_main_screen = matches!(
+            self.active_block,
+            ActiveBlock::Traces
+                | ActiveBlock::Details
+                | ActiveBlock::RequestBody
+                | ActiveBlock::ResponseBody
+                | ActiveBlock::ContextMenu
+        );
+
+        if mouse.kind != MouseEventKind::Down(MouseButton::Right) || !on_main_screen {
+            return Ok(None);
+        }
+
+        match handlers::get_trace_index_at(self, mouse.column, mouse.row) {
+            Some(index) => {
+                self.context_menu_anchor = (mouse.column, mouse.row);
+
+                Ok(Some(Action::OpenContextMenu(index)))
+            }
+            None => Ok(None),
+        }
+    }
+
     fn update(&mut self, action: Action) -> Result<Option<Action>, Box<dyn Error>> {
+        // Characters typed into a text input should not trigger their key bindings,
+        // neither should Esc which closes the input itself. Other keys such as
+        // page down keep working. Navigation carries the key it was sent for.
+        let is_input_key = |key: &KeyEvent| matches!(key.code, KeyCode::Char(_) | KeyCode::Esc);
+        let is_typed = match &action {
+            Action::NavigateUp(Some(key))
+            | Action::NavigateDown(Some(key))
+            | Action::NavigateLeft(Some(key))
+            | Action::NavigateRight(Some(key)) => is_input_key(key),
+            _ => self
+                .key_map
+                .iter()
+                .any(|(key, mapped)| mapped == &action && is_input_key(key)),
+        };
+        let is_typing = self.active_block == ActiveBlock::SearchQuery
+            || self.active_block == ActiveBlock::Help
+            || self.active_block == ActiveBlock::Annotate
+            || self.active_block == ActiveBlock::GoTo
+            || self.is_typing_filter();
+
+        if is_typing && action != Action::Select && is_typed {
+            return Ok(None);
+        }
+
         self.request_json_viewer.update(action.clone())?;
         self.response_json_viewer.update(action.clone())?;
 
@@ -416,7 +837,9 @@ impl Component for Home {
             })
             .clone();
 
-        match action {
+        let was_viewing_traces = self.active_block == ActiveBlock::Traces;
+
+        let result = match action {
             Action::Quit => {
                 let last_block = self.previous_blocks.pop();
 
@@ -432,6 +855,10 @@ impl Component for Home {
             Action::OnMount => Ok(handlers::handle_adjust_scroll_bar(self, metadata)),
             Action::Help => Ok(handlers::handle_help(self)),
             Action::ToggleDebug => Ok(handlers::handle_debug(self)),
+            Action::CycleLogLevel => Ok(handlers::handle_cycle_log_level(self)),
+            Action::ToggleLogTimestamps => Ok(handlers::handle_toggle_log_timestamps(self)),
+            Action::ToggleBodyZoom => Ok(handlers::handle_body_zoom(self)),
+            Action::ToggleDetails => Ok(handlers::handle_toggle_details(self)),
             Action::Select => Ok(handlers::handle_select(self)),
             Action::HandleFilter(l) => Ok(handlers::handle_general_status(self, l.to_string())),
             Action::OpenFilter => {
@@ -465,7 +892,9 @@ impl Component for Home {
             Action::DeleteItem => Ok(handlers::handle_delete_item(self)),
             Action::CopyToClipBoard => Ok(handlers::handle_yank(self, self.action_tx.clone())),
             Action::GoToEnd => Ok(handlers::handle_go_to_end(self, metadata)),
-            Action::GoToStart => Ok(handlers::handle_go_to_start(self)),
+            Action::GoToStart => Ok(handlers::handle_go_to_start(self, metadata)),
+            Action::PageUp => Ok(handlers::handle_page_up(self, metadata)),
+            Action::PageDown => Ok(handlers::handle_page_down(self, metadata)),
             Action::PreviousSection => Ok(handlers::handle_back_tab(self)),
             Action::NextDetailsTab => Ok(handlers::handle_details_tab_next(self)),
             Action::PreviousDetailsTab => Ok(handlers::handle_details_tab_prev(self)),
@@ -473,6 +902,61 @@ impl Component for Home {
             Action::UpdateSearchQuery(c) => Ok(handlers::handle_search_push(self, c)),
             Action::DeleteSearchQuery => Ok(handlers::handle_search_pop(self)),
             Action::ExitSearch => Ok(handlers::handle_search_exit(self)),
+            Action::SearchHistoryPrevious => Ok(handlers::handle_search_history_previous(self)),
+            Action::SearchHistoryNext => Ok(handlers::handle_search_history_next(self)),
+            Action::ToggleShowHidden => Ok(handlers::handle_show_hidden(self, metadata)),
+            Action::ToggleFreeze => Ok(handlers::handle_toggle_freeze(self, metadata)),
+            Action::ToggleCorrelationFilter => {
+                Ok(handlers::handle_toggle_correlation_filter(self, metadata))
+            }
+            Action::ToggleFilterInvert => Ok(handlers::handle_filter_invert(self)),
+            Action::ToggleTimeColumn => Ok(handlers::handle_toggle_time_column(self)),
+            Action::ToggleCompact => Ok(handlers::handle_toggle_compact(self, metadata)),
+            Action::CycleDetailsSort => Ok(handlers::handle_cycle_details_sort(self)),
+            Action::ToggleCurlQueryFlags => Ok(handlers::handle_toggle_curl_query_flags(self)),
+            Action::ToggleRedaction => Ok(handlers::handle_toggle_redaction(self)),
+            Action::ExportKeyMap => Ok(handlers::handle_export_key_map(self)),
+            Action::ExportMockStub => Ok(handlers::handle_export_mock_stub(self)),
+            Action::CopyAsMarkdown => Ok(handlers::handle_copy_as_markdown(
+                self,
+                self.action_tx.clone(),
+            )),
+            Action::CopyUrl => Ok(handlers::handle_copy_url(self, self.action_tx.clone())),
+            Action::OpenExportMenu => Ok(handlers::handle_open_export_menu(self)),
+            Action::CopyAs(format) => Ok(handlers::handle_copy_as(
+                self,
+                format,
+                self.action_tx.clone(),
+            )),
+            Action::OpenContextMenu(index) => {
+                Ok(handlers::handle_open_context_menu(self, index, metadata))
+            }
+            Action::FilterByMethod(method) => {
+                Ok(handlers::handle_filter_by_method(self, method, metadata))
+            }
+            Action::FilterByStatus(status) => {
+                Ok(handlers::handle_filter_by_status(self, status, metadata))
+            }
+            Action::SaveFilterPreset => Ok(handlers::handle_save_filter_preset(self)),
+            Action::AnnotateTrace => Ok(handlers::handle_annotate_trace(self)),
+            Action::SaveAnnotation => Ok(handlers::handle_save_annotation(self)),
+            Action::UpdateAnnotationInput(c) => Ok(handlers::handle_annotation_input_push(self, c)),
+            Action::DeleteAnnotationInput => Ok(handlers::handle_annotation_input_pop(self)),
+            Action::OpenGoTo => Ok(handlers::handle_open_go_to(self)),
+            Action::GoToTrace => Ok(handlers::handle_go_to_trace(self, metadata)),
+            Action::OpenLargestResponses => Ok(handlers::handle_open_largest_responses(self)),
+            Action::GoToLargestResponse => {
+                Ok(handlers::handle_go_to_largest_response(self, metadata))
+            }
+            Action::SaveTrace(path) => Ok(handlers::handle_save_trace(self, path)),
+            Action::ApplyFilterPreset(name) => Ok(handlers::handle_apply_filter_preset(self, name)),
+            Action::DeleteFilterPreset(name) => {
+                Ok(handlers::handle_delete_filter_preset(self, name))
+            }
+            Action::NextMatch => Ok(handlers::handle_next_match(self, metadata)),
+            Action::PreviousMatch => Ok(handlers::handle_previous_match(self, metadata)),
+            Action::NextError => Ok(handlers::handle_next_error(self, metadata)),
+            Action::PreviousError => Ok(handlers::handle_previous_error(self, metadata)),
             Action::FocusOnTraces => Ok(handlers::handle_esc(self)),
             Action::StopWebSocketServer => {
                 self.wss_connected = false;
@@ -484,10 +968,18 @@ impl Component for Home {
             }
             Action::SetGeneralStatus(s) => Ok(handlers::handle_general_status(self, s)),
             Action::SetWebsocketStatus(s) => {
+                self.connected_since = match s {
+                    WebSocketInternalState::Connected(count) if count > 0 => {
+                        self.connected_since.or(Some(Instant::now()))
+                    }
+                    _ => None,
+                };
                 self.wss_state = s;
                 Ok(None)
             }
             Action::NavigateUp(Some(key)) => Ok(handlers::handle_up(self, key, metadata)),
+            Action::NavigateLeft(Some(_)) => Ok(handlers::handle_left(self)),
+            Action::NavigateRight(Some(_)) => Ok(handlers::handle_right(self)),
             Action::NavigateDown(Some(key)) => Ok(handlers::handle_down(self, key, metadata)),
             Action::UpdateMeta(metadata) => {
                 self.metadata = Some(metadata);
@@ -497,20 +989,51 @@ impl Component for Home {
                 self.status_message = None;
                 Ok(None)
             }
+            Action::Tick => Ok(handlers::handle_tick(self)),
+            Action::ClearAlert => {
+                self.alert_message = None;
+                Ok(None)
+            }
             Action::AddTrace(trace) => {
-                self.items.replace(trace);
+                self.logs.extend(trace.warnings.iter().map(|warning| {
+                    (
+                        Local::now(),
+                        format!("WARN trace {}: {}", trace.id, warning),
+                    )
+                }));
+                // a later version of a trace, e.g. its response, is no new arrival
+                if self.items.replace(trace.clone()).is_none() {
+                    self.record_trace_arrival(Instant::now());
+                }
                 handlers::handle_adjust_scroll_bar(self, metadata);
-                Ok(None)
+                handlers::handle_request_timeout(self, &trace, self.action_tx.clone());
+                Ok(handlers::handle_alert(self, &trace, self.action_tx.clone()))
             }
             Action::MarkTraceAsTimedOut(id) => {
+                self.timeout_abort_handlers.remove(&id);
                 self.mark_trace_as_timed_out(id);
                 Ok(Some(Action::SelectTrace(self.selected_trace.clone())))
             }
             Action::SelectTrace(maybe_trace) => {
+                let is_same_trace = self.selected_trace.as_ref().map(|trace| &trace.id)
+                    == maybe_trace.as_ref().map(|trace| &trace.id);
+                let cursors = self.details_list_cursors();
+
                 self.selected_trace = maybe_trace;
 
+                // a headers search only narrows the trace it was made on
+                if !is_same_trace {
+                    self.headers_search.clear();
+                }
+
+                self.sync_extra_pane();
                 self.update_details_lists();
 
+                // a refreshed trace keeps the cursors, a different one starts over
+                if is_same_trace {
+                    self.restore_details_list_cursors(cursors);
+                }
+
                 Ok(None)
             }
             Action::PopOutDetailsTab(pane) => {
@@ -523,6 +1046,22 @@ impl Component for Home {
 
                 Ok(None)
             }
+            Action::MoveDetailsTabLeft | Action::MoveDetailsTabRight => {
+                let index = self.details_tab_index;
+                let target = if action == Action::MoveDetailsTabLeft {
+                    index.checked_sub(1)
+                } else {
+                    Some(index + 1)
+                }
+                .filter(|&next| next.max(index) < self.details_tabs.len());
+
+                if let Some(target) = target {
+                    self.details_tabs.swap(index, target);
+                    self.details_tab_index = target;
+                }
+
+                Ok(None)
+            }
             Action::CloseDetailsPane(pane) => {
                 self.details_panes.retain(|&d| pane != d);
                 self.details_tabs.push(pane);
@@ -533,6 +1072,33 @@ impl Component for Home {
 
                 Ok(None)
             }
+            Action::PopOutAllDetailsTabs => {
+                let tabs = mem::take(&mut self.details_tabs);
+
+                self.details_panes.extend(tabs.iter().copied());
+                self.details_tab_index = 0;
+
+                self.update_details_lists();
+                tabs.into_iter()
+                    .for_each(|pane| self.reset_active_pane(pane));
+
+                Ok(None)
+            }
+            Action::CloseAllDetailsPanes => {
+                let panes = mem::take(&mut self.details_panes);
+
+                self.details_tabs.extend(panes.iter().copied());
+                self.details_tab_index = self
+                    .details_tab_index
+                    .min(self.details_tabs.len().saturating_sub(1));
+
+                self.update_details_lists();
+                panes
+                    .into_iter()
+                    .for_each(|pane| self.reset_active_pane(pane));
+
+                Ok(None)
+            }
             Action::ActivateBlock(block) => {
                 if block == ActiveBlock::Sort(SortScreen::Actions) {
                     self.sort_actions.next();
@@ -594,12 +1160,33 @@ impl Component for Home {
                 self.sort = self.selected_sort.clone();
                 Ok(Some(Action::ActivateBlock(ActiveBlock::Traces)))
             }
+            Action::UpdateFilterInput(c) => Ok(handlers::handle_filter_input_push(self, c)),
+            Action::DeleteFilterInput => Ok(handlers::handle_filter_input_pop(self)),
             Action::UpdateFilter => {
+                if let Err(e) = parse_status_range(&self.selected_filters.status_range) {
+                    self.status_message = Some(e);
+
+                    return Ok(None);
+                }
+
                 self.filters = self.selected_filters.clone();
                 Ok(Some(Action::ActivateBlock(ActiveBlock::Traces)))
             }
             _ => Ok(None),
+        };
+
+        // everything listed by the time the traces are focused counts as seen
+        if !was_viewing_traces && self.active_block == ActiveBlock::Traces {
+            self.last_viewed_timestamp = Some(
+                self.items
+                    .iter()
+                    .map(|trace| trace.timestamp)
+                    .max()
+                    .unwrap_or(i64::MIN),
+            );
         }
+
+        result
     }
 
     fn render(&mut self, frame: &mut Frame, rect: Rect) -> Result<(), Box<dyn Error>> {
@@ -640,10 +1227,57 @@ impl Component for Home {
 
                 render::render_debug(self, frame, main_layout[0]);
             }
+            ActiveBlock::LargestResponses => {
+                let main_layout = Layout::default()
+                    .direction(Direction::Vertical)
+                    .margin(3)
+                    .constraints([Constraint::Percentage(100)].as_ref())
+                    .split(rect);
+
+                render::render_largest_responses(self, frame, main_layout[0]);
+            }
+            ActiveBlock::BodyZoom(screen) => {
+                let main_layout = Layout::default()
+                    .direction(Direction::Vertical)
+                    .margin(1)
+                    .constraints([Constraint::Percentage(100)].as_ref())
+                    .split(rect);
+
+                match screen {
+                    BodyScreen::Request => {
+                        self.request_json_viewer.render(frame, main_layout[0])?
+                    }
+                    BodyScreen::Response => {
+                        self.response_json_viewer.render(frame, main_layout[0])?
+                    }
+                }
+            }
             _ => {
                 let terminal_width = frame.size().width;
 
-                if terminal_width > 200 {
+                if self.details_hidden {
+                    let main_layout = Layout::default()
+                        .direction(Direction::Vertical)
+                        .margin(1)
+                        .constraints([Constraint::Min(3), Constraint::Length(3)].as_ref())
+                        .split(rect);
+
+                    render::render_traces(self, frame, main_layout[0]);
+                    self.traces_area = main_layout[0];
+                    render::render_footer(self, frame, main_layout[1]);
+                    render::render_search(self, frame);
+                    render::render_annotation_prompt(self, frame);
+                    render::render_go_to_prompt(self, frame);
+                    render::render_context_menu(self, frame);
+
+                    // the body panes keep their last size while hidden
+                    let _ = self.action_tx.as_ref().unwrap().send(Action::UpdateMeta(
+                        handlers::HandlerMetadata {
+                            main_height: main_layout[0].height,
+                            ..self.metadata.clone().unwrap_or_default()
+                        },
+                    ));
+                } else if terminal_width > 200 {
                     let main_layout = Layout::default()
                         .direction(Direction::Vertical)
                         .margin(1)
@@ -675,12 +1309,16 @@ impl Component for Home {
                         .split(right_column_layout[1]);
 
                     render::render_traces(self, frame, left_column);
+                    self.traces_area = left_column;
 
                     render::details(self, frame, right_column_layout[0]);
                     self.request_json_viewer.render(frame, body_layout[1])?;
                     self.response_json_viewer.render(frame, body_layout[0])?;
                     render::render_footer(self, frame, main_layout[1]);
                     render::render_search(self, frame);
+                    render::render_annotation_prompt(self, frame);
+                    render::render_go_to_prompt(self, frame);
+                    render::render_context_menu(self, frame);
 
                     let _ = self.action_tx.as_ref().unwrap().send(Action::UpdateMeta(
                         handlers::HandlerMetadata {
@@ -726,7 +1364,11 @@ impl Component for Home {
                     self.response_json_viewer
                         .render(frame, response_layout[1])?;
                     render::render_traces(self, frame, main_layout[0]);
+                    self.traces_area = main_layout[0];
                     render::render_search(self, frame);
+                    render::render_annotation_prompt(self, frame);
+                    render::render_go_to_prompt(self, frame);
+                    render::render_context_menu(self, frame);
                     render::render_footer(self, frame, main_layout[4]);
 
                     let _ = self.action_tx.as_ref().unwrap().send(Action::UpdateMeta(
@@ -745,3 +1387,1971 @@ impl Component for Home {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::error::Error;
+    use std::time::Duration;
+
+    use crossterm::event::{
+        KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    };
+    use http::{HeaderMap, HeaderValue};
+    use pretty_assertions::assert_eq;
+    use ratatui::backend::TestBackend;
+    use ratatui::prelude::Rect;
+    use ratatui::widgets::ScrollbarState;
+    use ratatui::Terminal;
+    use strum::IntoEnumIterator;
+    use tokio::sync::mpsc;
+    use tokio::time::sleep;
+
+    use crate::app::{
+        Action, ActiveBlock, BodyScreen, DetailsPane, DetailsSort, ExportFormat, FilterCombination,
+        FilterScreen, SourceFilter, UIState,
+    };
+    use crate::components::actionable_list::ActionableList;
+    use crate::components::component::Component;
+    use crate::components::handlers;
+    use crate::components::home::{split_details_panes, Home};
+    use crate::components::jsonviewer::JSONViewer;
+    use crate::config::{self, AlertConfig, Colors, RedactConfig};
+    use crate::consts::NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE;
+    use crate::mock;
+    use crate::parser::{
+        generate_httpie_command, parse_raw_trace, parse_raw_trace_with_max_body, Payload,
+    };
+    use crate::render::details_tab_title;
+    use crate::services::websocket::{HTTPTrace, State, Trace, TraceKind};
+    use crate::utils::{
+        get_copied_response_body, get_focused_headers, get_hidden_count, get_match_count,
+        get_rendered_items, get_selected_trace_export, get_selected_trace_url, is_new_trace,
+        parse_status_range,
+    };
+
+    fn trace_with_status(id: &str, status: u16) -> Trace {
+        Trace {
+            id: id.to_string(),
+            http: Some(HTTPTrace {
+                status: http::StatusCode::from_u16(status).ok(),
+                uri: format!("http://localhost/{}", id),
+                ..HTTPTrace::default()
+            }),
+            ..Trace::default()
+        }
+    }
+
+    fn trace_with_state(id: &str, state: State) -> Trace {
+        Trace {
+            id: id.to_string(),
+            http: Some(HTTPTrace {
+                state,
+                uri: format!("http://localhost/{}", id),
+                ..HTTPTrace::default()
+            }),
+            ..Trace::default()
+        }
+    }
+
+    fn mock_traces() -> Vec<Trace> {
+        [
+            mock::TEST_JSON_1,
+            mock::TEST_JSON_2,
+            mock::TEST_JSON_3,
+            mock::TEST_JSON_4,
+            mock::TEST_JSON_5,
+            mock::TEST_JSON_6,
+            mock::TEST_JSON_7,
+            mock::TEST_JSON_8,
+            mock::TEST_JSON_9,
+            mock::TEST_JSON_10,
+            mock::TEST_JSON_11,
+            mock::TEST_JSON_12,
+            mock::TEST_JSON_13,
+            mock::TEST_JSON_14,
+            mock::TEST_JSON_15,
+            mock::TEST_JSON_16,
+            mock::TEST_JSON_17,
+            mock::TEST_JSON_18,
+        ]
+        .iter()
+        .filter_map(|raw| match parse_raw_trace(raw) {
+            Ok(Payload::Trace(trace)) => Some(trace),
+            _ => None,
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_server_error_trace_triggers_alert() -> Result<(), Box<dyn Error>> {
+        let mut home = Home {
+            alerts: AlertConfig {
+                bell: false,
+                on_server_error: true,
+                slow_response_ms: None,
+            },
+            ..Home::default()
+        };
+
+        home.update(Action::AddTrace(trace_with_status("1", 200)))?;
+
+        assert_eq!(None, home.alert_message);
+
+        home.update(Action::AddTrace(trace_with_status("2", 500)))?;
+
+        assert!(home.alert_message.is_some());
+
+        home.update(Action::ClearAlert)?;
+
+        assert_eq!(None, home.alert_message);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout() -> Result<(), Box<dyn Error>> {
+        let (action_tx, mut action_rx) = mpsc::unbounded_channel();
+
+        let mut home = Home {
+            action_tx: Some(action_tx),
+            request_timeout: Some(Duration::from_millis(10)),
+            ..Home::default()
+        };
+
+        for id in ["1", "2"] {
+            home.update(Action::AddTrace(trace_with_state(id, State::Sent)))?;
+        }
+
+        home.update(Action::AddTrace(trace_with_state("2", State::Received)))?;
+
+        sleep(Duration::from_millis(50)).await;
+
+        while let Ok(action) = action_rx.try_recv() {
+            home.update(action)?;
+        }
+
+        let state = |id: &str| {
+            home.items
+                .iter()
+                .find(|trace| trace.id == id)
+                .and_then(|trace| trace.http.as_ref())
+                .map(|http| http.state.clone())
+        };
+
+        assert_eq!(Some(State::Timeout), state("1"));
+        assert_eq!(Some(State::Received), state("2"));
+        assert!(home.timeout_abort_handlers.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_deleted_trace_does_not_time_out() -> Result<(), Box<dyn Error>> {
+        let (action_tx, mut action_rx) = mpsc::unbounded_channel();
+
+        let mut home = Home {
+            action_tx: Some(action_tx),
+            request_timeout: Some(Duration::from_millis(10)),
+            ..Home::default()
+        };
+
+        home.update(Action::AddTrace(trace_with_state("1", State::Sent)))?;
+        home.update(Action::DeleteItem)?;
+
+        assert!(home.items.is_empty());
+        assert!(home.timeout_abort_handlers.is_empty());
+
+        sleep(Duration::from_millis(50)).await;
+
+        while let Ok(action) = action_rx.try_recv() {
+            assert_ne!(Action::MarkTraceAsTimedOut("1".to_string()), action);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_toggle_body_zoom() -> Result<(), Box<dyn Error>> {
+        let mut home = Home {
+            active_block: ActiveBlock::ResponseBody,
+            ..Home::default()
+        };
+
+        home.update(Action::ToggleBodyZoom)?;
+
+        assert_eq!(
+            ActiveBlock::BodyZoom(BodyScreen::Response),
+            home.active_block
+        );
+
+        home.update(Action::ToggleBodyZoom)?;
+
+        assert_eq!(ActiveBlock::ResponseBody, home.active_block);
+
+        home.active_block = ActiveBlock::RequestBody;
+
+        home.update(Action::ToggleBodyZoom)?;
+
+        assert_eq!(
+            ActiveBlock::BodyZoom(BodyScreen::Request),
+            home.active_block
+        );
+
+        home.update(Action::FocusOnTraces)?;
+
+        assert_eq!(ActiveBlock::RequestBody, home.active_block);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_details_show_remote_address_and_tls_version() -> Result<(), Box<dyn Error>> {
+        let raw = r#"{"type":"trace","data":{"id":"1","timestamp":1616239022,"http":{"state":"received","method":"GET","port":443,"path":"/","url":"https://testserver.com/","requestHeaders":{},"statusCode":200,"remoteAddress":"93.184.216.34:443","tlsVersion":"TLSv1.3"}}}"#;
+
+        let trace = match parse_raw_trace(raw)? {
+            Payload::Trace(trace) => trace,
+            _ => panic!("Expected a trace payload"),
+        };
+
+        let mut home = Home {
+            selected_trace: Some(trace),
+            ..Home::default()
+        };
+
+        home.update_details_lists();
+
+        let request_row = home
+            .request_details_list
+            .items
+            .iter()
+            .find(|item| item.label == "remote address");
+        let response_row = home
+            .response_details_list
+            .items
+            .iter()
+            .find(|item| item.label == "tls");
+
+        assert_eq!(
+            Some("93.184.216.34:443".to_string()),
+            request_row.and_then(|item| item.value.clone())
+        );
+        assert_eq!(
+            Some("TLSv1.3".to_string()),
+            response_row.and_then(|item| item.value.clone())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_prefilled_on_reopen() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+
+        home.update(Action::NewSearch)?;
+        home.update(Action::UpdateSearchQuery('a'))?;
+        home.update(Action::UpdateSearchQuery('p'))?;
+        home.update(Action::UpdateSearchQuery('i'))?;
+        home.update(Action::ExitSearch)?;
+
+        assert_eq!(Some("api".to_string()), home.last_search);
+
+        home.update(Action::NewSearch)?;
+
+        assert_eq!(ActiveBlock::SearchQuery, home.active_block);
+        assert_eq!("api", home.search_query);
+
+        home.update(Action::UpdateSearchQuery('s'))?;
+
+        assert_eq!("apis", home.search_query);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_history_cycling() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+
+        for query in ["one", "two", "three"] {
+            home.update(Action::NewSearch)?;
+            home.search_query = query.to_string();
+            home.update(Action::ExitSearch)?;
+        }
+
+        home.update(Action::NewSearch)?;
+
+        home.update(Action::SearchHistoryPrevious)?;
+        assert_eq!("three", home.search_query);
+
+        home.update(Action::SearchHistoryPrevious)?;
+        assert_eq!("two", home.search_query);
+
+        home.update(Action::SearchHistoryPrevious)?;
+        home.update(Action::SearchHistoryPrevious)?;
+        assert_eq!("one", home.search_query);
+
+        home.update(Action::SearchHistoryNext)?;
+        assert_eq!("two", home.search_query);
+
+        home.update(Action::SearchHistoryNext)?;
+        home.update(Action::SearchHistoryNext)?;
+        assert_eq!("", home.search_query);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_match_advances_selection() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+
+        for (id, path) in [
+            ("1", "api/graphql"),
+            ("2", "features"),
+            ("3", "api/graphql"),
+        ] {
+            home.items.insert(Trace {
+                id: id.to_string(),
+                http: Some(HTTPTrace {
+                    uri: format!("http://localhost/{}", path),
+                    ..HTTPTrace::default()
+                }),
+                ..Trace::default()
+            });
+        }
+
+        home.search_query = "graphql".to_string();
+
+        home.update(Action::NextMatch)?;
+
+        assert_eq!(1, home.main.index);
+
+        home.update(Action::NextMatch)?;
+
+        assert_eq!(0, home.main.index);
+
+        home.update(Action::PreviousMatch)?;
+
+        assert_eq!(1, home.main.index);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_error_wraps() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+
+        home.items.extend(mock_traces());
+        home.metadata = Some(handlers::HandlerMetadata {
+            main_height: NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE as u16 + 5,
+            ..handlers::HandlerMetadata::default()
+        });
+
+        let mut visited = vec![];
+
+        for _ in 0..5 {
+            if let Some(action) = home.update(Action::NextError)? {
+                home.update(action)?;
+            }
+
+            let selected = home.selected_trace.as_ref().ok_or("no trace selected")?;
+
+            assert!(home.main.index >= home.main.offset);
+            assert!(home.main.index < home.main.offset + 5);
+
+            visited.push((
+                selected.id.clone(),
+                selected.http.clone().unwrap_or_default().status,
+            ));
+        }
+
+        let status = |code| http::StatusCode::from_u16(code).ok();
+
+        assert_eq!(
+            vec![
+                ("7".to_string(), status(500)),
+                ("16".to_string(), status(500)),
+                ("4".to_string(), status(404)),
+                ("13".to_string(), status(404)),
+                // wraps around to the first failed trace
+                ("7".to_string(), status(500)),
+            ],
+            visited
+        );
+
+        if let Some(action) = home.update(Action::PreviousError)? {
+            home.update(action)?;
+        }
+
+        assert_eq!(
+            Some("13"),
+            home.selected_trace.as_ref().map(|trace| trace.id.as_str())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_match_count() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+
+        home.items.extend(mock_traces());
+
+        assert_eq!(None, get_match_count(&home));
+
+        home.search_query = "graphql".to_string();
+
+        assert_eq!(Some(4), get_match_count(&home));
+
+        home.search_query = "nothingmatchesthis".to_string();
+
+        assert_eq!(Some(0), get_match_count(&home));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hide_patterns() -> Result<(), Box<dyn Error>> {
+        let mut home = Home {
+            hide_patterns: vec![regex::Regex::new(r"/features$")?],
+            ..Home::default()
+        };
+
+        home.items.extend(mock_traces());
+
+        let total = home.items.len();
+
+        assert_eq!(4, get_hidden_count(&home));
+        assert_eq!(total - 4, get_rendered_items(&home).len());
+        assert!(get_rendered_items(&home).iter().all(|trace| !trace
+            .http
+            .as_ref()
+            .unwrap()
+            .uri
+            .ends_with("/features")));
+
+        home.update(Action::ToggleShowHidden)?;
+
+        assert_eq!(0, get_hidden_count(&home));
+        assert_eq!(total, get_rendered_items(&home).len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_freeze_pins_rendered_list() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+
+        home.items.extend(mock_traces());
+
+        let before = rendered_ids(&home);
+
+        home.update(Action::ToggleFreeze)?;
+
+        assert!(home.frozen);
+
+        home.update(Action::AddTrace(Trace {
+            id: "new".to_string(),
+            http: Some(HTTPTrace {
+                uri: "http://localhost/new".to_string(),
+                ..HTTPTrace::default()
+            }),
+            ..Trace::default()
+        }))?;
+
+        assert_eq!(before, rendered_ids(&home));
+
+        home.update(Action::ToggleFreeze)?;
+
+        assert!(!home.frozen);
+        assert!(rendered_ids(&home).contains(&"new".to_string()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_correlation_filter() -> Result<(), Box<dyn Error>> {
+        let trace = |id: &str, timestamp: i64, trace_id: Option<&str>, header: Option<&str>| {
+            let mut request_headers = HeaderMap::new();
+
+            if let Some(header) = header {
+                request_headers.insert("traceparent", HeaderValue::from_str(header).unwrap());
+            }
+
+            Trace {
+                id: id.to_string(),
+                timestamp,
+                http: Some(HTTPTrace {
+                    request_headers,
+                    ..HTTPTrace::default()
+                }),
+                extra: trace_id
+                    .map(|trace_id| serde_json::json!({ "traceId": trace_id }))
+                    .and_then(|extra| extra.as_object().cloned())
+                    .unwrap_or_default(),
+                ..Trace::default()
+            }
+        };
+
+        let traceparent = Some("00-4bf92f35-00f067aa-01");
+        let mut home = Home::default();
+
+        home.items.insert(trace("1", 1, Some("4bf92f35"), None));
+        home.items.insert(trace("2", 2, None, traceparent));
+        home.items.insert(trace("3", 3, Some("a3ce929d"), None));
+        home.items.insert(trace("4", 4, None, None));
+        home.items.insert(trace("5", 5, Some("4bf92f35"), None));
+
+        let all = rendered_ids(&home);
+
+        home.update(Action::SelectTrace(Some(trace("4", 4, None, None))))?;
+        home.update(Action::ToggleCorrelationFilter)?;
+
+        assert_eq!(None, home.correlation_id);
+        assert_eq!(
+            Some("No correlation id on the selected trace".to_string()),
+            home.status_message
+        );
+
+        home.update(Action::SelectTrace(Some(trace("2", 2, None, traceparent))))?;
+
+        if let Some(Action::SelectTrace(Some(selected))) =
+            home.update(Action::ToggleCorrelationFilter)?
+        {
+            assert_eq!("2", selected.id);
+        } else {
+            panic!("expected the selected trace to stay selected");
+        }
+
+        let mut correlated = rendered_ids(&home);
+
+        correlated.sort();
+
+        assert_eq!(vec!["1", "2", "5"], correlated);
+
+        home.update(Action::ToggleCorrelationFilter)?;
+
+        assert_eq!(None, home.correlation_id);
+        assert_eq!(all, rendered_ids(&home));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_filter_by_key_presence() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+
+        home.items.extend(mock_traces());
+
+        let total = home.items.len();
+
+        home.filters.key = "apikey".to_string();
+
+        assert_eq!(vec!["17".to_string(), "8".to_string()], rendered_ids(&home));
+
+        home.filters.key = "".to_string();
+
+        assert_eq!(total, get_rendered_items(&home).len());
+
+        Ok(())
+    }
+
+    fn rendered_ids(home: &Home) -> Vec<String> {
+        let mut ids = get_rendered_items(home)
+            .iter()
+            .map(|trace| trace.id.clone())
+            .collect::<Vec<_>>();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn test_filter_by_exact_status() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+
+        home.items.extend(mock_traces());
+
+        home.filters.status_range = "404".to_string();
+
+        assert_eq!(vec!["13".to_string(), "4".to_string()], rendered_ids(&home));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_filter_by_status_range() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+
+        home.items.extend(mock_traces());
+
+        home.filters.status_range = "400..=599".to_string();
+
+        assert_eq!(
+            vec![
+                "13".to_string(),
+                "16".to_string(),
+                "4".to_string(),
+                "7".to_string()
+            ],
+            rendered_ids(&home)
+        );
+
+        home.filters.status_range = "400..=599,!404".to_string();
+
+        assert_eq!(vec!["16".to_string(), "7".to_string()], rendered_ids(&home));
+
+        assert!(parse_status_range("500..=400").is_err());
+        assert!(parse_status_range("abc").is_err());
+        assert_eq!(Ok(None), parse_status_range(""));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_filter_by_content_type() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+
+        home.items.extend(mock_traces());
+
+        home.filters.content_type =
+            SourceFilter::Applied(HashSet::from(["application/xml".to_string()]));
+
+        assert_eq!(vec!["17".to_string(), "8".to_string()], rendered_ids(&home));
+        assert!(get_rendered_items(&home).iter().all(|trace| trace
+            .http
+            .as_ref()
+            .unwrap()
+            .uri
+            .contains("hits.webstats.com")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_filter_combination() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+
+        home.items.extend(mock_traces());
+
+        if let Some(method_filter) = home.filters.method.get_mut(&http::Method::POST) {
+            method_filter.selected = true;
+        }
+        if let Some(status_filter) = home.filters.status.get_mut("5xx") {
+            status_filter.selected = true;
+        }
+
+        assert_eq!(Vec::<String>::new(), rendered_ids(&home));
+
+        home.filters.combination = FilterCombination::Or;
+
+        assert_eq!(
+            vec!["11", "14", "15", "16", "2", "5", "6", "7"],
+            rendered_ids(&home)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_filter_exclude_method() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+
+        home.items.extend(mock_traces());
+
+        if let Some(method_filter) = home.filters.method.get_mut(&http::Method::POST) {
+            method_filter.selected = true;
+        }
+        home.filters.inverted.insert(FilterScreen::Method);
+
+        let rendered_items = get_rendered_items(&home);
+
+        assert_eq!(12, rendered_items.len());
+        assert!(rendered_items
+            .iter()
+            .all(|trace| trace.http.as_ref().unwrap().method != http::Method::POST));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_filter_exclude_status() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+
+        home.items.extend(mock_traces());
+
+        if let Some(status_filter) = home.filters.status.get_mut("2xx") {
+            status_filter.selected = true;
+        }
+        home.filters.inverted.insert(FilterScreen::Status);
+
+        // 9 and 18 are still pending, so they are not excluded either.
+        assert_eq!(vec!["13", "16", "18", "4", "7", "9"], rendered_ids(&home));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reset_body_ui_state() {
+        let scrolled = UIState {
+            index: 3,
+            offset: 12,
+            horizontal_offset: 8,
+            scroll_state: ScrollbarState::new(40).position(12),
+            horizontal_scroll_state: ScrollbarState::new(20).position(8),
+            ..UIState::default()
+        };
+        let mut home = Home {
+            response_body: scrolled.clone(),
+            request_body: scrolled,
+            ..Home::default()
+        };
+
+        handlers::reset_request_and_response_body_ui_state(&mut home);
+
+        for state in [&home.response_body, &home.request_body] {
+            assert_eq!(3, state.index);
+            assert_eq!(0, state.offset);
+            assert_eq!(0, state.horizontal_offset);
+            assert_eq!(ScrollbarState::new(40), state.scroll_state);
+            assert_eq!(ScrollbarState::new(20), state.horizontal_scroll_state);
+        }
+    }
+
+    #[test]
+    fn test_page_up_and_down() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+
+        home.items.extend(mock_traces());
+        home.active_block = ActiveBlock::Traces;
+        // Five usable rows per page.
+        home.metadata = Some(handlers::HandlerMetadata {
+            main_height: NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE as u16 + 5,
+            ..handlers::HandlerMetadata::default()
+        });
+
+        home.update(Action::PageUp)?;
+
+        assert_eq!((0, 0), (home.main.index, home.main.offset));
+
+        home.update(Action::PageDown)?;
+
+        assert_eq!((5, 5), (home.main.index, home.main.offset));
+
+        home.update(Action::PageDown)?;
+        home.update(Action::PageUp)?;
+
+        assert_eq!((5, 5), (home.main.index, home.main.offset));
+
+        home.update(Action::PageDown)?;
+        home.update(Action::PageDown)?;
+        home.update(Action::PageDown)?;
+
+        assert_eq!((17, 13), (home.main.index, home.main.offset));
+
+        home.update(Action::PageUp)?;
+
+        assert_eq!((12, 8), (home.main.index, home.main.offset));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_go_to_end_and_start() -> Result<(), Box<dyn Error>> {
+        let length = mock_traces().len();
+
+        for usable_height in [1, 3, 5, length - 1, length, length + 4] {
+            let mut home = Home::default();
+
+            home.items.extend(mock_traces());
+            home.active_block = ActiveBlock::Traces;
+            home.metadata = Some(handlers::HandlerMetadata {
+                main_height: (NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE + usable_height) as u16,
+                ..handlers::HandlerMetadata::default()
+            });
+
+            home.update(Action::GoToEnd)?;
+
+            let (index, offset) = (home.main.index, home.main.offset);
+
+            assert_eq!(length - 1, index);
+            assert_eq!(length.saturating_sub(usable_height), offset);
+            assert!(offset <= index && index < offset + usable_height);
+
+            let position = if length > usable_height {
+                length - 1
+            } else {
+                0
+            };
+
+            assert_eq!(
+                ScrollbarState::default().position(position),
+                home.main.scroll_state
+            );
+
+            home.update(Action::GoToStart)?;
+
+            assert_eq!((0, 0), (home.main.index, home.main.offset));
+            assert_eq!(ScrollbarState::default(), home.main.scroll_state);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_move_details_tab() -> Result<(), Box<dyn Error>> {
+        let mut home = Home {
+            details_tabs: vec![
+                DetailsPane::RequestDetails,
+                DetailsPane::QueryParams,
+                DetailsPane::Timing,
+            ],
+            details_tab_index: 2,
+            ..Home::default()
+        };
+
+        home.update(Action::MoveDetailsTabRight)?;
+
+        assert_eq!(2, home.details_tab_index);
+
+        home.update(Action::MoveDetailsTabLeft)?;
+        home.update(Action::MoveDetailsTabLeft)?;
+
+        assert_eq!(
+            vec![
+                DetailsPane::Timing,
+                DetailsPane::RequestDetails,
+                DetailsPane::QueryParams,
+            ],
+            home.details_tabs
+        );
+        assert_eq!(0, home.details_tab_index);
+
+        home.update(Action::MoveDetailsTabLeft)?;
+
+        assert_eq!(0, home.details_tab_index);
+        assert_eq!(DetailsPane::Timing, home.details_tabs[0]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pop_out_and_close_all_details_panes() -> Result<(), Box<dyn Error>> {
+        let mut home = Home {
+            details_tabs: vec![DetailsPane::RequestDetails, DetailsPane::QueryParams],
+            details_panes: vec![DetailsPane::Timing],
+            details_tab_index: 1,
+            ..Home::default()
+        };
+
+        home.update(Action::PopOutAllDetailsTabs)?;
+
+        assert!(home.details_tabs.is_empty());
+        assert_eq!(
+            vec![
+                DetailsPane::Timing,
+                DetailsPane::RequestDetails,
+                DetailsPane::QueryParams,
+            ],
+            home.details_panes
+        );
+        assert_eq!(0, home.details_tab_index);
+
+        home.update(Action::CloseAllDetailsPanes)?;
+
+        assert!(home.details_panes.is_empty());
+        assert_eq!(
+            vec![
+                DetailsPane::Timing,
+                DetailsPane::RequestDetails,
+                DetailsPane::QueryParams,
+            ],
+            home.details_tabs
+        );
+        assert_eq!(0, home.details_tab_index);
+
+        home.details_tab_index = 2;
+        home.update(Action::PopOutDetailsTab(DetailsPane::RequestDetails))?;
+        home.update(Action::CloseAllDetailsPanes)?;
+
+        assert!(home.details_panes.is_empty());
+        assert_eq!(3, home.details_tabs.len());
+        assert!(home.details_tab_index < home.details_tabs.len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_traces_enter_action() -> Result<(), Box<dyn Error>> {
+        let trace = match parse_raw_trace(mock::TEST_JSON_1)? {
+            Payload::Trace(trace) => trace,
+            _ => panic!("expected a trace"),
+        };
+
+        let mut home = Home::default();
+
+        home.items.insert(trace.clone());
+        home.update(Action::SelectTrace(Some(trace)))?;
+
+        assert_eq!(None, home.update(Action::Select)?);
+        assert_eq!(ActiveBlock::Details, home.active_block);
+
+        let config = config::parse("traces_enter_action: CopyToClipBoard")?;
+
+        home.traces_enter_action = config.traces_enter_action;
+        home.active_block = ActiveBlock::Traces;
+
+        assert_eq!(Some(Action::CopyToClipBoard), home.update(Action::Select)?);
+        assert_eq!(ActiveBlock::Traces, home.active_block);
+
+        // navigation and input actions are rejected
+        let config = config::parse("traces_enter_action: Select")?;
+
+        assert_eq!(None, config.traces_enter_action);
+        assert_eq!(
+            vec!["traces_enter_action Select ignored".to_string()],
+            config.warnings
+        );
+        assert!(config::parse("traces_enter_action: PageDown")?
+            .traces_enter_action
+            .is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_extra_pane_only_for_traces_with_extra_fields() -> Result<(), Box<dyn Error>> {
+        let mut home = Home {
+            details_tabs: vec![DetailsPane::RequestDetails, DetailsPane::Timing],
+            ..Home::default()
+        };
+
+        let mut trace = Trace {
+            id: "1".to_string(),
+            http: Some(HTTPTrace::default()),
+            ..Trace::default()
+        };
+
+        home.update(Action::SelectTrace(Some(trace.clone())))?;
+
+        assert!(!home.details_tabs.contains(&DetailsPane::Extra));
+
+        trace.extra.insert("traceId".to_string(), "4bf92f35".into());
+
+        home.update(Action::SelectTrace(Some(trace.clone())))?;
+
+        assert_eq!(
+            vec![
+                DetailsPane::RequestDetails,
+                DetailsPane::Timing,
+                DetailsPane::Extra,
+            ],
+            home.details_tabs
+        );
+
+        home.update(Action::PopOutDetailsTab(DetailsPane::Extra))?;
+        home.update(Action::SelectTrace(Some(trace)))?;
+
+        assert_eq!(vec![DetailsPane::Extra], home.details_panes);
+
+        home.update(Action::SelectTrace(Some(Trace {
+            id: "2".to_string(),
+            http: Some(HTTPTrace::default()),
+            ..Trace::default()
+        })))?;
+
+        assert!(home.details_panes.is_empty());
+        assert_eq!(
+            vec![DetailsPane::RequestDetails, DetailsPane::Timing],
+            home.details_tabs
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_default_open_details_panes() -> Result<(), Box<dyn Error>> {
+        let config = config::parse("details_panes: [Timing, ResponseDetails, Timing]")?;
+
+        let (details_tabs, details_panes) = split_details_panes(&config.details_panes);
+
+        assert_eq!(
+            vec![DetailsPane::Timing, DetailsPane::ResponseDetails],
+            details_panes
+        );
+        assert_eq!(
+            vec![
+                DetailsPane::RequestDetails,
+                DetailsPane::QueryParams,
+                DetailsPane::RequestHeaders,
+                DetailsPane::ResponseHeaders,
+                DetailsPane::Curl,
+            ],
+            details_tabs
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_header_scroll() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+        let trace = mock_traces().remove(1);
+
+        home.items.insert(trace.clone());
+        home.update(Action::SelectTrace(Some(trace)))?;
+        home.active_block = ActiveBlock::Details;
+
+        let down = KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE);
+        let up = KeyEvent::new(KeyCode::Char('k'), KeyModifiers::NONE);
+
+        for pane in [DetailsPane::RequestHeaders, DetailsPane::ResponseHeaders] {
+            home.details_block = pane;
+
+            let selected = |home: &Home| match pane {
+                DetailsPane::RequestHeaders => home.request_headers_list.scroll_state.selected(),
+                _ => home.response_headers_list.scroll_state.selected(),
+            };
+            let length = match pane {
+                DetailsPane::RequestHeaders => home.request_headers_list.items.len(),
+                _ => home.response_headers_list.items.len(),
+            };
+
+            // the trailing row holds the pane actions
+            assert!(length > 1);
+
+            home.update(Action::NavigateDown(Some(down)))?;
+
+            assert_eq!(Some(0), selected(&home));
+
+            for _ in 0..length + 2 {
+                home.update(Action::NavigateDown(Some(down)))?;
+            }
+
+            assert_eq!(Some(length - 1), selected(&home));
+
+            home.update(Action::NavigateUp(Some(up)))?;
+
+            assert_eq!(Some(length - 2), selected(&home));
+
+            for _ in 0..length + 2 {
+                home.update(Action::NavigateUp(Some(up)))?;
+            }
+
+            assert_eq!(Some(0), selected(&home));
+        }
+
+        // the other lists are left untouched
+        assert_eq!(None, home.request_details_list.scroll_state.selected());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_go_to_end_of_long_header_list() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+        let mut trace = trace_with_status("1", 200);
+
+        if let Some(http) = trace.http.as_mut() {
+            for i in 0..40 {
+                http.request_headers.insert(
+                    http::HeaderName::from_bytes(format!("x-header-{}", i).as_bytes())?,
+                    http::HeaderValue::from_static("value"),
+                );
+            }
+        }
+
+        home.items.insert(trace.clone());
+        home.update(Action::SelectTrace(Some(trace)))?;
+        home.active_block = ActiveBlock::Details;
+        home.details_block = DetailsPane::RequestHeaders;
+
+        // every header plus the pane actions row
+        let length = home.request_headers_list.items.len();
+
+        assert_eq!(41, length);
+
+        home.update(Action::GoToEnd)?;
+
+        assert_eq!(
+            Some(length - 1),
+            home.request_headers_list.scroll_state.selected()
+        );
+        assert_eq!(
+            ScrollbarState::new(length).position(length - 1),
+            home.request_headers_list.scrollbar_state()
+        );
+
+        home.update(Action::GoToStart)?;
+
+        assert_eq!(Some(0), home.request_headers_list.scroll_state.selected());
+        assert_eq!(
+            ScrollbarState::new(length),
+            home.request_headers_list.scrollbar_state()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_since_last_view() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+
+        let trace_at = |id: &str, timestamp: i64| Trace {
+            timestamp,
+            ..trace_with_status(id, 200)
+        };
+
+        home.items.insert(trace_at("1", 1000));
+        home.items.insert(trace_at("2", 2000));
+
+        // nothing is new before the list was ever focused
+        assert!(home.items.iter().all(|trace| !is_new_trace(&home, trace)));
+
+        home.active_block = ActiveBlock::Details;
+        home.update(Action::FocusOnTraces)?;
+
+        assert_eq!(Some(2000), home.last_viewed_timestamp);
+
+        home.items.insert(trace_at("3", 3000));
+        home.items.insert(trace_at("4", 4000));
+
+        let new_ids: Vec<&str> = home
+            .items
+            .iter()
+            .filter(|trace| is_new_trace(&home, trace))
+            .map(|trace| trace.id.as_str())
+            .collect();
+
+        assert_eq!(vec!["4", "3"], new_ids);
+
+        // staying on the list keeps the divider where it is
+        home.update(Action::FocusOnTraces)?;
+
+        assert_eq!(Some(2000), home.last_viewed_timestamp);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_selected_trace_url() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+
+        assert_eq!(None, get_selected_trace_url(&home));
+
+        let trace = match parse_raw_trace(mock::TEST_JSON_8)? {
+            Payload::Trace(trace) => trace,
+            _ => panic!("expected a trace"),
+        };
+        let uri = trace.http.as_ref().unwrap().uri.clone();
+
+        home.update(Action::SelectTrace(Some(trace)))?;
+
+        assert_eq!(Some(uri.clone()), get_selected_trace_url(&home));
+
+        home.redact.keys = vec!["apikey".to_string()];
+
+        let redacted = get_selected_trace_url(&home).unwrap();
+
+        assert!(redacted.contains("apikey=<redacted>"));
+        assert_eq!(
+            uri.replace("c82e66bd-4d5b-4bb7-b439-896936c94eb2", "<redacted>"),
+            redacted
+        );
+
+        home.redact.enabled = false;
+
+        assert_eq!(Some(uri), get_selected_trace_url(&home));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_context_menu() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+
+        home.items.extend(mock_traces());
+        home.active_block = ActiveBlock::Traces;
+        home.traces_area = Rect::new(0, 0, 80, 12);
+        home.metadata = Some(handlers::HandlerMetadata {
+            main_height: 12,
+            ..handlers::HandlerMetadata::default()
+        });
+
+        let right_click = |column, row| MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Right),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+
+        // the header is not a trace
+        assert_eq!(None, home.handle_mouse_events(right_click(10, 1))?);
+
+        let action = home.handle_mouse_events(right_click(10, 4))?;
+
+        assert_eq!(Some(Action::OpenContextMenu(1)), action);
+
+        home.update(action.unwrap())?;
+
+        let trace = get_rendered_items(&home)[1].clone();
+        let method = trace.http.as_ref().unwrap().method.to_string();
+
+        assert_eq!(ActiveBlock::ContextMenu, home.active_block);
+        assert_eq!(1, home.main.index);
+
+        let labels: Vec<&str> = home
+            .context_menu
+            .items
+            .iter()
+            .map(|item| item.label.as_str())
+            .collect();
+
+        assert_eq!(format!("filter to {}", method), labels[0]);
+        assert_eq!(["copy cURL", "delete"], labels[labels.len() - 2..]);
+
+        let down = KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE);
+
+        for _ in 0..labels.len() - 2 {
+            home.update(Action::NavigateDown(Some(down)))?;
+        }
+
+        assert_eq!(Some(Action::CopyToClipBoard), home.update(Action::Select)?);
+        assert_eq!(ActiveBlock::Traces, home.active_block);
+
+        home.update(Action::OpenContextMenu(1))?;
+
+        let action = home.update(Action::Select)?;
+
+        assert_eq!(Some(Action::FilterByMethod(method.clone())), action);
+
+        home.update(action.unwrap())?;
+
+        assert!(get_rendered_items(&home).iter().all(|trace| trace
+            .http
+            .as_ref()
+            .unwrap()
+            .method
+            .to_string()
+            == method));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_go_to_trace_by_id() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+
+        home.items.extend(mock_traces());
+        home.active_block = ActiveBlock::Traces;
+        home.metadata = Some(handlers::HandlerMetadata {
+            main_height: NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE as u16 + 5,
+            ..handlers::HandlerMetadata::default()
+        });
+
+        let last = get_rendered_items(&home)
+            .last()
+            .map(|trace| trace.id.clone())
+            .ok_or("no traces")?;
+
+        home.update(Action::OpenGoTo)?;
+
+        for c in last.chars() {
+            home.update(Action::UpdateFilterInput(c))?;
+        }
+
+        let action = home.update(Action::Select)?;
+
+        assert_eq!(Some(Action::GoToTrace), action);
+
+        if let Some(Action::SelectTrace(trace)) = home.update(Action::GoToTrace)? {
+            home.update(Action::SelectTrace(trace))?;
+        }
+
+        let index = get_rendered_items(&home).len() - 1;
+
+        assert_eq!(ActiveBlock::Traces, home.active_block);
+        assert_eq!((index, index - 4), (home.main.index, home.main.offset));
+        assert_eq!(
+            Some(last.as_str()),
+            home.selected_trace.as_ref().map(|trace| trace.id.as_str())
+        );
+
+        home.update(Action::OpenGoTo)?;
+        home.update(Action::UpdateFilterInput('?'))?;
+        home.update(Action::GoToTrace)?;
+
+        assert_eq!(index, home.main.index);
+        assert_eq!(
+            Some("No trace with id \"?\"".to_string()),
+            home.status_message
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_toggle_details_widens_traces() -> Result<(), Box<dyn Error>> {
+        let (action_tx, _action_rx) = mpsc::unbounded_channel();
+        // wide enough for the side by side layout
+        let mut terminal = Terminal::new(TestBackend::new(240, 40))?;
+        let (details_tabs, details_panes) = split_details_panes(&[]);
+        let mut home = Home {
+            action_tx: Some(action_tx),
+            details_tabs,
+            details_panes,
+            ..Home::default()
+        };
+
+        home.items.extend(mock_traces());
+        home.main.index = 3;
+
+        terminal.draw(|f| {
+            let _ = home.render(f, f.size());
+        })?;
+
+        let width = home.traces_area.width;
+
+        home.update(Action::ToggleDetails)?;
+
+        terminal.draw(|f| {
+            let _ = home.render(f, f.size());
+        })?;
+
+        assert!(home.details_hidden);
+        assert!(home.traces_area.width > width);
+        assert_eq!(3, home.main.index);
+
+        home.update(Action::ToggleDetails)?;
+
+        assert!(!home.details_hidden);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_focused_headers() -> Result<(), Box<dyn Error>> {
+        let mut request_headers = HeaderMap::new();
+
+        request_headers.insert("authorization", HeaderValue::from_static("Bearer secret"));
+        request_headers.insert("accept", HeaderValue::from_static("*/*"));
+
+        let mut home = Home {
+            details_tabs: DetailsPane::iter().collect(),
+            ..Home::default()
+        };
+
+        home.update(Action::SelectTrace(Some(Trace {
+            id: "1".to_string(),
+            http: Some(HTTPTrace {
+                request_headers,
+                ..HTTPTrace::default()
+            }),
+            ..Trace::default()
+        })))?;
+
+        assert_eq!(None, get_focused_headers(&home));
+
+        home.active_block = ActiveBlock::Details;
+        home.details_block = DetailsPane::RequestHeaders;
+        home.redact.keys = vec!["authorization".to_string()];
+
+        assert_eq!(
+            Some("accept: */*\nauthorization: <redacted>".to_string()),
+            get_focused_headers(&home)
+        );
+
+        home.redact.enabled = false;
+
+        assert_eq!(
+            Some("accept: */*\nauthorization: Bearer secret".to_string()),
+            get_focused_headers(&home)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_details_focus_restored() -> Result<(), Box<dyn Error>> {
+        let trace = match parse_raw_trace(mock::TEST_JSON_1)? {
+            Payload::Trace(trace) => trace,
+            _ => panic!("expected a trace"),
+        };
+        let (details_tabs, details_panes) = split_details_panes(&[DetailsPane::RequestHeaders]);
+        let mut home = Home {
+            details_tabs,
+            details_panes,
+            ..Home::default()
+        };
+
+        home.items.insert(trace.clone());
+        home.update(Action::SelectTrace(Some(trace.clone())))?;
+
+        let down = KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE);
+
+        // into the tabs, then on to the popped out headers pane
+        home.update(Action::NextSection)?;
+        home.update(Action::NextSection)?;
+        home.update(Action::NavigateDown(Some(down)))?;
+        home.update(Action::NavigateDown(Some(down)))?;
+
+        assert_eq!(DetailsPane::RequestHeaders, home.details_block);
+        assert_eq!(Some(1), home.request_headers_list.scroll_state.selected());
+
+        home.update(Action::FocusOnTraces)?;
+        // the same trace arriving again, e.g. with its response
+        home.update(Action::SelectTrace(Some(trace)))?;
+        home.update(Action::NextSection)?;
+
+        assert_eq!(ActiveBlock::Details, home.active_block);
+        assert_eq!(DetailsPane::RequestHeaders, home.details_block);
+        assert_eq!(Some(1), home.request_headers_list.scroll_state.selected());
+
+        home.update(Action::SelectTrace(Some(trace_with_status("2", 200))))?;
+
+        assert_eq!(None, home.request_headers_list.scroll_state.selected());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_mode_fits_more_rows() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+
+        home.items.extend(mock_traces());
+        home.active_block = ActiveBlock::Traces;
+        // Five usable rows per page outside of compact mode.
+        home.metadata = Some(handlers::HandlerMetadata {
+            main_height: NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE as u16 + 5,
+            ..handlers::HandlerMetadata::default()
+        });
+
+        assert_eq!(
+            NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE,
+            home.traces_unusable_vertical_space()
+        );
+
+        home.update(Action::ToggleCompact)?;
+
+        assert!(home.compact);
+        assert_eq!(
+            NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE - 1,
+            home.traces_unusable_vertical_space()
+        );
+
+        home.update(Action::PageDown)?;
+
+        assert_eq!((6, 6), (home.main.index, home.main.offset));
+
+        home.update(Action::ToggleCompact)?;
+
+        assert_eq!(
+            NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE,
+            home.traces_unusable_vertical_space()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_grpc_trace_details() -> Result<(), Box<dyn Error>> {
+        let trace = match parse_raw_trace(mock::TEST_JSON_20)? {
+            Payload::Trace(trace) => trace,
+            _ => panic!("expected a trace"),
+        };
+
+        assert_eq!(TraceKind::Grpc, trace.kind());
+        assert_eq!(
+            Some("<binary 12 bytes>".to_string()),
+            trace.http.clone().unwrap_or_default().request_body
+        );
+
+        let mut home = Home::default();
+
+        home.update(Action::SelectTrace(Some(trace)))?;
+
+        let row = |list: &ActionableList, label: &str| {
+            list.items
+                .iter()
+                .find(|item| item.label == label)
+                .and_then(|item| item.value.clone())
+        };
+
+        assert_eq!(
+            Some("helloworld.Greeter/SayHello".to_string()),
+            row(&home.request_details_list, "grpc method")
+        );
+        assert_eq!(
+            Some("5 NOT_FOUND".to_string()),
+            row(&home.response_details_list, "grpc status")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_menu_copies_httpie() -> Result<(), Box<dyn Error>> {
+        let trace = match parse_raw_trace(mock::TEST_JSON_1)? {
+            Payload::Trace(trace) => trace,
+            _ => panic!("expected a trace"),
+        };
+        let mut home = Home::default();
+
+        home.items.insert(trace.clone());
+        home.update(Action::SelectTrace(Some(trace.clone())))?;
+        home.update(Action::OpenExportMenu)?;
+
+        let labels: Vec<&str> = home
+            .context_menu
+            .items
+            .iter()
+            .map(|item| item.label.as_str())
+            .collect();
+
+        assert_eq!(ActiveBlock::ContextMenu, home.active_block);
+        assert_eq!(
+            vec![
+                "copy as cURL",
+                "copy as HTTPie",
+                "copy as fetch",
+                "copy as .http",
+                "copy as Markdown"
+            ],
+            labels
+        );
+
+        let down = KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE);
+
+        home.update(Action::NavigateDown(Some(down)))?;
+
+        assert_eq!(
+            Some(Action::CopyAs(ExportFormat::Httpie)),
+            home.update(Action::Select)?
+        );
+        assert_eq!(ActiveBlock::Traces, home.active_block);
+        assert_eq!(
+            Some(generate_httpie_command(&trace, home.redacted_keys())),
+            get_selected_trace_export(&home, ExportFormat::Httpie)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_targets_focused_block() -> Result<(), Box<dyn Error>> {
+        let trace = match parse_raw_trace(mock::TEST_JSON_1)? {
+            Payload::Trace(trace) => trace,
+            _ => panic!("expected a trace"),
+        };
+        let mut home = Home::default();
+
+        home.items.extend(mock_traces());
+        home.update(Action::SelectTrace(Some(trace)))?;
+
+        let search = |home: &mut Home, query: &str| -> Result<(), Box<dyn Error>> {
+            home.update(Action::NewSearch)?;
+
+            for c in query.chars() {
+                if let Some(action) = home.update(Action::UpdateSearchQuery(c))? {
+                    home.update(action)?;
+                }
+            }
+
+            home.update(Action::ExitSearch)?;
+
+            Ok(())
+        };
+
+        // the traces list
+        search(&mut home, "auth")?;
+
+        assert_eq!(ActiveBlock::Traces, home.active_block);
+        assert_eq!("auth", home.search_query);
+        assert_eq!("", home.headers_search);
+
+        // a headers pane
+        home.active_block = ActiveBlock::Details;
+        home.details_block = DetailsPane::RequestHeaders;
+
+        search(&mut home, "acc")?;
+
+        let labels: Vec<&str> = home
+            .request_headers_list
+            .items
+            .iter()
+            .filter(|item| item.action.is_none())
+            .map(|item| item.label.as_str())
+            .collect();
+
+        assert_eq!(ActiveBlock::Details, home.active_block);
+        assert_eq!(vec!["accept", "accept-encoding"], labels);
+        assert_eq!("auth", home.search_query);
+        // the narrowed panes say so
+        assert_eq!(
+            "REQUEST HEADERS (2) filtered: acc",
+            details_tab_title(&home, DetailsPane::RequestHeaders)
+        );
+
+        // a body pane has its own query
+        home.active_block = ActiveBlock::ResponseBody;
+        home.update(Action::ActivateBlock(ActiveBlock::ResponseBody))?;
+
+        search(&mut home, "token")?;
+
+        assert_eq!("acc", home.headers_search);
+        assert_eq!("token", home.body_search);
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 8))?;
+
+        terminal.draw(|f| {
+            let _ = home.response_json_viewer.render(f, f.size());
+        })?;
+
+        let rendered: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+
+        assert_eq!(ActiveBlock::ResponseBody, home.active_block);
+        assert!(rendered.contains("/token"));
+        assert_eq!("auth", home.search_query);
+
+        // another trace lists all of its headers again
+        home.update(Action::SelectTrace(
+            parse_raw_trace(mock::TEST_JSON_3)?.into_traces().pop(),
+        ))?;
+
+        assert_eq!("", home.headers_search);
+        assert!(!details_tab_title(&home, DetailsPane::RequestHeaders).contains("filtered"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_details_sort() -> Result<(), Box<dyn Error>> {
+        let mut request_headers = HeaderMap::new();
+
+        request_headers.insert("x-request-id", HeaderValue::from_static("1"));
+        request_headers.insert("accept", HeaderValue::from_static("*/*"));
+        request_headers.insert("user-agent", HeaderValue::from_static("curl"));
+        request_headers.insert("content-type", HeaderValue::from_static("text/plain"));
+
+        let trace = Trace {
+            id: "1".to_string(),
+            http: Some(HTTPTrace {
+                uri: "http://localhost/?page=2&limit=10&sort=name".to_string(),
+                request_headers,
+                ..HTTPTrace::default()
+            }),
+            ..Trace::default()
+        };
+
+        let labels = |list: &ActionableList| -> Vec<String> {
+            list.items
+                .iter()
+                .filter(|item| item.action.is_none())
+                .map(|item| item.label.clone())
+                .collect()
+        };
+
+        let mut home = Home::default();
+
+        home.update(Action::SelectTrace(Some(trace)))?;
+
+        assert_eq!(DetailsSort::NameAsc, home.details_sort);
+        assert_eq!(
+            vec!["accept", "content-type", "user-agent", "x-request-id"],
+            labels(&home.request_headers_list)
+        );
+        assert_eq!(
+            vec!["limit", "page", "sort"],
+            labels(&home.query_params_list)
+        );
+
+        for (sort, headers, params) in [
+            (
+                DetailsSort::NameDesc,
+                vec!["x-request-id", "user-agent", "content-type", "accept"],
+                vec!["sort", "page", "limit"],
+            ),
+            (
+                DetailsSort::Unsorted,
+                vec!["x-request-id", "accept", "user-agent", "content-type"],
+                vec!["page", "limit", "sort"],
+            ),
+            (
+                DetailsSort::NameAsc,
+                vec!["accept", "content-type", "user-agent", "x-request-id"],
+                vec!["limit", "page", "sort"],
+            ),
+        ] {
+            if let Some(action) = home.update(Action::CycleDetailsSort)? {
+                home.update(action)?;
+            }
+
+            assert_eq!(sort, home.details_sort);
+            assert_eq!(headers, labels(&home.request_headers_list));
+            assert_eq!(params, labels(&home.query_params_list));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_save_trace() -> Result<(), Box<dyn Error>> {
+        let path =
+            std::env::temp_dir().join(format!("envy-test-trace-{}.json", std::process::id()));
+        let trace = match parse_raw_trace(mock::TEST_JSON_5)? {
+            Payload::Trace(trace) => trace,
+            _ => panic!("expected a trace"),
+        };
+        let mut home = Home {
+            redact: RedactConfig {
+                enabled: true,
+                keys: vec!["authorization".to_string()],
+            },
+            ..Home::default()
+        };
+
+        home.update(Action::SelectTrace(Some(trace)))?;
+        home.update(Action::OpenGoTo)?;
+
+        for c in format!("save {}", path.display()).chars() {
+            home.update(Action::UpdateFilterInput(c))?;
+        }
+
+        let action = home.update(Action::Select)?;
+
+        assert_eq!(Some(Action::SaveTrace(path.display().to_string())), action);
+
+        home.update(action.ok_or("no action")?)?;
+
+        assert_eq!(ActiveBlock::Traces, home.active_block);
+        assert_eq!(
+            Some(format!("Saved trace 5 to {}", path.display())),
+            home.status_message
+        );
+
+        let raw = std::fs::read_to_string(&path)?;
+        let saved = match parse_raw_trace(&raw)? {
+            Payload::Trace(trace) => trace,
+            _ => panic!("expected a trace"),
+        };
+
+        assert_eq!("5", saved.id);
+        assert_eq!(
+            Some(r#"{"id":"4"}"#.to_string()),
+            saved.http.and_then(|http| http.response_body)
+        );
+        // the authorization header is redacted
+        assert!(!raw.contains("Bearer"));
+        assert!(raw.contains(r#""authorization": "<redacted>""#));
+
+        // an existing file is not overwritten
+        home.update(Action::SaveTrace(path.display().to_string()))?;
+
+        let overwritten = std::fs::read_to_string(&path)?;
+
+        std::fs::remove_file(&path)?;
+
+        assert_eq!(raw, overwritten);
+        assert_eq!(
+            Some(format!(
+                "Failed to save trace: {} already exists",
+                path.display()
+            )),
+            home.status_message
+        );
+
+        home.update(Action::SelectTrace(None))?;
+        home.update(Action::SaveTrace(path.display().to_string()))?;
+
+        assert!(!path.exists());
+        assert_eq!(
+            Some("No trace selected to save".to_string()),
+            home.status_message
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_annotate_prompt_has_its_own_input() -> Result<(), Box<dyn Error>> {
+        let trace = Trace {
+            id: "1".to_string(),
+            http: Some(HTTPTrace::default()),
+            ..Trace::default()
+        };
+        let mut home = Home {
+            selected_trace: Some(trace),
+            ..Home::default()
+        };
+
+        home.update(Action::AnnotateTrace)?;
+
+        assert_eq!(ActiveBlock::Annotate, home.active_block);
+
+        for code in [KeyCode::Char('o'), KeyCode::Char('k'), KeyCode::Backspace] {
+            let action = home.handle_key_events(KeyEvent::from(code))?;
+
+            assert!(matches!(
+                action,
+                Some(Action::UpdateAnnotationInput(_) | Action::DeleteAnnotationInput)
+            ));
+
+            home.update(action.ok_or("no action")?)?;
+        }
+
+        assert_eq!("o", home.annotation_input);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_paging_while_filtering_help() -> Result<(), Box<dyn Error>> {
+        let mut home = Home {
+            key_map: config::parse(include_str!("../../.config/config.yml"))?
+                .mapping
+                .0,
+            active_block: ActiveBlock::Help,
+            metadata: Some(handlers::HandlerMetadata {
+                main_height: NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE as u16 + 3,
+                ..handlers::HandlerMetadata::default()
+            }),
+            ..Home::default()
+        };
+
+        home.update(Action::UpdateSearchQuery('t'))?;
+
+        assert_eq!("t", home.help_filter);
+
+        // bound to page down, not to a typed character
+        home.update(Action::PageDown)?;
+
+        assert_eq!(3, home.help_offset);
+        assert_eq!(ActiveBlock::Help, home.active_block);
+
+        home.update(Action::PageUp)?;
+
+        assert_eq!(0, home.help_offset);
+
+        // bound to `q`, typed into the filter instead
+        assert_eq!(None, home.update(Action::Quit)?);
+        assert_eq!(ActiveBlock::Help, home.active_block);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_trace_updates_are_not_arrivals() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+        let trace = |state: State| Trace {
+            id: "1".to_string(),
+            http: Some(HTTPTrace {
+                state,
+                ..HTTPTrace::default()
+            }),
+            ..Trace::default()
+        };
+
+        home.update(Action::AddTrace(trace(State::Sent)))?;
+        home.update(Action::AddTrace(trace(State::Received)))?;
+
+        assert_eq!(1, home.trace_arrivals.len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_yank_grpc_response_body() -> Result<(), Box<dyn Error>> {
+        let trace = match parse_raw_trace(mock::TEST_JSON_20)? {
+            Payload::Trace(trace) => trace,
+            _ => panic!("expected a trace"),
+        };
+        let mut home = Home::default();
+
+        home.update(Action::SelectTrace(Some(trace.clone())))?;
+        home.active_block = ActiveBlock::ResponseBody;
+        home.update(Action::CopyToClipBoard)?;
+
+        assert!(home.status_message.is_some());
+        assert_eq!(
+            Some("<binary 5 bytes>".to_string()),
+            get_copied_response_body(&trace)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_yank_truncated_response_body() -> Result<(), Box<dyn Error>> {
+        let trace = match parse_raw_trace_with_max_body(mock::TEST_JSON_1, 16)? {
+            Payload::Trace(trace) => trace,
+            _ => panic!("expected a trace"),
+        };
+        let body = get_copied_response_body(&trace).ok_or("no response body")?;
+
+        assert!(body.ends_with(" bytes)"));
+
+        let mut home = Home::default();
+
+        home.update(Action::SelectTrace(Some(trace)))?;
+        home.active_block = ActiveBlock::ResponseBody;
+        home.update(Action::CopyToClipBoard)?;
+
+        assert!(home.status_message.is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_navigation_keys_typed_into_inputs() -> Result<(), Box<dyn Error>> {
+        let j = KeyEvent::from(KeyCode::Char('j'));
+        let k = KeyEvent::from(KeyCode::Char('k'));
+
+        let mut home = Home {
+            active_block: ActiveBlock::Filter(FilterScreen::Key),
+            filter_value_index: 1,
+            ..Home::default()
+        };
+
+        home.update(Action::NavigateDown(Some(j)))?;
+        home.update(Action::NavigateUp(Some(k)))?;
+
+        assert_eq!(1, home.filter_value_index);
+
+        let trace = match parse_raw_trace(mock::TEST_JSON_1)? {
+            Payload::Trace(trace) => trace,
+            _ => panic!("expected a trace"),
+        };
+        let mut home = Home {
+            response_json_viewer: JSONViewer::new(
+                ActiveBlock::ResponseBody,
+                4,
+                "Response body",
+                Colors::default(),
+            )?,
+            ..Home::default()
+        };
+
+        home.update(Action::SelectTrace(Some(trace)))?;
+        home.update(Action::ActivateBlock(ActiveBlock::ResponseBody))?;
+        home.update(Action::ExpandAll)?;
+        home.update(Action::NewSearch)?;
+
+        assert_eq!(ActiveBlock::SearchQuery, home.active_block);
+
+        home.update(Action::NavigateDown(Some(j)))?;
+
+        assert_eq!(0, home.response_json_viewer.cursor_position);
+
+        home.update(Action::ExitSearch)?;
+        home.update(Action::NavigateDown(Some(j)))?;
+
+        assert_eq!(ActiveBlock::ResponseBody, home.active_block);
+        assert_eq!(1, home.response_json_viewer.cursor_position);
+
+        Ok(())
+    }
+}
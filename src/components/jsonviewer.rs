@@ -4,8 +4,8 @@ use ratatui::prelude::{
     Alignment, Constraint, Direction, Layout, Line, Margin, Modifier, Rect, Span, Style,
 };
 use ratatui::widgets::{
-    block::Padding, Block, BorderType, Borders, Paragraph, Scrollbar, ScrollbarOrientation,
-    ScrollbarState, Wrap,
+    block::{Padding, Position, Title},
+    Block, BorderType, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap,
 };
 use ratatui::Frame;
 use tokio::sync::mpsc::UnboundedSender;
@@ -13,22 +13,46 @@ use tokio::sync::mpsc::UnboundedSender;
 use crate::{
     app::{Action, ActiveBlock},
     config::Colors,
-    consts::RESPONSE_BODY_UNUSABLE_VERTICAL_SPACE,
+    consts::{
+        JSON_VIEWER_HORIZONTAL_SCROLL_STEP, JSON_VIEWER_MAX_URL_LENGTH,
+        RESPONSE_BODY_UNUSABLE_VERTICAL_SPACE,
+    },
     render::{get_border_style, get_row_style, RowStyle},
+    utils::truncate_middle,
 };
 
+const MIN_INDENT_SPACING: usize = 1;
+
+const MAX_INDENT_SPACING: usize = 8;
+
 #[derive(Default)]
 pub struct JSONViewer {
     active_block: ActiveBlock,
     pub action_tx: Option<UnboundedSender<Action>>,
+    auto_expand_nodes: usize,
+    // Shown in the top right corner, e.g. errors returned with a 200.
+    badge: Option<String>,
+    border_type: BorderType,
     colors: Colors,
-    cursor_position: usize,
+    pub cursor_position: usize,
     data: Option<String>,
     expanded_idxs: Vec<usize>,
+    flatten: bool,
+    focus_envelope_data: bool,
+    horizontal_offset: usize,
     indent_spacing: usize,
     is_active: bool,
     is_expanded: bool,
+    // Set with the data when it isn't JSON, e.g. the placeholder of a binary
+    // gRPC body, so it is shown as received.
+    is_text: bool,
+    line_numbers: bool,
+    page_height: usize,
+    raw: bool,
+    search: String,
     title: String,
+    type_hints: bool,
+    wrap: bool,
 }
 
 impl JSONViewer {
@@ -43,10 +67,39 @@ impl JSONViewer {
             colors,
             indent_spacing,
             title: title.to_string(),
+            wrap: true,
             ..Self::default()
         })
     }
 
+    pub fn with_line_numbers(self, line_numbers: bool) -> Self {
+        Self {
+            line_numbers,
+            ..self
+        }
+    }
+
+    pub fn with_auto_expand_nodes(self, auto_expand_nodes: usize) -> Self {
+        Self {
+            auto_expand_nodes,
+            ..self
+        }
+    }
+
+    pub fn with_focus_envelope_data(self, focus_envelope_data: bool) -> Self {
+        Self {
+            focus_envelope_data,
+            ..self
+        }
+    }
+
+    pub fn with_border_type(self, border_type: BorderType) -> Self {
+        Self {
+            border_type,
+            ..self
+        }
+    }
+
     pub fn register_action_handler(
         &mut self,
         tx: UnboundedSender<Action>,
@@ -55,6 +108,82 @@ impl JSONViewer {
         Ok(())
     }
 
+    // Expands the body so nested values can match, then moves the cursor to
+    // the first matching line.
+    pub fn set_search(&mut self, query: &str) -> Result<(), Box<dyn Error>> {
+        self.search = query.to_string();
+
+        if self.search.is_empty() {
+            return Ok(());
+        }
+
+        if !self.raw && !self.flatten {
+            self.is_expanded = true;
+            self.expanded_idxs.clear();
+        }
+
+        if let Some(position) = self.search_matches()?.first() {
+            self.cursor_position = *position;
+        }
+
+        Ok(())
+    }
+
+    fn should_auto_expand(&self) -> bool {
+        self.auto_expand_nodes > 0
+            && self
+                .data
+                .as_ref()
+                .and_then(|data| serde_json::from_str::<serde_json::Value>(data).ok())
+                .is_some_and(|value| node_count(&value) <= self.auto_expand_nodes)
+    }
+
+    // Expands the top-level `data` key of an enveloped body and moves the
+    // cursor onto it.
+    fn focus_data_key(&mut self) {
+        let position = self
+            .data
+            .as_ref()
+            .and_then(|data| serde_json::from_str::<serde_json::Value>(data).ok())
+            .and_then(|value| {
+                let object = value.as_object()?;
+
+                object.iter().position(|(key, value)| {
+                    key == "data" && (value.is_object() || value.is_array())
+                })
+            });
+
+        if let Some(position) = position {
+            // the opening brace comes first
+            self.expanded_idxs = vec![position + 1];
+            self.cursor_position = position + 1;
+        }
+    }
+
+    // Lines containing the search query, case-insensitively.
+    fn search_matches(&self) -> Result<Vec<usize>, Box<dyn Error>> {
+        if self.search.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let query = self.search.to_lowercase();
+
+        Ok(self
+            .lines()?
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| {
+                line.spans
+                    .iter()
+                    .map(|s| s.content.as_ref())
+                    .collect::<String>()
+                    .to_lowercase()
+                    .contains(&query)
+            })
+            .map(|(idx, _)| idx)
+            .collect())
+    }
+
     pub fn update(&mut self, action: Action) -> Result<Option<Action>, Box<dyn Error>> {
         match action {
             Action::NavigateUp(Some(_)) => {
@@ -69,13 +198,7 @@ impl JSONViewer {
                     return Ok(None);
                 }
 
-                let max_cursor_position = raw_lines(
-                    self.data.clone(),
-                    self.expanded_idxs.clone(),
-                    self.is_expanded,
-                )?
-                .len()
-                .saturating_sub(1);
+                let max_cursor_position = self.lines()?.len().saturating_sub(1);
 
                 if max_cursor_position > self.cursor_position {
                     self.cursor_position = self.cursor_position.saturating_add(1)
@@ -87,13 +210,7 @@ impl JSONViewer {
                 }
 
                 if self.is_expanded {
-                    let max_cursor_position = raw_lines(
-                        self.data.clone(),
-                        self.expanded_idxs.clone(),
-                        self.is_expanded,
-                    )?
-                    .len()
-                    .saturating_sub(1);
+                    let max_cursor_position = self.lines()?.len().saturating_sub(1);
                     self.expanded_idxs = (0..max_cursor_position).collect();
                     self.expanded_idxs.retain(|&x| x != self.cursor_position);
                     self.is_expanded = false
@@ -106,7 +223,7 @@ impl JSONViewer {
                     return Ok(None);
                 }
 
-                if self.is_expanded {
+                if self.is_expanded || self.raw || self.flatten {
                     return Ok(None);
                 }
 
@@ -166,18 +283,195 @@ impl JSONViewer {
                     // TODO(vandosant): shift cursor position to active value
                 }
             }
+            Action::CollapseNode => {
+                if !self.is_active {
+                    return Ok(None);
+                }
+
+                self.collapse_node()?;
+            }
+            Action::UpdateMeta(metadata) => {
+                let height = match self.active_block {
+                    ActiveBlock::RequestBody => metadata.request_body_rectangle_height,
+                    _ => metadata.response_body_rectangle_height,
+                };
+
+                self.page_height =
+                    (height as usize).saturating_sub(RESPONSE_BODY_UNUSABLE_VERTICAL_SPACE);
+            }
+            Action::PageUp => {
+                if !self.is_active {
+                    return Ok(None);
+                }
+
+                self.cursor_position = self.cursor_position.saturating_sub(self.page_height.max(1));
+            }
+            Action::PageDown => {
+                if !self.is_active {
+                    return Ok(None);
+                }
+
+                let max_cursor_position = self.lines()?.len().saturating_sub(1);
+
+                self.cursor_position = self
+                    .cursor_position
+                    .saturating_add(self.page_height.max(1))
+                    .min(max_cursor_position);
+            }
+            Action::ToggleWrap => {
+                if !self.is_active {
+                    return Ok(None);
+                }
+
+                self.wrap = !self.wrap;
+                self.horizontal_offset = 0;
+            }
+            Action::ToggleRawBody => {
+                if !self.is_active {
+                    return Ok(None);
+                }
+
+                self.raw = !self.raw;
+                self.cursor_position = 0;
+                self.horizontal_offset = 0;
+            }
+            Action::ToggleFlattenBody => {
+                if !self.is_active {
+                    return Ok(None);
+                }
+
+                self.flatten = !self.flatten;
+                self.cursor_position = 0;
+                self.horizontal_offset = 0;
+            }
+            Action::NextMatch => {
+                if !self.is_active {
+                    return Ok(None);
+                }
+
+                let matches = self.search_matches()?;
+
+                if let Some(position) = matches
+                    .iter()
+                    .find(|&&idx| idx > self.cursor_position)
+                    .or(matches.first())
+                {
+                    self.cursor_position = *position;
+                }
+            }
+            Action::PreviousMatch => {
+                if !self.is_active {
+                    return Ok(None);
+                }
+
+                let matches = self.search_matches()?;
+
+                if let Some(position) = matches
+                    .iter()
+                    .rev()
+                    .find(|&&idx| idx < self.cursor_position)
+                    .or(matches.last())
+                {
+                    self.cursor_position = *position;
+                }
+            }
+            Action::JumpToMatchingBracket => {
+                if !self.is_active {
+                    return Ok(None);
+                }
+
+                let lines = self.lines()?;
+
+                if let Some(position) = matching_bracket_position(&lines, self.cursor_position) {
+                    self.cursor_position = position;
+                }
+            }
+            Action::GoToParent => {
+                if !self.is_active {
+                    return Ok(None);
+                }
+
+                let lines = self.lines()?;
+
+                if let Some(Some(position)) = parent_positions(&lines).get(self.cursor_position) {
+                    self.cursor_position = *position;
+                }
+            }
+            Action::IncreaseIndent => {
+                if !self.is_active {
+                    return Ok(None);
+                }
+
+                self.indent_spacing = self
+                    .indent_spacing
+                    .saturating_add(1)
+                    .clamp(MIN_INDENT_SPACING, MAX_INDENT_SPACING);
+            }
+            Action::DecreaseIndent => {
+                if !self.is_active {
+                    return Ok(None);
+                }
+
+                self.indent_spacing = self
+                    .indent_spacing
+                    .saturating_sub(1)
+                    .clamp(MIN_INDENT_SPACING, MAX_INDENT_SPACING);
+            }
+            Action::ToggleLineNumbers => {
+                if !self.is_active {
+                    return Ok(None);
+                }
+
+                self.line_numbers = !self.line_numbers;
+            }
+            Action::ToggleTypeHints => {
+                if !self.is_active {
+                    return Ok(None);
+                }
+
+                self.type_hints = !self.type_hints;
+            }
+            Action::GoToRight => {
+                if !self.is_active || self.wrap {
+                    return Ok(None);
+                }
+
+                self.horizontal_offset = self
+                    .horizontal_offset
+                    .saturating_add(JSON_VIEWER_HORIZONTAL_SCROLL_STEP)
+            }
+            Action::GoToLeft => {
+                if !self.is_active || self.wrap {
+                    return Ok(None);
+                }
+
+                self.horizontal_offset = self
+                    .horizontal_offset
+                    .saturating_sub(JSON_VIEWER_HORIZONTAL_SCROLL_STEP)
+            }
             Action::SelectTrace(maybe_trace) => {
                 if let Some(trace) = maybe_trace {
                     if let Some(http) = trace.http {
                         if ActiveBlock::RequestBody == self.active_block {
-                            self.data = http.request_body;
-                            self.is_expanded = false;
+                            self.set_data(http.request_body);
+                            self.is_expanded = self.should_auto_expand();
                             self.expanded_idxs = vec![];
+                            self.horizontal_offset = 0;
                         }
                         if ActiveBlock::ResponseBody == self.active_block {
-                            self.data = http.response_body;
-                            self.is_expanded = false;
+                            self.set_data(http.response_body);
+                            self.is_expanded = self.should_auto_expand();
                             self.expanded_idxs = vec![];
+                            self.horizontal_offset = 0;
+                            self.badge = match http.response_errors {
+                                0 => None,
+                                1 => Some("⚠ 1 error".to_string()),
+                                count => Some(format!("⚠ {} errors", count)),
+                            };
+
+                            if self.focus_envelope_data && !self.is_expanded {
+                                self.focus_data_key();
+                            }
                         }
                     }
                 }
@@ -191,30 +485,161 @@ impl JSONViewer {
         Ok(None)
     }
 
+    fn set_data(&mut self, data: Option<String>) {
+        self.is_text = data
+            .as_ref()
+            .is_some_and(|data| serde_json::from_str::<serde_json::Value>(data).is_err());
+        self.data = data;
+    }
+
+    // The body as displayed, its text as received when `raw` is set or when
+    // it isn't JSON.
+    fn lines(&self) -> Result<Vec<Line<'static>>, Box<dyn Error>> {
+        if self.raw || self.is_text {
+            return Ok(self
+                .data
+                .iter()
+                .flat_map(|data| data.lines())
+                .map(|line| Line::raw(line.to_string()))
+                .collect());
+        }
+
+        if self.flatten {
+            return flat_lines(self.data.clone());
+        }
+
+        raw_lines(
+            self.data.clone(),
+            self.expanded_idxs.clone(),
+            self.is_expanded,
+        )
+    }
+
+    // Collapses the object opened at the cursor, or the one enclosing it,
+    // leaving the cursor on its opening line.
+    fn collapse_node(&mut self) -> Result<(), Box<dyn Error>> {
+        if self.raw || self.flatten {
+            return Ok(());
+        }
+
+        let lines = self.lines()?;
+
+        let position = match lines.get(self.cursor_position) {
+            Some(line) if bracket_depth_change(line) > 0 => Some(self.cursor_position),
+            Some(_) => parent_positions(&lines)[self.cursor_position],
+            None => None,
+        };
+
+        // The root object is always expanded.
+        let position = match position {
+            Some(position) if position > 0 => position,
+            _ => return Ok(()),
+        };
+
+        let end = matching_bracket_position(&lines, position).unwrap_or(position);
+
+        if self.is_expanded {
+            self.expanded_idxs = (0..lines.len()).collect();
+            self.is_expanded = false;
+        }
+
+        let collapsed_len = end - position;
+        self.expanded_idxs = self
+            .expanded_idxs
+            .iter()
+            .filter(|&&idx| idx < position || idx > end)
+            .map(|&idx| if idx > end { idx - collapsed_len } else { idx })
+            .collect();
+        self.cursor_position = position;
+
+        Ok(())
+    }
+
     pub fn render(&self, f: &mut Frame, rect: Rect) -> Result<(), Box<dyn Error>> {
         let padding = Padding::zero();
 
         let outer_area = rect;
 
-        let outer_block = Block::default()
+        let raw_lines = self.lines()?;
+
+        let mut outer_block = Block::default()
             .borders(Borders::ALL)
             .padding(padding)
             .border_style(get_border_style(self.is_active, &self.colors))
-            .title(self.title.to_string())
-            .border_type(BorderType::Plain);
+            .title(if self.raw {
+                format!("{} (raw)", self.title)
+            } else if self.flatten {
+                format!("{} (flat)", self.title)
+            } else {
+                self.title.to_string()
+            })
+            .border_type(self.border_type);
+
+        if let Some(percentage) = scroll_percentage(self.cursor_position, raw_lines.len()) {
+            outer_block = outer_block.title(
+                Title::from(format!(
+                    "  {}/{} {}%  ",
+                    self.cursor_position + 1,
+                    raw_lines.len(),
+                    percentage
+                ))
+                .position(Position::Bottom)
+                .alignment(Alignment::Right),
+            );
+        }
+
+        if let Some(badge) = &self.badge {
+            outer_block = outer_block.title(
+                Title::from(Span::styled(
+                    format!(" {} ", badge),
+                    Style::default()
+                        .fg(self.colors.surface.warning)
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .alignment(Alignment::Right),
+            );
+        }
+
+        if !self.search.is_empty() {
+            outer_block = outer_block.title(
+                Title::from(format!(" /{} ", self.search))
+                    .position(Position::Bottom)
+                    .alignment(Alignment::Left),
+            );
+        }
 
         let inner_area = outer_block.inner(outer_area);
 
-        let inner_layout = Layout::default()
+        let line_number_width = if self.line_numbers {
+            line_number_width(raw_lines.len())
+        } else {
+            0
+        };
+
+        let layout = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Length(4), Constraint::Min(0)])
+            .constraints([
+                Constraint::Length(line_number_width),
+                Constraint::Length(4),
+                Constraint::Min(0),
+            ])
             .split(inner_area);
 
-        let raw_lines = raw_lines(
-            self.data.clone(),
-            self.expanded_idxs.clone(),
-            self.is_expanded,
-        )?;
+        let (line_numbers_area, inner_layout) = (layout[0], &layout[1..]);
+
+        let raw_lines = if self.type_hints && !self.raw && !self.flatten {
+            raw_lines
+                .iter()
+                .map(|line| type_hinted_line(line, &self.colors))
+                .collect()
+        } else {
+            raw_lines
+        };
+
+        let raw_lines: Vec<Line> = raw_lines
+            .iter()
+            .map(|line| linkified_line(line, &self.colors))
+            .collect();
 
         let mut lines: Vec<Line> = raw_lines
             .iter()
@@ -230,29 +655,11 @@ impl JSONViewer {
             })
             .collect();
 
-        let mut indent: usize = 0;
-        for line in lines.iter_mut() {
-            if line
-                .spans
-                .iter()
-                .any(|s| s.content.ends_with('{') || s.content.ends_with("["))
-            {
-                line.spans.insert(0, Span::raw(" ".repeat(indent)));
-                indent = indent.saturating_add(self.indent_spacing);
-            } else if line.spans.iter().any(|s| {
-                !s.content.contains("{..}")
-                    && !s.content.contains("[..]")
-                    && (s.content.ends_with("}")
-                        || s.content.ends_with("},")
-                        || s.content.ends_with("]")
-                        || s.content.ends_with("],"))
-            }) {
-                indent = indent.saturating_sub(self.indent_spacing);
-                line.spans.insert(0, Span::raw(" ".repeat(indent)));
-            } else {
-                line.spans.insert(0, Span::raw(" ".repeat(indent)));
-            }
+        // raw bodies are shown exactly as received, flat ones have no nesting
+        if !self.raw && !self.flatten {
+            indent_lines(&mut lines, self.indent_spacing);
         }
+
         let mut line_indicators = vec![];
         for (idx, line) in lines.iter_mut().enumerate() {
             if idx == 0 {
@@ -308,7 +715,7 @@ impl JSONViewer {
             .height
             .saturating_sub(RESPONSE_BODY_UNUSABLE_VERTICAL_SPACE.try_into()?);
 
-        let json = Paragraph::new(lines)
+        let mut json = Paragraph::new(lines)
             .style(
                 Style::default()
                     .fg(if self.is_active {
@@ -323,9 +730,13 @@ impl JSONViewer {
                     .saturating_sub(available_height.into())
                     .saturating_sub(1)
                     .try_into()?,
-                0,
-            ))
-            .wrap(Wrap { trim: false });
+                self.horizontal_offset.try_into()?,
+            ));
+
+        // raw bodies are often a single long line
+        if self.wrap || self.raw {
+            json = json.wrap(Wrap { trim: false });
+        }
 
         let line_indicators_paragraph = Paragraph::new(line_indicators)
             .alignment(Alignment::Right)
@@ -341,6 +752,29 @@ impl JSONViewer {
         f.render_widget(json, inner_layout[1]);
         f.render_widget(line_indicators_paragraph, inner_layout[0]);
 
+        if self.line_numbers {
+            let line_numbers: Vec<Line> = (1..=number_of_lines)
+                .map(|number| Line::raw(number.to_string()))
+                .collect();
+
+            let line_numbers_paragraph = Paragraph::new(line_numbers)
+                .alignment(Alignment::Right)
+                .style(
+                    Style::default()
+                        .fg(self.colors.text.unselected)
+                        .add_modifier(Modifier::DIM),
+                )
+                .scroll((
+                    self.cursor_position
+                        .saturating_sub(available_height.into())
+                        .saturating_sub(1)
+                        .try_into()?,
+                    0,
+                ));
+
+            f.render_widget(line_numbers_paragraph, line_numbers_area);
+        }
+
         f.render_stateful_widget(
             Scrollbar::default().orientation(ScrollbarOrientation::VerticalRight),
             outer_area.inner(&Margin {
@@ -378,6 +812,51 @@ fn raw_lines(
     Ok(items)
 }
 
+// Every value in the tree, the root included.
+fn node_count(v: &serde_json::Value) -> usize {
+    match v {
+        serde_json::Value::Object(o) => 1 + o.values().map(node_count).sum::<usize>(),
+        serde_json::Value::Array(a) => 1 + a.iter().map(node_count).sum::<usize>(),
+        _ => 1,
+    }
+}
+
+// One `a.b[0].c = value` line per leaf, in document order.
+fn flat_lines(maybe_data: Option<String>) -> Result<Vec<Line<'static>>, Box<dyn Error>> {
+    let mut items = vec![];
+
+    if let Some(data) = maybe_data {
+        let v = serde_json::from_str(data.as_str())?;
+
+        flatten_value("", &v, &mut items);
+    }
+
+    Ok(items.into_iter().map(Line::raw).collect())
+}
+
+fn flatten_value(path: &str, v: &serde_json::Value, items: &mut Vec<String>) {
+    match v {
+        serde_json::Value::Object(o) if !o.is_empty() => {
+            for (k, v) in o {
+                let path = if path.is_empty() {
+                    k.to_string()
+                } else {
+                    format!("{}.{}", path, k)
+                };
+
+                flatten_value(&path, v, items);
+            }
+        }
+        serde_json::Value::Array(a) if !a.is_empty() => {
+            for (i, v) in a.iter().enumerate() {
+                flatten_value(&format!("{}[{}]", path, i), v, items);
+            }
+        }
+        _ if path.is_empty() => items.push(v.to_string()),
+        _ => items.push(format!("{} = {}", path, v)),
+    }
+}
+
 fn value_to_string(v: serde_json::Value) -> Result<String, serde_json::Error> {
     match v {
         serde_json::Value::Array(_) => Ok("[..]".to_string()),
@@ -386,6 +865,227 @@ fn value_to_string(v: serde_json::Value) -> Result<String, serde_json::Error> {
     }
 }
 
+fn indent_lines(lines: &mut [Line], indent_spacing: usize) {
+    let mut indent: usize = 0;
+    for line in lines.iter_mut() {
+        if line
+            .spans
+            .iter()
+            .any(|s| s.content.ends_with('{') || s.content.ends_with("["))
+        {
+            line.spans.insert(0, Span::raw(" ".repeat(indent)));
+            indent = indent.saturating_add(indent_spacing);
+        } else if line.spans.iter().any(|s| {
+            !s.content.contains("{..}")
+                && !s.content.contains("[..]")
+                && (s.content.ends_with("}")
+                    || s.content.ends_with("},")
+                    || s.content.ends_with("]")
+                    || s.content.ends_with("],"))
+        }) {
+            indent = indent.saturating_sub(indent_spacing);
+            line.spans.insert(0, Span::raw(" ".repeat(indent)));
+        } else {
+            line.spans.insert(0, Span::raw(" ".repeat(indent)));
+        }
+    }
+}
+
+// How far through the body the cursor is, `None` for an empty body.
+fn scroll_percentage(cursor_position: usize, number_of_lines: usize) -> Option<usize> {
+    match number_of_lines {
+        0 => None,
+        1 => Some(100),
+        _ => Some(cursor_position.min(number_of_lines - 1) * 100 / (number_of_lines - 1)),
+    }
+}
+
+// Wide enough for the largest line number plus a separating space.
+fn line_number_width(number_of_lines: usize) -> u16 {
+    let digits = number_of_lines.max(1).to_string().len() as u16;
+
+    digits.saturating_add(1)
+}
+
+fn bracket_depth_change(line: &Line) -> i32 {
+    let content: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+    let content = content.trim();
+
+    if content.contains("{..}") || content.contains("[..]") {
+        0
+    } else if content.ends_with('{') || content.ends_with('[') {
+        1
+    } else if content.ends_with('}')
+        || content.ends_with("},")
+        || content.ends_with(']')
+        || content.ends_with("],")
+    {
+        -1
+    } else {
+        0
+    }
+}
+
+fn matching_bracket_position(lines: &[Line], position: usize) -> Option<usize> {
+    let direction = bracket_depth_change(lines.get(position)?);
+
+    if direction == 0 {
+        return None;
+    }
+
+    let mut depth = 0;
+
+    if direction > 0 {
+        for (idx, line) in lines.iter().enumerate().skip(position) {
+            depth += bracket_depth_change(line);
+            if depth == 0 {
+                return Some(idx);
+            }
+        }
+    } else {
+        for (idx, line) in lines.iter().enumerate().take(position + 1).rev() {
+            depth += bracket_depth_change(line);
+            if depth == 0 {
+                return Some(idx);
+            }
+        }
+    }
+
+    None
+}
+
+fn parent_positions(lines: &[Line]) -> Vec<Option<usize>> {
+    let mut parents = vec![];
+    let mut open_positions: Vec<usize> = vec![];
+
+    for (idx, line) in lines.iter().enumerate() {
+        match bracket_depth_change(line) {
+            1 => {
+                parents.push(open_positions.last().copied());
+                open_positions.push(idx);
+            }
+            -1 => {
+                open_positions.pop();
+                parents.push(open_positions.last().copied());
+            }
+            _ => parents.push(open_positions.last().copied()),
+        }
+    }
+
+    parents
+}
+
+fn format_number(raw: &str) -> String {
+    if raw.contains(['e', 'E']) {
+        return raw.to_string();
+    }
+
+    let (sign, unsigned) = match raw.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", raw),
+    };
+
+    let (integer, fraction) = match unsigned.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (unsigned, None),
+    };
+
+    let mut grouped = String::new();
+    for (idx, c) in integer.chars().enumerate() {
+        if idx > 0 && (integer.len() - idx) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+
+    match fraction {
+        Some(fraction) => format!("{sign}{grouped}.{fraction}"),
+        None => format!("{sign}{grouped}"),
+    }
+}
+
+// Splits a rendered line into key, value and trailing comma spans,
+// formatting numeric values and coloring values by their JSON type.
+fn type_hinted_line(line: &Line<'static>, colors: &Colors) -> Line<'static> {
+    let content: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+
+    let (body, trailing) = match content.strip_suffix(',') {
+        Some(body) => (body, ","),
+        None => (content.as_str(), ""),
+    };
+
+    let (key, value) = match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(_) => ("", body),
+        Err(_) => match body.find(r#"": "#) {
+            Some(idx) => body.split_at(idx + 3),
+            None => return line.clone(),
+        },
+    };
+
+    let value_span = match serde_json::from_str::<serde_json::Value>(value) {
+        Ok(serde_json::Value::Number(_)) => Span::styled(
+            format_number(value),
+            Style::default().fg(colors.text.accent_1),
+        ),
+        Ok(serde_json::Value::String(_)) => {
+            Span::styled(value.to_string(), Style::default().fg(colors.text.accent_2))
+        }
+        _ => return line.clone(),
+    };
+
+    let mut spans = vec![];
+    if !key.is_empty() {
+        spans.push(Span::raw(key.to_string()));
+    }
+    spans.push(value_span);
+    if !trailing.is_empty() {
+        spans.push(Span::raw(trailing));
+    }
+
+    Line::from(spans)
+}
+
+fn is_url(value: &str) -> bool {
+    value.starts_with("http://") || value.starts_with("https://")
+}
+
+// Styles string values that look like URLs as links, shortening long ones.
+// Only the rendered line is changed, the underlying data keeps the full URL.
+fn linkified_line(line: &Line<'static>, colors: &Colors) -> Line<'static> {
+    let content: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+
+    let (body, trailing) = match content.strip_suffix(',') {
+        Some(body) => (body, ","),
+        None => (content.as_str(), ""),
+    };
+
+    let (key, value) = match body.find(r#"": "#) {
+        Some(idx) => body.split_at(idx + 3),
+        None => ("", body),
+    };
+
+    let url = match serde_json::from_str::<serde_json::Value>(value) {
+        Ok(serde_json::Value::String(url)) if is_url(&url) => url,
+        _ => return line.clone(),
+    };
+
+    let mut spans = vec![];
+    if !key.is_empty() {
+        spans.push(Span::raw(key.to_string()));
+    }
+    spans.push(Span::styled(
+        format!("\"{}\"", truncate_middle(&url, JSON_VIEWER_MAX_URL_LENGTH)),
+        Style::default()
+            .fg(colors.text.accent_1)
+            .add_modifier(Modifier::UNDERLINED),
+    ));
+    if !trailing.is_empty() {
+        spans.push(Span::raw(trailing));
+    }
+
+    Line::from(spans)
+}
+
 fn array_lines(
     v: Vec<serde_json::Value>,
     key: Option<String>,
@@ -573,11 +1273,28 @@ fn obj_lines(
 
 #[cfg(test)]
 mod tests {
+    use crate::app::{Action, ActiveBlock};
     use crate::components::jsonviewer;
+    use crate::config::Colors;
+    use crate::consts::JSON_VIEWER_MAX_URL_LENGTH;
+    use crate::mock::{TEST_JSON_19, TEST_JSON_21, TEST_JSON_5};
+    use crate::parser::{parse_raw_trace, Payload};
+    use crate::utils;
     use pretty_assertions::assert_eq;
-    use ratatui::prelude::Line;
+    use ratatui::backend::TestBackend;
+    use ratatui::prelude::{Line, Modifier, Span, Style};
+    use ratatui::Terminal;
     use std::error::Error;
 
+    #[test]
+    fn test_scroll_percentage() {
+        assert_eq!(None, jsonviewer::scroll_percentage(0, 0));
+        assert_eq!(Some(100), jsonviewer::scroll_percentage(0, 1));
+        assert_eq!(Some(0), jsonviewer::scroll_percentage(0, 21));
+        assert_eq!(Some(50), jsonviewer::scroll_percentage(10, 21));
+        assert_eq!(Some(100), jsonviewer::scroll_percentage(20, 21));
+    }
+
     #[test]
     fn test_raw_empty() -> Result<(), Box<dyn Error>> {
         let result = jsonviewer::raw_lines(None, vec![], true)?;
@@ -870,4 +1587,550 @@ mod tests {
 
         Ok(())
     }
+
+    fn rendered_text(viewer: &jsonviewer::JSONViewer) -> Result<String, Box<dyn Error>> {
+        let mut terminal = Terminal::new(TestBackend::new(24, 8))?;
+
+        terminal.draw(|f| viewer.render(f, f.size()).unwrap())?;
+
+        Ok(terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect())
+    }
+
+    #[test]
+    fn test_toggle_wrap() -> Result<(), Box<dyn Error>> {
+        let mut viewer = jsonviewer::JSONViewer::new(
+            ActiveBlock::ResponseBody,
+            4,
+            "Response body",
+            Default::default(),
+        )?;
+        viewer.data = Some(r#"{"token": "abcdefghijklmnopqrstuvwxyz"}"#.to_string());
+
+        viewer.update(Action::ActivateBlock(ActiveBlock::ResponseBody))?;
+
+        assert!(viewer.wrap);
+        assert!(rendered_text(&viewer)?.contains("xyz"));
+
+        viewer.update(Action::ToggleWrap)?;
+
+        assert!(!viewer.wrap);
+        assert!(!rendered_text(&viewer)?.contains("xyz"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_collapse_node() -> Result<(), Box<dyn Error>> {
+        let mut viewer = jsonviewer::JSONViewer::new(
+            ActiveBlock::ResponseBody,
+            4,
+            "Response body",
+            Default::default(),
+        )?;
+        viewer.data = Some(r#"{"a": {"b": 1, "c": 2}, "d": {"e": 3}}"#.to_string());
+
+        viewer.update(Action::ActivateBlock(ActiveBlock::ResponseBody))?;
+        viewer.update(Action::ExpandAll)?;
+
+        // Cursor on `"c": 2`, inside the first object.
+        viewer.cursor_position = 3;
+        viewer.update(Action::CollapseNode)?;
+
+        assert_eq!(1, viewer.cursor_position);
+        assert_eq!(
+            vec![
+                Line::raw("{"),
+                Line::raw(r#""a": {..},"#),
+                Line::raw(r#""d": {"#),
+                Line::raw(r#""e": 3"#),
+                Line::raw("}"),
+                Line::raw("}"),
+            ],
+            jsonviewer::raw_lines(viewer.data.clone(), viewer.expanded_idxs.clone(), false)?,
+        );
+
+        // Cursor on the opening line of the second object.
+        viewer.cursor_position = 2;
+        viewer.update(Action::CollapseNode)?;
+
+        assert_eq!(2, viewer.cursor_position);
+        assert_eq!(
+            vec![
+                Line::raw("{"),
+                Line::raw(r#""a": {..},"#),
+                Line::raw(r#""d": {..}"#),
+                Line::raw("}"),
+            ],
+            jsonviewer::raw_lines(viewer.data.clone(), viewer.expanded_idxs.clone(), false)?,
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!("0", jsonviewer::format_number("0"));
+        assert_eq!("999", jsonviewer::format_number("999"));
+        assert_eq!("1,616,239,022", jsonviewer::format_number("1616239022"));
+        assert_eq!("-12,345.678", jsonviewer::format_number("-12345.678"));
+        assert_eq!("1e10", jsonviewer::format_number("1e10"));
+    }
+
+    #[test]
+    fn test_type_hinted_lines() -> Result<(), Box<dyn Error>> {
+        let colors = Colors::default();
+
+        let raw = jsonviewer::raw_lines(
+            Some(r#"{"timestamp": 1616239022, "name": "Juan", "empty": null}"#.to_string()),
+            vec![],
+            false,
+        )?;
+
+        assert_eq!(Line::raw(r#""timestamp": 1616239022,"#), raw[1]);
+
+        let hinted: Vec<Line> = raw
+            .iter()
+            .map(|line| jsonviewer::type_hinted_line(line, &colors))
+            .collect();
+
+        assert_eq!(
+            vec![
+                Line::raw("{"),
+                Line::from(vec![
+                    Span::raw(r#""timestamp": "#),
+                    Span::styled("1,616,239,022", Style::default().fg(colors.text.accent_1)),
+                    Span::raw(","),
+                ]),
+                Line::from(vec![
+                    Span::raw(r#""name": "#),
+                    Span::styled(r#""Juan""#, Style::default().fg(colors.text.accent_2)),
+                    Span::raw(","),
+                ]),
+                Line::raw(r#""empty": null"#),
+                Line::raw("}"),
+            ],
+            hinted,
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_linkified_lines() -> Result<(), Box<dyn Error>> {
+        let colors = Colors::default();
+        let link_style = Style::default()
+            .fg(colors.text.accent_1)
+            .add_modifier(Modifier::UNDERLINED);
+
+        let long_url = format!("https://example.com/{}/end", "a".repeat(80));
+        let raw = jsonviewer::raw_lines(
+            Some(
+                serde_json::json!({
+                    "docs": "https://example.com/docs",
+                    "long": long_url,
+                    "name": "http",
+                })
+                .to_string(),
+            ),
+            vec![],
+            false,
+        )?;
+
+        let linkified: Vec<Line> = raw
+            .iter()
+            .map(|line| jsonviewer::linkified_line(line, &colors))
+            .collect();
+
+        assert_eq!(
+            vec![
+                Line::raw("{"),
+                Line::from(vec![
+                    Span::raw(r#""docs": "#),
+                    Span::styled(r#""https://example.com/docs""#, link_style),
+                    Span::raw(","),
+                ]),
+                Line::from(vec![
+                    Span::raw(r#""long": "#),
+                    Span::styled(
+                        format!(
+                            "\"{}\"",
+                            utils::truncate_middle(&long_url, JSON_VIEWER_MAX_URL_LENGTH)
+                        ),
+                        link_style,
+                    ),
+                    Span::raw(","),
+                ]),
+                Line::raw(r#""name": "http""#),
+                Line::raw("}"),
+            ],
+            linkified,
+        );
+        assert!(linkified[2].spans[1].content.contains('…'));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_matching_bracket_position() -> Result<(), Box<dyn Error>> {
+        let input = serde_json::json!({
+            "one": {
+                "a": 1,
+                "two": {
+                    "c": 3
+                },
+                "three": [
+                    5,
+                    6
+                ]
+            },
+            "four": {
+                "d": 4
+            }
+        });
+
+        let lines = jsonviewer::raw_lines(Some(input.to_string()), vec![], true)?;
+
+        // 0  {
+        // 1    "one": {
+        // 2      "a": 1,
+        // 3      "two": {
+        // 4        "c": 3
+        // 5      },
+        // 6      "three": [
+        // 7        5,
+        // 8        6
+        // 9      ]
+        // 10   },
+        // 11   "four": {
+        // 12     "d": 4
+        // 13   }
+        // 14 }
+        assert_eq!(Some(14), jsonviewer::matching_bracket_position(&lines, 0));
+        assert_eq!(Some(0), jsonviewer::matching_bracket_position(&lines, 14));
+        assert_eq!(Some(10), jsonviewer::matching_bracket_position(&lines, 1));
+        assert_eq!(Some(1), jsonviewer::matching_bracket_position(&lines, 10));
+        assert_eq!(Some(5), jsonviewer::matching_bracket_position(&lines, 3));
+        assert_eq!(Some(9), jsonviewer::matching_bracket_position(&lines, 6));
+        assert_eq!(Some(6), jsonviewer::matching_bracket_position(&lines, 9));
+        assert_eq!(Some(13), jsonviewer::matching_bracket_position(&lines, 11));
+        assert_eq!(None, jsonviewer::matching_bracket_position(&lines, 2));
+
+        let collapsed = jsonviewer::raw_lines(Some(input.to_string()), vec![], false)?;
+
+        assert_eq!(None, jsonviewer::matching_bracket_position(&collapsed, 1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parent_positions() -> Result<(), Box<dyn Error>> {
+        let response_body = match parse_raw_trace(TEST_JSON_19)? {
+            Payload::Trace(trace) => trace.http.unwrap_or_default().response_body,
+            _ => None,
+        };
+
+        let lines = jsonviewer::raw_lines(response_body, vec![], true)?;
+        let parents = jsonviewer::parent_positions(&lines);
+
+        // 0  {
+        // 1    "name": "Juan J Hartley",
+        // ...
+        // 5    "phones": [
+        // 6      "+44 1234567",
+        // 7      "+44 2345678"
+        // 8    ],
+        // 9    "age": 43,
+        // 10   "nested_one": {
+        // 11     "name": "Imogene Thompson",
+        // 12     "phones_one": [
+        // 13       "+44 1234567",
+        // 14       "+44 2345678"
+        // 15     ],
+        // 16     "deep_nested": {
+        // 17       "name": "Sandy Feldstein"
+        // 18     }
+        // 19   },
+        assert_eq!(lines.len(), parents.len());
+        assert_eq!(None, parents[0]);
+        assert_eq!(Some(0), parents[1]);
+        assert_eq!(Some(5), parents[6]);
+        assert_eq!(Some(0), parents[8]);
+        assert_eq!(Some(10), parents[11]);
+        assert_eq!(Some(12), parents[13]);
+        assert_eq!(Some(16), parents[17]);
+        assert_eq!(Some(10), parents[18]);
+        assert_eq!(Some(0), parents[19]);
+        assert_eq!(None, parents[lines.len() - 1]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_line_number_width() {
+        assert_eq!(2, jsonviewer::line_number_width(0));
+        assert_eq!(2, jsonviewer::line_number_width(9));
+        assert_eq!(3, jsonviewer::line_number_width(10));
+        assert_eq!(4, jsonviewer::line_number_width(999));
+        assert_eq!(5, jsonviewer::line_number_width(1000));
+    }
+
+    #[test]
+    fn test_indent_spacing() -> Result<(), Box<dyn Error>> {
+        let input = serde_json::json!({ "one": { "a": 1 } });
+
+        let leading_spaces = |indent_spacing: usize| -> Result<Vec<usize>, Box<dyn Error>> {
+            let mut lines = jsonviewer::raw_lines(Some(input.to_string()), vec![], true)?;
+
+            jsonviewer::indent_lines(&mut lines, indent_spacing);
+
+            Ok(lines
+                .iter()
+                .map(|line| line.spans[0].content.len())
+                .collect())
+        };
+
+        assert_eq!(vec![0, 4, 8, 4, 0], leading_spaces(4)?);
+        assert_eq!(vec![0, 2, 4, 2, 0], leading_spaces(2)?);
+
+        let mut viewer = jsonviewer::JSONViewer::new(
+            ActiveBlock::RequestBody,
+            8,
+            "Request body",
+            Default::default(),
+        )?;
+
+        viewer.update(Action::ActivateBlock(ActiveBlock::RequestBody))?;
+        viewer.update(Action::IncreaseIndent)?;
+
+        assert_eq!(8, viewer.indent_spacing);
+
+        viewer.update(Action::DecreaseIndent)?;
+
+        assert_eq!(7, viewer.indent_spacing);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_toggle_raw_body() -> Result<(), Box<dyn Error>> {
+        let mut viewer = jsonviewer::JSONViewer::new(
+            ActiveBlock::ResponseBody,
+            4,
+            "Response body",
+            Default::default(),
+        )?;
+        viewer.data = Some(r#"{"code":200,"ok":true}"#.to_string());
+
+        viewer.update(Action::ActivateBlock(ActiveBlock::ResponseBody))?;
+        viewer.update(Action::ExpandAll)?;
+
+        assert_eq!(4, viewer.lines()?.len());
+        assert!(!rendered_text(&viewer)?.contains(r#"{"code":200"#));
+
+        viewer.update(Action::ToggleRawBody)?;
+
+        assert_eq!(
+            vec![Line::raw(r#"{"code":200,"ok":true}"#)],
+            viewer.lines()?
+        );
+        assert!(rendered_text(&viewer)?.contains(r#"{"code":200"#));
+
+        viewer.update(Action::ToggleRawBody)?;
+
+        assert_eq!(4, viewer.lines()?.len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_flatten_body() -> Result<(), Box<dyn Error>> {
+        let mut viewer = jsonviewer::JSONViewer::new(
+            ActiveBlock::ResponseBody,
+            4,
+            "Response body",
+            Default::default(),
+        )?;
+        viewer.data = match parse_raw_trace(TEST_JSON_19)? {
+            Payload::Trace(trace) => trace.http.unwrap_or_default().response_body,
+            _ => None,
+        };
+
+        viewer.update(Action::ActivateBlock(ActiveBlock::ResponseBody))?;
+        viewer.update(Action::ToggleFlattenBody)?;
+
+        let lines = viewer.lines()?;
+
+        assert_eq!(
+            vec![
+                Line::raw(r#"name = "Juan J Hartley""#),
+                Line::raw("empty = null"),
+                Line::raw("boolean_a = true"),
+                Line::raw("boolean_b = false"),
+                Line::raw(r#"phones[0] = "+44 1234567""#),
+                Line::raw(r#"phones[1] = "+44 2345678""#),
+                Line::raw("age = 43"),
+                Line::raw(r#"nested_one.name = "Imogene Thompson""#),
+                Line::raw(r#"nested_one.phones_one[0] = "+44 1234567""#),
+                Line::raw(r#"nested_one.phones_one[1] = "+44 2345678""#),
+                Line::raw(r#"nested_one.deep_nested.name = "Sandy Feldstein""#),
+                Line::raw(r#"nested_two.name = "Imogene Thompson""#),
+            ],
+            lines[..12]
+        );
+        assert_eq!(23, lines.len());
+        assert_eq!(
+            Line::raw(r#"nested_seven.deep_nested.name = "Sandy Feldstein""#),
+            lines[22]
+        );
+
+        viewer.update(Action::ToggleFlattenBody)?;
+
+        assert_eq!(Line::raw("{"), viewer.lines()?[0]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_body_search() -> Result<(), Box<dyn Error>> {
+        let mut viewer = jsonviewer::JSONViewer::new(
+            ActiveBlock::ResponseBody,
+            4,
+            "Response body",
+            Default::default(),
+        )?;
+        viewer.data = Some(r#"{"code":200,"nested":{"name":"x","code":1}}"#.to_string());
+
+        viewer.update(Action::ActivateBlock(ActiveBlock::ResponseBody))?;
+        viewer.set_search("CODE")?;
+
+        // the nested object was expanded to be searched
+        assert_eq!(7, viewer.lines()?.len());
+        assert_eq!(1, viewer.cursor_position);
+
+        viewer.update(Action::NextMatch)?;
+
+        assert_eq!(4, viewer.cursor_position);
+
+        viewer.update(Action::NextMatch)?;
+
+        assert_eq!(1, viewer.cursor_position);
+
+        viewer.update(Action::PreviousMatch)?;
+
+        assert_eq!(4, viewer.cursor_position);
+        assert!(rendered_text(&viewer)?.contains("/CODE"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_auto_expand_small_bodies() -> Result<(), Box<dyn Error>> {
+        let mut viewer = jsonviewer::JSONViewer::new(
+            ActiveBlock::ResponseBody,
+            4,
+            "Response body",
+            Default::default(),
+        )?
+        .with_auto_expand_nodes(20);
+
+        let select = |viewer: &mut jsonviewer::JSONViewer, raw| -> Result<(), Box<dyn Error>> {
+            if let Payload::Trace(trace) = parse_raw_trace(raw)? {
+                viewer.update(Action::SelectTrace(Some(trace)))?;
+            }
+
+            Ok(())
+        };
+
+        select(&mut viewer, TEST_JSON_5)?;
+
+        assert!(viewer.is_expanded);
+        assert_eq!(
+            vec![Line::raw("{"), Line::raw(r#""id": "4""#), Line::raw("}")],
+            viewer.lines()?
+        );
+
+        select(&mut viewer, TEST_JSON_19)?;
+
+        assert!(!viewer.is_expanded);
+        assert!(viewer
+            .lines()?
+            .iter()
+            .any(|line| line.spans.iter().any(|s| s.content.contains("{..}"))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_envelope_data_and_errors() -> Result<(), Box<dyn Error>> {
+        let trace = match parse_raw_trace(TEST_JSON_21)? {
+            Payload::Trace(trace) => trace,
+            _ => panic!("expected a trace"),
+        };
+
+        let http = trace.http.clone().unwrap_or_default();
+
+        assert_eq!(Some(http::StatusCode::OK), http.status);
+        assert_eq!(1, http.response_errors);
+
+        let mut viewer = jsonviewer::JSONViewer::new(
+            ActiveBlock::ResponseBody,
+            4,
+            "Response body",
+            Default::default(),
+        )?
+        .with_focus_envelope_data(true);
+
+        viewer.update(Action::SelectTrace(Some(trace)))?;
+
+        assert_eq!(Some("⚠ 1 error".to_string()), viewer.badge);
+        assert_eq!(2, viewer.cursor_position);
+        assert_eq!(
+            vec![
+                Line::raw("{"),
+                Line::raw(r#""errors": [..],"#),
+                Line::raw(r#""data": {"#),
+                Line::raw(r#""person": null"#),
+                Line::raw("}"),
+                Line::raw("}"),
+            ],
+            viewer.lines()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_text_body_shown_as_received() -> Result<(), Box<dyn Error>> {
+        let trace = |body: &str| crate::services::websocket::Trace {
+            http: Some(crate::services::websocket::HTTPTrace {
+                response_body: Some(body.to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let mut viewer = jsonviewer::JSONViewer::new(
+            ActiveBlock::ResponseBody,
+            4,
+            "Response body",
+            Default::default(),
+        )?;
+
+        viewer.update(Action::SelectTrace(Some(trace("<binary 12 bytes>"))))?;
+
+        assert!(viewer.is_text);
+        assert_eq!(vec![Line::raw("<binary 12 bytes>")], viewer.lines()?);
+
+        viewer.update(Action::SelectTrace(Some(trace(r#"{"a":1}"#))))?;
+
+        assert!(!viewer.is_text);
+
+        Ok(())
+    }
 }
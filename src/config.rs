@@ -1,12 +1,15 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::error::Error;
 use std::fs;
+use std::str::FromStr;
 
+use chrono::format::{Item, StrftimeItems};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use ratatui::style::Color;
+use ratatui::{style::Color, widgets::BorderType};
+use regex::Regex;
 use serde::{de::Deserializer, Deserialize};
 
-use crate::app::Action;
+use crate::app::{Action, DetailsPane, DetailsSort, TimeColumn, TraceColumn};
 
 const CONFIG: &str = include_str!("../.config/config.yml");
 
@@ -33,17 +36,339 @@ impl<'de> Deserialize<'de> for Mapping {
 pub struct Config {
     #[serde(default)]
     pub mapping: Mapping,
-    #[serde(default)]
+    #[serde(default, rename = "colors")]
+    pub raw_colors: RawColors,
+    #[serde(skip)]
     pub colors: Colors,
+    // Problems found while loading, reported in the debug log.
+    #[serde(skip)]
+    pub warnings: Vec<String>,
+    #[serde(default)]
+    pub alerts: AlertConfig,
+    #[serde(default)]
+    pub json_viewer: JSONViewerConfig,
+    #[serde(default)]
+    pub hide_patterns: Vec<String>,
+    #[serde(default)]
+    pub mask_query_params: Vec<String>,
+    #[serde(default)]
+    pub columns: Vec<String>,
+    #[serde(default)]
+    pub time_column: TimeColumn,
+    #[serde(default)]
+    pub compact: bool,
+    #[serde(default)]
+    pub details_sort: DetailsSort,
+    #[serde(default = "default_time_format")]
+    pub time_format: String,
+    #[serde(default)]
+    pub border_style: BorderStyle,
+    #[serde(default)]
+    pub details_panes: Vec<DetailsPane>,
+    #[serde(default)]
+    pub redact: RedactConfig,
+    #[serde(default = "default_request_timeout_seconds")]
+    pub request_timeout_seconds: u64,
+    #[serde(default = "default_max_body_bytes")]
+    pub max_body_bytes: usize,
+    // Replaces focusing the details when enter is pressed on a trace.
+    #[serde(default)]
+    pub traces_enter_action: Option<Action>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BorderStyle {
+    #[default]
+    Plain,
+    Rounded,
+    Double,
+    Thick,
+}
+
+pub fn block_border(config: &Config) -> BorderType {
+    match config.border_style {
+        BorderStyle::Plain => BorderType::Plain,
+        BorderStyle::Rounded => BorderType::Rounded,
+        BorderStyle::Double => BorderType::Double,
+        BorderStyle::Thick => BorderType::Thick,
+    }
+}
+
+fn default_time_format() -> String {
+    "%Y-%m-%d @ %H:%M:%S".to_string()
+}
+
+fn default_request_timeout_seconds() -> u64 {
+    30
+}
+
+fn default_max_body_bytes() -> usize {
+    1024 * 1024
+}
+
+fn is_valid_time_format(format: &str) -> bool {
+    !StrftimeItems::new(format).any(|item| item == Item::Error)
+}
+
+// Navigation and input actions would leave enter on a trace doing nothing
+// useful, or recurse into the enter handler itself for `Select`.
+fn is_valid_enter_action(action: &Action) -> bool {
+    !matches!(
+        action,
+        Action::Select
+            | Action::NavigateLeft(_)
+            | Action::NavigateDown(_)
+            | Action::NavigateUp(_)
+            | Action::NavigateRight(_)
+            | Action::GoToRight
+            | Action::GoToLeft
+            | Action::GoToEnd
+            | Action::GoToStart
+            | Action::NextSection
+            | Action::PreviousSection
+            | Action::PageUp
+            | Action::PageDown
+            | Action::UpdateSearchQuery(_)
+            | Action::DeleteSearchQuery
+            | Action::ExitSearch
+            | Action::SearchHistoryPrevious
+            | Action::SearchHistoryNext
+            | Action::UpdateFilterInput(_)
+            | Action::DeleteFilterInput
+            | Action::UpdateAnnotationInput(_)
+            | Action::DeleteAnnotationInput
+            | Action::UpdateTraceIndex(_)
+            | Action::SelectTrace(_)
+            | Action::UpdateMeta(_)
+            | Action::Tick
+    )
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct AlertConfig {
+    pub bell: bool,
+    pub on_server_error: bool,
+    pub slow_response_ms: Option<u32>,
+}
+
+// Same as the bundled config, so a user config without `alerts` keeps them.
+impl Default for AlertConfig {
+    fn default() -> Self {
+        Self {
+            bell: false,
+            on_server_error: true,
+            slow_response_ms: Some(3000),
+        }
+    }
+}
+
+// Header names and query param keys, matched case-insensitively, whose
+// values are replaced when copying requests.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct RedactConfig {
+    pub enabled: bool,
+    pub keys: Vec<String>,
+}
+
+impl Default for RedactConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            keys: vec![],
+        }
+    }
 }
 
+// Bodies with at most `auto_expand_nodes` JSON values open fully expanded,
+// `0` always opens them collapsed.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct JSONViewerConfig {
+    pub line_numbers: bool,
+    pub auto_expand_nodes: usize,
+    pub focus_envelope_data: bool,
+}
+
+impl Default for JSONViewerConfig {
+    fn default() -> Self {
+        Self {
+            line_numbers: false,
+            auto_expand_nodes: 20,
+            focus_envelope_data: false,
+        }
+    }
+}
+
+// Colors as written in the config file, validated into `Colors` by
+// `Colors::apply`.
 #[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct RawColors {
+    pub surface: BTreeMap<String, serde_yaml::Value>,
+    pub text: BTreeMap<String, serde_yaml::Value>,
+}
+
+#[derive(Clone, Debug, Default)]
 pub struct Colors {
     pub surface: SurfaceColors,
     pub text: TextColors,
+    // Set when colors are turned off, selections and focus are then shown
+    // with text attributes instead.
+    pub monochrome: bool,
 }
 
-#[derive(Clone, Debug, Default, Deserialize)]
+impl Colors {
+    // Every color left to the terminal's default.
+    pub fn monochrome() -> Self {
+        Self {
+            monochrome: true,
+            ..Self::default()
+        }
+    }
+
+    // Overrides each field that holds a valid color. Invalid or unknown
+    // entries keep the current value and are reported in the returned
+    // warnings.
+    pub fn apply(&mut self, raw: &RawColors) -> Vec<String> {
+        let mut warnings = vec![];
+
+        for (section, values) in [("surface", &raw.surface), ("text", &raw.text)] {
+            for (name, value) in values {
+                let color = parse_color_value(value);
+
+                match (self.field_mut(section, name), color) {
+                    (Some(field), Some(color)) => *field = color,
+                    (Some(_), None) => warnings.push(format!(
+                        "invalid color \"{}\" for colors.{}.{} ignored",
+                        value
+                            .as_str()
+                            .map(str::to_string)
+                            .unwrap_or_else(|| format!("{:?}", value)),
+                        section,
+                        name
+                    )),
+                    (None, _) => {
+                        warnings.push(format!("unknown color colors.{}.{} ignored", section, name))
+                    }
+                }
+            }
+        }
+
+        warnings
+    }
+
+    fn field_mut(&mut self, section: &str, name: &str) -> Option<&mut Color> {
+        match (section, name) {
+            ("surface", "bg") => Some(&mut self.surface.bg),
+            ("surface", "selected") => Some(&mut self.surface.selected),
+            ("surface", "unselected") => Some(&mut self.surface.unselected),
+            ("surface", "success") => Some(&mut self.surface.success),
+            ("surface", "error") => Some(&mut self.surface.error),
+            ("surface", "warning") => Some(&mut self.surface.warning),
+            ("surface", "null") => Some(&mut self.surface.null),
+            ("text", "selected") => Some(&mut self.text.selected),
+            ("text", "unselected") => Some(&mut self.text.unselected),
+            ("text", "default") => Some(&mut self.text.default),
+            ("text", "accent_1") => Some(&mut self.text.accent_1),
+            ("text", "accent_2") => Some(&mut self.text.accent_2),
+            _ => None,
+        }
+    }
+}
+
+// Colors are turned off by a non-empty `NO_COLOR` (see no-color.org) or the
+// `--no-color` flag.
+pub fn is_no_color(no_color_var: Option<&str>, mut args: impl Iterator<Item = String>) -> bool {
+    no_color_var.is_some_and(|value| !value.is_empty()) || args.any(|arg| arg == "--no-color")
+}
+
+// Accepts `#rgb`, `#rrggbb`, `rgb(r, g, b)`, a 256-color index or one of the
+// 16 ANSI color names.
+pub fn parse_color(raw: &str) -> Option<Color> {
+    let raw = raw.trim().to_lowercase();
+
+    if let Some(hex) = raw.strip_prefix('#') {
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        let channel = |digits: &str| u8::from_str_radix(digits, 16).ok();
+
+        return match hex.len() {
+            3 => Some(Color::Rgb(
+                channel(&hex[0..1])? * 17,
+                channel(&hex[1..2])? * 17,
+                channel(&hex[2..3])? * 17,
+            )),
+            6 => Some(Color::Rgb(
+                channel(&hex[0..2])?,
+                channel(&hex[2..4])?,
+                channel(&hex[4..6])?,
+            )),
+            _ => None,
+        };
+    }
+
+    if let Some(channels) = raw
+        .strip_prefix("rgb(")
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        let channels = channels
+            .split(',')
+            .map(|channel| channel.trim().parse::<u8>().ok())
+            .collect::<Option<Vec<u8>>>()?;
+
+        return match channels[..] {
+            [r, g, b] => Some(Color::Rgb(r, g, b)),
+            _ => None,
+        };
+    }
+
+    if let Ok(index) = raw.parse::<u8>() {
+        return Some(Color::Indexed(index));
+    }
+
+    let index = match raw.replace(['_', '-', ' '], "").as_str() {
+        "black" => 0,
+        "red" => 1,
+        "green" => 2,
+        "yellow" => 3,
+        "blue" => 4,
+        "magenta" => 5,
+        "cyan" => 6,
+        "white" | "gray" | "grey" => 7,
+        "brightblack" | "darkgray" | "darkgrey" => 8,
+        "brightred" | "lightred" => 9,
+        "brightgreen" | "lightgreen" => 10,
+        "brightyellow" | "lightyellow" => 11,
+        "brightblue" | "lightblue" => 12,
+        "brightmagenta" | "lightmagenta" => 13,
+        "brightcyan" | "lightcyan" => 14,
+        "brightwhite" | "lightwhite" => 15,
+        _ => return None,
+    };
+
+    Some(Color::Indexed(index))
+}
+
+// Colors may also be written as a bare index or as `!Indexed 235`.
+fn parse_color_value(value: &serde_yaml::Value) -> Option<Color> {
+    match value {
+        serde_yaml::Value::String(raw) => parse_color(raw),
+        serde_yaml::Value::Number(index) => u8::try_from(index.as_u64()?).ok().map(Color::Indexed),
+        serde_yaml::Value::Tagged(tagged)
+            if tagged.tag == "Indexed" && tagged.value.is_number() =>
+        {
+            parse_color_value(&tagged.value)
+        }
+        _ => None,
+    }
+}
+
+#[derive(Clone, Debug, Default)]
 pub struct SurfaceColors {
     pub bg: Color,
     pub selected: Color,
@@ -54,7 +379,7 @@ pub struct SurfaceColors {
     pub null: Color,
 }
 
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default)]
 pub struct TextColors {
     pub selected: Color,
     pub unselected: Color,
@@ -64,7 +389,25 @@ pub struct TextColors {
 }
 
 pub fn parse(contents: &str) -> Result<Config, Box<dyn Error>> {
-    let config = serde_yaml::from_str::<Config>(contents)?;
+    let mut config = serde_yaml::from_str::<Config>(contents)?;
+    config.warnings = config.colors.apply(&config.raw_colors);
+    // an invalid time format falls back to the default one
+    if !is_valid_time_format(&config.time_format) {
+        config.warnings.push(format!(
+            "invalid time format \"{}\" ignored",
+            config.time_format
+        ));
+        config.time_format = default_time_format();
+    }
+    match &config.traces_enter_action {
+        Some(action) if !is_valid_enter_action(action) => {
+            config
+                .warnings
+                .push(format!("traces_enter_action {:?} ignored", action));
+            config.traces_enter_action = None;
+        }
+        _ => {}
+    }
     Ok(config)
 }
 
@@ -82,16 +425,77 @@ impl Config {
 
         for file in &["config.yaml", "config.yml"] {
             match load(file) {
-                Ok(right) => {
-                    cfg.mapping.0.extend(right.mapping.0.into_iter());
-                    // cfg.colors.extend(right.colors.0.into_iter())
-                }
+                Ok(right) => cfg.merge(right),
                 Err(e) => println!("failed to load file: {}, err: {}", file, e),
             }
         }
 
+        if is_no_color(std::env::var("NO_COLOR").ok().as_deref(), std::env::args()) {
+            cfg.colors = Colors::monochrome();
+        }
+
         Ok(cfg)
     }
+
+    // Applies a user config over this one.
+    fn merge(&mut self, right: Config) {
+        self.mapping.0.extend(right.mapping.0.into_iter());
+        self.hide_patterns.extend(right.hide_patterns.into_iter());
+        self.mask_query_params
+            .extend(right.mask_query_params.into_iter());
+        self.time_column = right.time_column;
+        self.compact = right.compact;
+        self.details_sort = right.details_sort;
+        self.time_format = right.time_format;
+        self.border_style = right.border_style;
+        self.details_panes = right.details_panes;
+        self.redact.enabled = right.redact.enabled;
+        self.redact.keys.extend(right.redact.keys.into_iter());
+        self.request_timeout_seconds = right.request_timeout_seconds;
+        self.max_body_bytes = right.max_body_bytes;
+        self.alerts = right.alerts;
+        self.traces_enter_action = right.traces_enter_action;
+        self.json_viewer = right.json_viewer;
+        if !right.columns.is_empty() {
+            self.columns = right.columns;
+        }
+        // invalid colors fall back to the built-in theme, the user config
+        // already carries the warnings for them
+        self.colors.apply(&right.raw_colors);
+        self.warnings.extend(right.warnings);
+    }
+}
+
+// Unknown column names are skipped and reported in the returned warnings.
+pub fn parse_trace_columns(names: &[String]) -> (Vec<TraceColumn>, Vec<String>) {
+    let mut columns = vec![];
+    let mut warnings = vec![];
+
+    for name in names {
+        match TraceColumn::from_str(&name.to_lowercase()) {
+            Ok(column) if !columns.contains(&column) => columns.push(column),
+            Ok(_) => {}
+            Err(_) => warnings.push(format!("unknown trace list column \"{}\" ignored", name)),
+        }
+    }
+
+    (columns, warnings)
+}
+
+// Patterns that are not valid regexes are skipped and reported in the returned
+// warnings.
+pub fn parse_hide_patterns(patterns: &[String]) -> (Vec<Regex>, Vec<String>) {
+    let mut regexes = vec![];
+    let mut warnings = vec![];
+
+    for pattern in patterns {
+        match Regex::new(pattern) {
+            Ok(regex) => regexes.push(regex),
+            Err(_) => warnings.push(format!("invalid hide pattern \"{}\" ignored", pattern)),
+        }
+    }
+
+    (regexes, warnings)
 }
 
 fn parse_key_event(raw: &str) -> Result<KeyEvent, String> {
@@ -154,6 +558,8 @@ mod tests {
     use super::*;
     use std::error::Error;
 
+    use crate::utils::format_timestamp;
+
     #[test]
     fn test_config() -> Result<(), Box<dyn Error>> {
         let c = Config::new()?;
@@ -164,6 +570,78 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_merge_user_alerts() -> Result<(), Box<dyn Error>> {
+        let mut config = parse(CONFIG)?;
+
+        assert!(!config.alerts.bell);
+
+        config.merge(parse("alerts:\n  bell: true\n  slow_response_ms: 500")?);
+
+        assert!(config.alerts.bell);
+        assert!(config.alerts.on_server_error);
+        assert_eq!(Some(500), config.alerts.slow_response_ms);
+
+        // a user config without alerts keeps the bundled ones
+        config.merge(parse("compact: true")?);
+
+        assert!(!config.alerts.bell);
+        assert!(config.alerts.on_server_error);
+        assert_eq!(Some(3000), config.alerts.slow_response_ms);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_time_format() -> Result<(), Box<dyn Error>> {
+        let c = parse("time_format: \"%I:%M:%S %p\"")?;
+
+        assert_eq!(
+            "01:30:05 PM",
+            format_timestamp(1700487005000, &c.time_format)
+        );
+        assert_eq!(
+            "2023-11-20 @ 13:30:05",
+            format_timestamp(1700487005000, &parse("")?.time_format)
+        );
+
+        let invalid = parse("time_format: \"%Q\"")?;
+
+        assert_eq!(default_time_format(), invalid.time_format);
+        assert_eq!(
+            vec!["invalid time format \"%Q\" ignored".to_string()],
+            invalid.warnings
+        );
+
+        let mut merged = parse(CONFIG)?;
+        merged.merge(invalid);
+
+        assert_eq!(default_time_format(), merged.time_format);
+        assert_eq!(1, merged.warnings.len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_block_border() -> Result<(), Box<dyn Error>> {
+        assert_eq!(BorderType::Plain, block_border(&parse("")?));
+        assert_eq!(
+            BorderType::Rounded,
+            block_border(&parse("border_style: rounded")?)
+        );
+        assert_eq!(
+            BorderType::Double,
+            block_border(&parse("border_style: double")?)
+        );
+        assert_eq!(
+            BorderType::Thick,
+            block_border(&parse("border_style: thick")?)
+        );
+        assert!(parse("border_style: dotted").is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn test_parse_color_rgb() {
         let color = parse_color("rgb(255,255,255)");
@@ -181,4 +659,78 @@ mod tests {
         let color = parse_color("unknown");
         assert_eq!(color, None);
     }
+
+    #[test]
+    fn test_parse_color_hex() {
+        assert_eq!(Some(Color::Rgb(255, 170, 0)), parse_color("#fa0"));
+        assert_eq!(Some(Color::Rgb(0, 255, 127)), parse_color("#00FF7F"));
+        assert_eq!(None, parse_color("#GGG"));
+        assert_eq!(None, parse_color("#12345"));
+        assert_eq!(None, parse_color("12345"));
+    }
+
+    #[test]
+    fn test_invalid_color_falls_back() -> Result<(), Box<dyn Error>> {
+        let mut config = parse(CONFIG)?;
+
+        assert!(config.warnings.is_empty());
+        assert_eq!(Color::Indexed(37), config.colors.text.accent_1);
+
+        let user = parse(
+            "colors:
+  text:
+    accent_1: \"#GGG\"
+    accent_2: \"#0af\"
+    default: light-cyan
+  surface:
+    border: red",
+        )?;
+        let warnings = config.colors.apply(&user.raw_colors);
+
+        assert_eq!(Color::Indexed(37), config.colors.text.accent_1);
+        assert_eq!(Color::Rgb(0, 170, 255), config.colors.text.accent_2);
+        assert_eq!(Color::Indexed(14), config.colors.text.default);
+        assert_eq!(
+            vec![
+                "unknown color colors.surface.border ignored",
+                "invalid color \"#GGG\" for colors.text.accent_1 ignored",
+            ],
+            warnings
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_color() -> Result<(), Box<dyn Error>> {
+        let args = |raw: &[&str]| raw.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        assert!(is_no_color(Some("1"), args(&["envy"]).into_iter()));
+        assert!(is_no_color(None, args(&["envy", "--no-color"]).into_iter()));
+        assert!(!is_no_color(Some(""), args(&["envy"]).into_iter()));
+        assert!(!is_no_color(None, args(&["envy"]).into_iter()));
+
+        let colors = Colors::monochrome();
+
+        assert!(colors.monochrome);
+        assert_eq!(Color::Reset, colors.surface.selected);
+        assert_eq!(Color::Reset, colors.surface.error);
+        assert_eq!(Color::Reset, colors.text.accent_1);
+        assert!(!parse(CONFIG)?.colors.monochrome);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_hide_patterns() {
+        let patterns = vec![r"/features$".to_string(), "(unclosed".to_string()];
+        let (regexes, warnings) = parse_hide_patterns(&patterns);
+
+        assert_eq!(1, regexes.len());
+        assert!(regexes[0].is_match("/api/features"));
+        assert_eq!(
+            vec!["invalid hide pattern \"(unclosed\" ignored".to_string()],
+            warnings
+        );
+    }
 }
@@ -1,13 +1,64 @@
+use std::time::Duration;
+
 pub const RESPONSE_BODY_UNUSABLE_HORIZONTAL_SPACE: usize = 2;
 
 pub const RESPONSE_BODY_UNUSABLE_VERTICAL_SPACE: usize = 2;
 
-pub const RESPONSE_HEADERS_UNUSABLE_VERTICAL_SPACE: usize = 6;
-
-pub const REQUEST_HEADERS_UNUSABLE_VERTICAL_SPACE: usize = 6;
-
 pub const REQUEST_BODY_UNUSABLE_HORIZONTAL_SPACE: usize = 2;
 
 pub const REQUEST_BODY_UNUSABLE_VERTICAL_SPACE: usize = 2;
 
 pub const NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE: usize = 4;
+
+// Compact mode drops the margin below the header.
+pub const COMPACT_NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE: usize = 3;
+
+pub const TRACES_COLUMN_MIN_WIDTH: u16 = 4;
+
+pub const TRACES_COLUMN_MAX_WIDTH: u16 = 24;
+
+pub const TRACES_REQUEST_MIN_WIDTH: u16 = 20;
+
+pub const JSON_VIEWER_HORIZONTAL_SCROLL_STEP: usize = 4;
+
+pub const JSON_VIEWER_MAX_URL_LENGTH: usize = 60;
+
+pub const LARGEST_RESPONSES_COUNT: usize = 10;
+
+// one day
+pub const MAX_DURATION_MS: u32 = 86_400_000;
+
+// Prefix of debug log lines, millis help telling ingestion and render apart.
+pub const LOG_TIME_FORMAT: &str = "%H:%M:%S%.3f";
+
+// Sliding window the footer's trace rate is averaged over.
+pub const TRACE_RATE_WINDOW: Duration = Duration::from_secs(10);
+
+// `data` fields of the collector protocol, the others are kept as extras.
+pub const TRACE_DATA_FIELDS: [&str; 5] = ["id", "type", "timestamp", "serviceName", "http"];
+
+pub const FILTER_SOURCES: [&str; 9] = [
+    "method",
+    "source",
+    "status",
+    "key",
+    "range",
+    "content-type",
+    "window",
+    "presets",
+    "combine",
+];
+
+pub const FILTER_PRESETS_FILE: &str = "presets.yml";
+
+pub const ANNOTATIONS_FILE: &str = "annotations.yml";
+
+pub const KEY_MAP_EXPORT_FILE: &str = "keymap.toml";
+
+pub const MOCK_STUB_EXPORT_FILE: &str = "mocks.json";
+
+pub const SEARCH_HISTORY_LIMIT: usize = 10;
+
+pub const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+pub const CONTEXT_MENU_WIDTH: u16 = 24;
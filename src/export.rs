@@ -0,0 +1,87 @@
+use http::Uri;
+use serde_json::{json, Map, Value};
+
+use crate::services::websocket::Trace;
+
+// Maps `METHOD /path` to the captured response. Bodies that are JSON are
+// embedded as is, anything else is kept as a string.
+pub fn to_mock_stubs(traces: &[&Trace]) -> String {
+    let mut stubs = Map::new();
+
+    for trace in traces {
+        let http = match &trace.http {
+            Some(http) => http,
+            None => continue,
+        };
+
+        let headers: Map<String, Value> = http
+            .response_headers
+            .iter()
+            .filter_map(|(name, value)| {
+                let value = value.to_str().ok()?;
+
+                Some((name.to_string(), Value::String(value.to_string())))
+            })
+            .collect();
+
+        let body = http.response_body.as_ref().map(|body| {
+            serde_json::from_str::<Value>(body).unwrap_or_else(|_| Value::String(body.clone()))
+        });
+
+        let path = http
+            .uri
+            .parse::<Uri>()
+            .ok()
+            .and_then(|uri| uri.path_and_query().map(|path| path.to_string()))
+            .unwrap_or_else(|| "/".to_string());
+
+        stubs.insert(
+            format!("{} {}", http.method, path),
+            json!({
+                "status": http.status.map(|status| status.as_u16()),
+                "headers": headers,
+                "body": body,
+            }),
+        );
+    }
+
+    serde_json::to_string_pretty(&stubs).unwrap_or_default()
+}
+
+pub fn to_mock_stub(trace: &Trace) -> String {
+    to_mock_stubs(&[trace])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::error::Error;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::mock;
+    use crate::parser::{parse_raw_trace, Payload};
+
+    #[test]
+    fn test_mock_stub() -> Result<(), Box<dyn Error>> {
+        let trace = match parse_raw_trace(mock::TEST_JSON_3)? {
+            Payload::Trace(trace) => trace,
+            _ => panic!("expected a trace"),
+        };
+
+        let stub: Value = serde_json::from_str(&to_mock_stub(&trace))?;
+
+        assert_eq!(json!(200), stub["GET /features"]["status"]);
+        assert_eq!(
+            json!("application/json; charset=utf-8"),
+            stub["GET /features"]["headers"]["content-type"]
+        );
+        assert_eq!(
+            json!({"awesomeFeature": true, "crappyFeature": false}),
+            stub["GET /features"]["body"]
+        );
+
+        Ok(())
+    }
+}
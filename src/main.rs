@@ -1,12 +1,17 @@
+mod annotations;
 mod app;
 mod components;
 mod config;
 mod consts;
+mod export;
 mod mock;
 mod parser;
+mod presets;
 mod render;
 mod services;
 mod tui;
+#[cfg(unix)]
+mod uds;
 mod utils;
 mod wss;
 
@@ -17,6 +22,11 @@ use app::App;
 async fn tokio_main() -> Result<(), Box<dyn Error>> {
     let mut app = App::new()?;
 
+    #[cfg(unix)]
+    {
+        app.uds_path = uds::parse_uds_arg(std::env::args());
+    }
+
     app.run().await?;
 
     Ok(())
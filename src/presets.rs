@@ -0,0 +1,141 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::{FilterCombination, FilterScreen, SourceFilter, TimeWindow, TraceFilter};
+
+pub type FilterPresets = HashMap<String, TraceFilter>;
+
+// The on-disk shape of a `TraceFilter`: only the selected methods and
+// statuses are stored, everything else is kept as is.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FilterPreset {
+    pub methods: Vec<String>,
+    pub statuses: Vec<String>,
+    pub source: SourceFilter,
+    pub key: String,
+    pub status_range: String,
+    pub content_type: SourceFilter,
+    pub combination: FilterCombination,
+    pub time_window: TimeWindow,
+    pub inverted: HashSet<FilterScreen>,
+}
+
+impl From<TraceFilter> for FilterPreset {
+    fn from(filter: TraceFilter) -> Self {
+        let mut methods = filter
+            .method
+            .values()
+            .filter(|method_filter| method_filter.selected)
+            .map(|method_filter| method_filter.name.clone())
+            .collect::<Vec<_>>();
+        let mut statuses = filter
+            .status
+            .values()
+            .filter(|status_filter| status_filter.selected)
+            .map(|status_filter| status_filter.name.clone())
+            .collect::<Vec<_>>();
+
+        methods.sort();
+        statuses.sort();
+
+        FilterPreset {
+            methods,
+            statuses,
+            source: filter.source,
+            key: filter.key,
+            status_range: filter.status_range,
+            content_type: filter.content_type,
+            combination: filter.combination,
+            time_window: filter.time_window,
+            inverted: filter.inverted,
+        }
+    }
+}
+
+impl From<FilterPreset> for TraceFilter {
+    fn from(preset: FilterPreset) -> Self {
+        let mut filter = TraceFilter::default();
+
+        filter.method.values_mut().for_each(|method_filter| {
+            method_filter.selected = preset.methods.contains(&method_filter.name);
+        });
+        filter.status.values_mut().for_each(|status_filter| {
+            status_filter.selected = preset.statuses.contains(&status_filter.name);
+        });
+
+        TraceFilter {
+            source: preset.source,
+            key: preset.key,
+            status_range: preset.status_range,
+            content_type: preset.content_type,
+            combination: preset.combination,
+            time_window: preset.time_window,
+            inverted: preset.inverted,
+            ..filter
+        }
+    }
+}
+
+pub fn parse(contents: &str) -> Result<FilterPresets, Box<dyn Error>> {
+    let presets = serde_yaml::from_str::<FilterPresets>(contents)?;
+    Ok(presets)
+}
+
+pub fn load(path: &str) -> Result<FilterPresets, Box<dyn Error>> {
+    let contents = fs::read_to_string(path)?;
+    parse(&contents)
+}
+
+pub fn save(path: &str, presets: &FilterPresets) -> Result<(), Box<dyn Error>> {
+    let contents = serde_yaml::to_string(presets)?;
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_preset_round_trip() -> Result<(), Box<dyn Error>> {
+        let mut filter = TraceFilter::default();
+
+        if let Some(method_filter) = filter.method.get_mut(&http::Method::POST) {
+            method_filter.selected = true;
+        }
+        if let Some(status_filter) = filter.status.get_mut("5xx") {
+            status_filter.selected = true;
+        }
+        filter.source = SourceFilter::Applied(HashSet::from(["api.example.com".to_string()]));
+        filter.status_range = "400..=499,!404".to_string();
+        filter.combination = FilterCombination::Or;
+        filter.time_window = TimeWindow::LastSeconds(60);
+        filter.inverted.insert(FilterScreen::Method);
+
+        let path = std::env::temp_dir().join("envy-test-presets.yml");
+        let path = path.to_str().unwrap();
+
+        save(
+            path,
+            &FilterPresets::from([("errors".to_string(), filter.clone())]),
+        )?;
+
+        let presets = load(path)?;
+
+        fs::remove_file(path)?;
+
+        assert_eq!(vec!["errors"], presets.keys().collect::<Vec<_>>());
+        assert_eq!(
+            FilterPreset::from(filter),
+            FilterPreset::from(presets["errors"].clone())
+        );
+
+        Ok(())
+    }
+}
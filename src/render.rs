@@ -1,6 +1,9 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::mem;
+use std::time::{Instant, SystemTime};
 
 use crossterm::event::{KeyCode, KeyEvent};
+use http::Uri;
 use ratatui::prelude::{Alignment, Constraint, Direction, Layout, Margin, Rect};
 use ratatui::text::{Line, Span};
 use ratatui::{
@@ -9,25 +12,39 @@ use ratatui::{
     symbols::border,
     widgets::{
         block::{Position, Title},
-        canvas, Block, BorderType, Borders, Cell, Clear, List, ListItem, Padding, Paragraph, Row,
-        Scrollbar, ScrollbarOrientation, Table, Tabs, Widget,
+        canvas, Block, Borders, Cell, Clear, List, ListItem, Padding, Paragraph, Row, Scrollbar,
+        ScrollbarOrientation, Table, Tabs, Widget, Wrap,
     },
     Frame,
 };
+use strum::IntoEnumIterator;
+use unicode_width::UnicodeWidthStr;
 
+use crate::annotations;
 use crate::app::{
-    Action, ActiveBlock,
+    Action, ActiveBlock, DetailsPane,
     DetailsPane::{
-        QueryParams, RequestDetails, RequestHeaders, ResponseDetails, ResponseHeaders, Timing,
+        Curl, Extra, QueryParams, RequestDetails, RequestHeaders, ResponseDetails, ResponseHeaders,
+        Timing,
     },
-    FilterScreen, SortScreen, SourceFilter, WebSocketInternalState,
+    FilterScreen, LogLevel, SortScreen, SourceFilter, TimeColumn, TraceColumn, UIState,
+    WebSocketInternalState, DEFAULT_TRACE_COLUMNS,
 };
 use crate::components::actionable_list::ActionableList;
 use crate::components::home::Home;
 use crate::config::Colors;
-use crate::consts::NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE;
-use crate::services::websocket::Trace;
-use crate::utils::{get_rendered_items, truncate};
+use crate::consts::{
+    CONTEXT_MENU_WIDTH, FILTER_SOURCES, LARGEST_RESPONSES_COUNT, LOG_TIME_FORMAT,
+    TRACES_COLUMN_MAX_WIDTH, TRACES_COLUMN_MIN_WIDTH, TRACES_REQUEST_MIN_WIDTH,
+};
+use crate::services::websocket::{State, Trace, TraceKind};
+use crate::utils::{
+    format_relative_time, format_size, format_timestamp, format_uptime, get_content_type,
+    get_filter_screen, get_filtered_count, get_hidden_count, get_largest_responses,
+    get_match_count, get_rendered_items, get_selected_trace_url, get_spinner_glyph, get_trace_age,
+    is_new_trace, mask_query_params, parse_status_range, trace_rate, truncate, truncate_middle,
+    wrap,
+};
 
 #[derive(Clone, Copy, PartialEq, Debug, Hash, Eq)]
 pub enum RowStyle {
@@ -48,13 +65,21 @@ pub fn get_row_style(row_style: RowStyle, colors: &Colors) -> Style {
 
     let active_style = Style::default().fg(colors.text.default);
 
-    let selected_style = Style::default()
-        .fg(colors.text.selected)
-        .bg(colors.surface.selected);
+    let selected_style = if colors.monochrome {
+        Style::default().add_modifier(Modifier::REVERSED)
+    } else {
+        Style::default()
+            .fg(colors.text.selected)
+            .bg(colors.surface.selected)
+    };
 
-    let inactive_style = Style::default()
-        .fg(colors.text.selected)
-        .bg(colors.surface.unselected);
+    let inactive_style = if colors.monochrome {
+        Style::default().add_modifier(Modifier::UNDERLINED)
+    } else {
+        Style::default()
+            .fg(colors.text.selected)
+            .bg(colors.surface.unselected)
+    };
 
     match row_style {
         RowStyle::Default => default_style,
@@ -64,8 +89,83 @@ pub fn get_row_style(row_style: RowStyle, colors: &Colors) -> Style {
     }
 }
 
+// Requests still waiting on a response are dimmed so they stand out from
+// completed ones.
+fn get_trace_row_style(
+    selected: bool,
+    state: &State,
+    active_block: ActiveBlock,
+    colors: &Colors,
+) -> Style {
+    let style = match (selected, active_block) {
+        (true, ActiveBlock::Traces) => get_row_style(RowStyle::Selected, colors),
+        (false, ActiveBlock::Traces) => get_row_style(RowStyle::Active, colors),
+        (true, _) => get_row_style(RowStyle::Inactive, colors),
+        (false, _) => get_row_style(RowStyle::Default, colors),
+    };
+
+    match state {
+        State::Sent => style.add_modifier(Modifier::DIM | Modifier::ITALIC),
+        State::Aborted => style.add_modifier(Modifier::DIM | Modifier::CROSSED_OUT),
+        _ => style,
+    }
+}
+
+struct TraceRow {
+    cells: Vec<String>,
+    request_prefix: String,
+    request_suffix: String,
+    selected: bool,
+    state: State,
+    new: bool,
+    // underlined to divide the new rows from the ones seen before
+    divider: bool,
+}
+
+// Every column is sized to its widest cell within the bounds, the `flexible`
+// one takes whatever space is left.
+fn get_traces_column_widths(
+    header: &[&str],
+    rows: &[Vec<String>],
+    flexible: Option<usize>,
+    available: u16,
+) -> Vec<u16> {
+    let mut widths: Vec<u16> = header
+        .iter()
+        .enumerate()
+        .map(|(i, title)| {
+            rows.iter()
+                .filter_map(|row| row.get(i))
+                .map(|cell| cell.width())
+                .fold(title.width(), usize::max)
+                .try_into()
+                .unwrap_or(u16::MAX)
+                .clamp(TRACES_COLUMN_MIN_WIDTH, TRACES_COLUMN_MAX_WIDTH)
+        })
+        .collect();
+
+    let spacing = header.len().saturating_sub(1) as u16;
+
+    let used: u16 = widths
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| Some(*i) != flexible)
+        .map(|(_, width)| width)
+        .sum();
+
+    if let Some(width) = flexible.and_then(|flexible| widths.get_mut(flexible)) {
+        *width = available
+            .saturating_sub(used + spacing)
+            .max(TRACES_REQUEST_MIN_WIDTH);
+    }
+
+    widths
+}
+
 pub fn get_border_style(active: bool, colors: &Colors) -> Style {
-    if active {
+    if active && colors.monochrome {
+        Style::default().add_modifier(Modifier::BOLD)
+    } else if active {
         Style::default().fg(colors.surface.selected)
     } else {
         Style::default().fg(colors.surface.unselected)
@@ -80,128 +180,83 @@ fn get_text_style(active: bool, colors: &Colors) -> Style {
     }
 }
 
-pub fn details(app: &mut Home, frame: &mut Frame, area: Rect) {
-    let mut cells: Vec<Rect> = vec![];
+// One line naming the selected trace, so it stays clear which trace the
+// panes belong to however they are laid out.
+pub fn get_request_summary(app: &Home, width: usize) -> String {
+    let http = match app
+        .selected_trace
+        .as_ref()
+        .and_then(|trace| trace.http.as_ref())
+    {
+        Some(http) => http,
+        None => return "No trace selected".to_string(),
+    };
 
-    match app.details_panes.len() {
-        0 => cells.push(area),
-        1 => cells.extend(
-            Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints([Constraint::Ratio(2, 3), Constraint::Ratio(1, 3)].as_ref())
-                .split(area)
-                .iter(),
-        ),
-        2 => {
-            let columns = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints([Constraint::Ratio(2, 3), Constraint::Ratio(1, 3)].as_ref())
-                .split(area);
+    let method = http.method.to_string();
+    let status = http
+        .status
+        .map_or("-".to_string(), |status| status.as_str().to_string());
+    let duration = http
+        .duration
+        .map_or("-".to_string(), |duration| format!("{}ms", duration));
+    let url_width = width.saturating_sub(method.len() + status.len() + duration.len() + 3);
+    let url = truncate_middle(&get_selected_trace_url(app).unwrap_or_default(), url_width);
+
+    format!("{} {} {} {}", method, url, status, duration)
+}
 
-            cells.push(columns[0]);
-            cells.extend(
-                Layout::default()
-                    .direction(Direction::Vertical)
-                    .constraints([Constraint::Ratio(1, 3), Constraint::Ratio(2, 3)].as_ref())
-                    .split(columns[1])
-                    .iter(),
-            )
-        }
-        3 => {
-            let rows = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([Constraint::Ratio(2, 3), Constraint::Ratio(1, 3)].as_ref())
-                .split(area);
+// Splits the area into `count` cells laid out in rows of equal height, the
+// last row sharing its width between the cells left over.
+fn grid_cells(area: Rect, count: usize) -> Vec<Rect> {
+    let count = count.max(1);
+    let columns = (1..)
+        .find(|columns| columns * columns >= count)
+        .unwrap_or(1);
+    let rows = count.div_ceil(columns);
 
-            let top_row_columns = Layout::default()
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Ratio(1, rows as u32); rows])
+        .split(area)
+        .iter()
+        .enumerate()
+        .flat_map(|(row, &row_area)| {
+            let row_count = columns.min(count - row * columns);
+
+            Layout::default()
                 .direction(Direction::Horizontal)
-                .constraints([Constraint::Ratio(2, 3), Constraint::Ratio(1, 3)].as_ref())
-                .split(rows[0]);
-
-            cells.push(top_row_columns[0]);
-            cells.extend(
-                Layout::default()
-                    .direction(Direction::Vertical)
-                    .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)].as_ref())
-                    .split(top_row_columns[1])
-                    .iter(),
-            );
+                .constraints(vec![Constraint::Ratio(1, row_count as u32); row_count])
+                .split(row_area)
+                .to_vec()
+        })
+        .collect()
+}
 
-            // add bottom row
-            cells.push(rows[1]);
-        }
-        4 => {
-            let rows = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([Constraint::Ratio(2, 3), Constraint::Ratio(1, 3)].as_ref())
-                .split(area);
+pub fn details(app: &mut Home, frame: &mut Frame, area: Rect) {
+    let [summary_area, area] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .areas(area);
 
-            let top_row_columns = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints([Constraint::Ratio(2, 3), Constraint::Ratio(1, 3)].as_ref())
-                .split(rows[0]);
-
-            cells.push(top_row_columns[0]);
-            cells.extend(
-                Layout::default()
-                    .direction(Direction::Vertical)
-                    .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)].as_ref())
-                    .split(top_row_columns[1])
-                    .iter(),
-            );
+    frame.render_widget(
+        Paragraph::new(get_request_summary(app, summary_area.width.into()))
+            .style(get_text_style(true, &app.colors)),
+        summary_area,
+    );
 
-            // split bottom row
-            cells.extend(
-                Layout::default()
-                    .direction(Direction::Horizontal)
-                    .constraints([Constraint::Ratio(1, 3), Constraint::Ratio(2, 3)].as_ref())
-                    .split(rows[1])
-                    .iter(),
-            );
-        }
-        5 => {
-            let rows = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([Constraint::Ratio(2, 3), Constraint::Ratio(1, 3)].as_ref())
-                .split(area);
+    // When every pane is popped out there is no tabs cell to reserve.
+    let has_tabs = !app.details_tabs.is_empty();
 
-            let top_row_columns = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints([Constraint::Ratio(2, 3), Constraint::Ratio(1, 3)].as_ref())
-                .split(rows[0]);
-
-            cells.push(top_row_columns[0]);
-            cells.extend(
-                Layout::default()
-                    .direction(Direction::Vertical)
-                    .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)].as_ref())
-                    .split(top_row_columns[1])
-                    .iter(),
-            );
+    let cells = grid_cells(area, app.details_panes.len() + usize::from(has_tabs));
 
-            // split bottom row
-            cells.extend(
-                Layout::default()
-                    .direction(Direction::Horizontal)
-                    .constraints([Constraint::Ratio(1, 3), Constraint::Ratio(2, 3)].as_ref())
-                    .constraints(
-                        [
-                            Constraint::Ratio(1, 3),
-                            Constraint::Ratio(1, 3),
-                            Constraint::Ratio(1, 3),
-                        ]
-                        .as_ref(),
-                    )
-                    .split(rows[1])
-                    .iter(),
-            );
-        }
-        _ => cells.push(area),
+    let pane_cells = if has_tabs {
+        details_tabs(app, frame, cells[0]);
+        &cells[1..]
+    } else {
+        &cells[..]
     };
 
-    details_tabs(app, frame, cells[0]);
-
-    for (idx, &cell) in cells[1..].iter().enumerate() {
+    for (idx, &cell) in pane_cells.iter().enumerate() {
         details_pane(app, frame, cell, idx);
     }
 }
@@ -218,6 +273,8 @@ pub fn details_pane(app: &mut Home, frame: &mut Frame, area: Rect, pane_idx: usi
                 .constraints([Constraint::Min(1)].as_ref())
                 .split(area);
 
+            let title = format!("  {}{}  ", pane, headers_filter_suffix(app, *pane));
+
             let actionable_list = match pane {
                 RequestDetails => &mut app.request_details_list,
                 QueryParams => &mut app.query_params_list,
@@ -225,10 +282,12 @@ pub fn details_pane(app: &mut Home, frame: &mut Frame, area: Rect, pane_idx: usi
                 ResponseDetails => &mut app.response_details_list,
                 ResponseHeaders => &mut app.response_headers_list,
                 Timing => &mut app.timing_list,
+                Curl => &mut app.curl_list,
+                Extra => &mut app.extra_list,
             };
 
             let details_block = Block::default()
-                .title(format!("  {}  ", pane))
+                .title(title)
                 .title(
                     Title::from(format!(
                         "  {} OF {}  ",
@@ -239,7 +298,7 @@ pub fn details_pane(app: &mut Home, frame: &mut Frame, area: Rect, pane_idx: usi
                     .alignment(Alignment::Right),
                 )
                 .border_style(get_border_style(is_active, &app.colors))
-                .border_type(BorderType::Plain)
+                .border_type(app.border_type)
                 .borders(Borders::ALL);
 
             frame.render_widget(details_block, area);
@@ -253,6 +312,16 @@ pub fn details_pane(app: &mut Home, frame: &mut Frame, area: Rect, pane_idx: usi
                     &app.colors,
                     is_active,
                 );
+            } else if pane.is_curl() {
+                render_curl_command(
+                    &app.curl_command,
+                    &mut app.curl,
+                    actionable_list,
+                    inner_layout[0],
+                    frame,
+                    &app.colors,
+                    is_active,
+                );
             } else {
                 render_actionable_list(
                     actionable_list,
@@ -266,30 +335,64 @@ pub fn details_pane(app: &mut Home, frame: &mut Frame, area: Rect, pane_idx: usi
     }
 }
 
+// Tab label, with the number of entries for the panes that list them.
+pub fn details_tab_title(app: &Home, pane: DetailsPane) -> String {
+    let list = match pane {
+        QueryParams => &app.query_params_list,
+        RequestHeaders => &app.request_headers_list,
+        ResponseHeaders => &app.response_headers_list,
+        Extra => &app.extra_list,
+        _ => return pane.to_string(),
+    };
+
+    // the action row is not an entry
+    let count = list
+        .items
+        .iter()
+        .filter(|item| item.action.is_none())
+        .count();
+
+    format!("{} ({}){}", pane, count, headers_filter_suffix(app, pane))
+}
+
+// Headers panes narrowed by a search say so in their title.
+fn headers_filter_suffix(app: &Home, pane: DetailsPane) -> String {
+    match pane {
+        RequestHeaders | ResponseHeaders if !app.headers_search.is_empty() => {
+            format!(" filtered: {}", app.headers_search)
+        }
+        _ => String::new(),
+    }
+}
+
 pub fn details_tabs(app: &mut Home, frame: &mut Frame, area: Rect) {
     if let Some(selected_trace) = &app.selected_trace {
         let is_active = app.active_block == ActiveBlock::Details
             && app.details_tabs.contains(&app.details_block);
 
-        let tabs = Tabs::new(app.details_tabs.iter().map(|t| t.to_string()))
-            .block(
-                Block::default()
-                    .borders(Borders::BOTTOM)
-                    .border_style(get_border_style(is_active, &app.colors))
-                    .border_type(BorderType::Plain)
-                    .border_set(border::DOUBLE),
-            )
-            .select(app.details_tab_index)
-            .style(Style::default().fg(if is_active {
-                app.colors.text.accent_1
-            } else {
-                app.colors.text.unselected
-            }))
-            .highlight_style(Style::default().fg(if is_active {
-                app.colors.text.accent_2
-            } else {
-                app.colors.text.unselected
-            }));
+        let tabs = Tabs::new(
+            app.details_tabs
+                .iter()
+                .map(|pane| details_tab_title(app, *pane)),
+        )
+        .block(
+            Block::default()
+                .borders(Borders::BOTTOM)
+                .border_style(get_border_style(is_active, &app.colors))
+                .border_type(app.border_type)
+                .border_set(border::DOUBLE),
+        )
+        .select(app.details_tab_index)
+        .style(Style::default().fg(if is_active {
+            app.colors.text.accent_1
+        } else {
+            app.colors.text.unselected
+        }))
+        .highlight_style(Style::default().fg(if is_active {
+            app.colors.text.accent_2
+        } else {
+            app.colors.text.unselected
+        }));
 
         let inner_layout = Layout::default()
             .vertical_margin(2)
@@ -310,6 +413,8 @@ pub fn details_tabs(app: &mut Home, frame: &mut Frame, area: Rect) {
             ResponseDetails => &mut app.response_details_list,
             ResponseHeaders => &mut app.response_headers_list,
             Timing => &mut app.timing_list,
+            Curl => &mut app.curl_list,
+            Extra => &mut app.extra_list,
         };
 
         let details_block = Block::default()
@@ -324,7 +429,7 @@ pub fn details_tabs(app: &mut Home, frame: &mut Frame, area: Rect) {
                 .alignment(Alignment::Right),
             )
             .border_style(get_border_style(is_active, &app.colors))
-            .border_type(BorderType::Plain)
+            .border_type(app.border_type)
             .borders(Borders::ALL);
 
         frame.render_widget(details_block, area);
@@ -339,6 +444,16 @@ pub fn details_tabs(app: &mut Home, frame: &mut Frame, area: Rect) {
                 &app.colors,
                 app.active_block == ActiveBlock::Details && app.details_block == *tab_block,
             );
+        } else if tab_block.is_curl() {
+            render_curl_command(
+                &app.curl_command,
+                &mut app.curl,
+                actionable_list,
+                inner_layout[1],
+                frame,
+                &app.colors,
+                app.active_block == ActiveBlock::Details && app.details_block == *tab_block,
+            );
         } else {
             render_actionable_list(
                 actionable_list,
@@ -351,6 +466,72 @@ pub fn details_tabs(app: &mut Home, frame: &mut Frame, area: Rect) {
     }
 }
 
+// Number of rows `text` takes up when wrapped on words, as `Wrap` does.
+fn get_wrapped_height(text: &str, width: usize) -> usize {
+    let width = width.max(1);
+    let mut rows = 1;
+    let mut row_width = 0;
+
+    for word in text.split(' ') {
+        let word_width = word.width();
+        let needed = if row_width == 0 {
+            word_width
+        } else {
+            row_width + 1 + word_width
+        };
+
+        if needed <= width {
+            row_width = needed;
+        } else {
+            // Words wider than a row are broken up over several rows.
+            rows += usize::from(row_width > 0) + word_width.saturating_sub(1) / width;
+            row_width = (word_width.saturating_sub(1) % width) + 1;
+        }
+    }
+
+    rows
+}
+
+fn render_curl_command(
+    command: &str,
+    scroll: &mut UIState,
+    actionable_list: &mut ActionableList,
+    area: Rect,
+    frame: &mut Frame,
+    colors: &Colors,
+    active: bool,
+) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+        .split(area);
+
+    let text = if command.is_empty() {
+        "No HTTP request to build a cURL command from."
+    } else {
+        command
+    };
+
+    let wrapped_height = get_wrapped_height(text, layout[0].width as usize);
+
+    scroll.offset = scroll
+        .offset
+        .min(wrapped_height.saturating_sub(layout[0].height as usize));
+
+    let paragraph = Paragraph::new(text)
+        .style(Style::default().fg(if active {
+            colors.text.default
+        } else {
+            colors.text.unselected
+        }))
+        .wrap(Wrap { trim: false })
+        .scroll((scroll.offset as u16, 0));
+
+    frame.render_widget(paragraph, layout[0]);
+
+    render_actionable_list(actionable_list, frame, layout[1], colors, active);
+}
+
 fn render_timing_chart(
     trace: &Trace,
     actionable_list: &mut ActionableList,
@@ -377,7 +558,11 @@ fn render_timing_chart(
                 timings.wait.into(),
                 timings.receive.into(),
             ];
-            let total = timings_vec.clone().iter().fold(0.0, |a, b| a + b);
+            // the reported total may include time outside of the phases
+            let total = timings_vec
+                .iter()
+                .sum::<f64>()
+                .max(timings.duration().into());
 
             let chart_layout = Layout::default()
                 .direction(Direction::Vertical)
@@ -424,7 +609,7 @@ fn render_timing_chart(
 pub fn render_traces(app: &Home, frame: &mut Frame, area: Rect) {
     let height = area.height;
 
-    let effective_height = height - NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE as u16;
+    let effective_height = height - app.traces_unusable_vertical_space() as u16;
 
     let active_block = app.active_block;
 
@@ -490,75 +675,181 @@ pub fn render_traces(app: &Home, frame: &mut Frame, area: Rect) {
 
     let title = format!("Traces - [{}] - [{}]", filter_message, sort_message);
 
-    let converted_rows: Vec<(Vec<String>, bool)> = items_as_vector
+    let now = SystemTime::now();
+
+    // pure HTTP sessions don't need the kind column
+    let show_kind = items_as_vector
         .iter()
-        .skip(app.main.offset)
-        .take(effective_height.into())
-        .map(|request| {
-            let uri = truncate(request.http.as_ref().unwrap().uri.clone().as_str(), 60);
+        .any(|trace| trace.kind() != TraceKind::Http);
 
-            let method = request.http.as_ref().unwrap().method.clone().to_string();
+    let columns: Vec<TraceColumn> = if app.trace_columns.is_empty() {
+        DEFAULT_TRACE_COLUMNS.to_vec()
+    } else {
+        app.trace_columns.clone()
+    }
+    .into_iter()
+    .filter(|column| match column {
+        TraceColumn::Kind => show_kind,
+        TraceColumn::Time => app.time_column != TimeColumn::Hidden,
+        _ => true,
+    })
+    .collect();
+
+    let trace_cells = |request: &Trace| -> Vec<String> {
+        let http = request.http.as_ref().unwrap();
+        let uri = mask_query_params(&http.uri, &app.mask_query_params);
+        let in_flight = http.state == State::Sent;
+
+        columns
+            .iter()
+            .map(|column| match column {
+                TraceColumn::Kind => request.kind().to_string(),
+                TraceColumn::Method => http.method.to_string(),
+                TraceColumn::Status => match http.status {
+                    Some(v) => v.as_u16().to_string(),
+                    None if in_flight => get_spinner_glyph(app.tick).to_string(),
+                    None => "...".to_string(),
+                },
+                TraceColumn::Url => uri.clone(),
+                TraceColumn::Duration => match http.duration {
+                    Some(v) => {
+                        format!("{:.3} s", ((v as f32) / 1000.0))
+                    }
+                    None => "...".to_string(),
+                },
+                TraceColumn::Host => http
+                    .uri
+                    .parse::<Uri>()
+                    .ok()
+                    .and_then(|uri| uri.host().map(|host| host.to_string()))
+                    .unwrap_or_default(),
+                TraceColumn::Time => match app.time_column {
+                    TimeColumn::Relative => format_relative_time(get_trace_age(request, now)),
+                    TimeColumn::Absolute => format_timestamp(request.timestamp, &app.time_format),
+                    TimeColumn::Hidden => "".to_string(),
+                },
+                // as received, truncated and binary bodies included
+                TraceColumn::Size => match &http.response_body {
+                    Some(_) => format_size(http.response_body_size),
+                    None => "...".to_string(),
+                },
+            })
+            .collect()
+    };
 
-            let status = request.http.as_ref().unwrap().status;
-            let duration = request.http.as_ref().unwrap().duration;
+    // every trace counts, so the columns keep their widths while scrolling
+    let all_cells: Vec<Vec<String>> = items_as_vector.iter().copied().map(trace_cells).collect();
 
-            let status = match status {
-                Some(v) => v.as_u16().to_string(),
-                None => "...".to_string(),
+    let mut converted_rows: Vec<TraceRow> = items_as_vector
+        .iter()
+        .zip(all_cells.iter())
+        .skip(app.main.offset)
+        .take(effective_height.into())
+        .map(|(request, cells)| {
+            let request_suffix = match &request.http.as_ref().unwrap().graphql_operation {
+                Some(operation) => format!(" [{}]", operation),
+                None => "".to_string(),
             };
+            let http = request.http.as_ref().unwrap();
 
-            let duration = match duration {
-                Some(v) => {
-                    format!("{:.3} s", ((v as f32) / 1000.0))
-                }
-                None => "...".to_string(),
+            let mut request_prefix = if app
+                .annotations
+                .is_annotated(&annotations::trace_key(request))
+            {
+                "✎ ".to_string()
+            } else {
+                "".to_string()
             };
 
-            let id = request.id.clone();
+            if http.state == State::Blocked {
+                request_prefix.push_str("🔒 ");
+            }
 
             let selected = match selected_item {
                 Some(item) => item == request,
                 None => false,
             };
 
-            (vec![method, status, uri, duration, id], selected)
+            TraceRow {
+                cells: cells.clone(),
+                request_prefix,
+                request_suffix,
+                selected,
+                state: http.state.clone(),
+                new: is_new_trace(app, request),
+                divider: false,
+            }
         })
         .collect();
 
+    if let Some(index) = converted_rows
+        .windows(2)
+        .position(|pair| pair[0].new != pair[1].new)
+    {
+        converted_rows[index].divider = true;
+    }
+
+    let header: Vec<&str> = columns.iter().map(|column| column.title()).collect();
+
+    let request_column = columns
+        .iter()
+        .position(|column| *column == TraceColumn::Url);
+
+    let widths = get_traces_column_widths(
+        &header,
+        &all_cells,
+        request_column,
+        area.width.saturating_sub(2),
+    );
+
     let styled_rows: Vec<Row> = converted_rows
         .iter()
-        .map(|(row, selected)| {
-            let str_vec: Vec<&str> = row
-                .iter()
-                .map(|x| x.as_str())
-                .collect::<Vec<&str>>()
-                .clone();
-
-            Row::new(str_vec).style(match (*selected, active_block) {
-                (true, ActiveBlock::Traces) => get_row_style(RowStyle::Selected, &app.colors),
-                (false, ActiveBlock::Traces) => get_row_style(RowStyle::Active, &app.colors),
-                (true, _) => get_row_style(RowStyle::Inactive, &app.colors),
-                (false, _) => get_row_style(RowStyle::Default, &app.colors),
-            })
+        .map(|row| {
+            let mut cells = row.cells.clone();
+
+            if let Some(request_column) = request_column {
+                // the uri gives way to the annotation marker and the operation name
+                let uri_width = usize::from(widths[request_column])
+                    .saturating_sub(row.request_prefix.width() + row.request_suffix.width())
+                    .max(1);
+
+                cells[request_column] = format!(
+                    "{}{}{}",
+                    row.request_prefix,
+                    truncate(&cells[request_column], uri_width - 1),
+                    row.request_suffix
+                );
+            }
+
+            let mut style =
+                get_trace_row_style(row.selected, &row.state, active_block, &app.colors);
+
+            if row.new {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+
+            if row.divider {
+                style = style.add_modifier(Modifier::UNDERLINED);
+            }
+
+            Row::new(cells).style(style)
         })
         .collect();
 
     let requests = Table::new(
         styled_rows,
-        &[
-            Constraint::Percentage(10),
-            Constraint::Percentage(10),
-            Constraint::Percentage(60),
-            Constraint::Length(20),
-        ],
+        widths
+            .into_iter()
+            .map(Constraint::Length)
+            .collect::<Vec<_>>(),
     )
     // You can set the style of the entire Table.
     .style(Style::default().fg(app.colors.surface.selected))
     // It has an optional header, which is simply a Row always visible at the top.
     .header(
-        Row::new(vec!["Method", "Status", "Request", "Duration"])
+        Row::new(header)
             .style(Style::default().fg(app.colors.text.accent_1))
-            .bottom_margin(1),
+            .bottom_margin(if app.compact { 0 } else { 1 }),
     )
     .block(
         Block::default()
@@ -573,14 +864,18 @@ pub fn render_traces(app: &Home, frame: &mut Frame, area: Rect) {
                     .position(Position::Bottom)
                     .alignment(Alignment::Right),
             )
-            .border_type(BorderType::Plain),
+            .border_type(app.border_type),
     );
 
     let vertical_scroll = Scrollbar::new(ScrollbarOrientation::VerticalRight);
 
     frame.render_widget(requests, area);
 
-    let usable_height = area.height - NETWORK_REQUESTS_UNUSABLE_VERTICAL_SPACE as u16;
+    if number_of_lines == 0 {
+        render_traces_empty_state(app, frame, area);
+    }
+
+    let usable_height = area.height - app.traces_unusable_vertical_space() as u16;
 
     if number_of_lines > usable_height.into() {
         frame.render_stateful_widget(
@@ -589,15 +884,87 @@ pub fn render_traces(app: &Home, frame: &mut Frame, area: Rect) {
                 horizontal: 0,
                 vertical: 2,
             }),
-            &mut app.main.scroll_state.clone(),
+            &mut app.main.scroll_state.content_length(number_of_lines),
         );
     }
 }
 
+pub fn get_traces_empty_message(app: &Home) -> &'static str {
+    if app.items.is_empty() {
+        "Waiting for traces — start your collector or pass --uds <path>"
+    } else {
+        "No traces match current filters"
+    }
+}
+
+// Centered in the table body, below the header.
+fn render_traces_empty_state(app: &Home, frame: &mut Frame, area: Rect) {
+    let body = area.inner(&Margin {
+        horizontal: 1,
+        vertical: 1,
+    });
+    let header_height = app.traces_unusable_vertical_space() as u16 - 2;
+    let body_height = body.height.saturating_sub(header_height);
+
+    if body_height == 0 {
+        return;
+    }
+
+    let message_area = Rect {
+        y: body.y + header_height + body_height / 2,
+        height: 1,
+        ..body
+    };
+
+    frame.render_widget(
+        Paragraph::new(get_traces_empty_message(app))
+            .style(get_text_style(false, &app.colors))
+            .alignment(Alignment::Center),
+        message_area,
+    );
+}
+
 pub fn render_search(app: &Home, frame: &mut Frame) {
     if app.active_block == ActiveBlock::SearchQuery {
         let area = overlay_area(frame.size());
-        let widget = Paragraph::new(format!("/{}", &app.search_query))
+        let query = match app.search_origin {
+            ActiveBlock::Traces => &app.search_query,
+            ActiveBlock::Details => &app.headers_search,
+            _ => &app.body_search,
+        };
+        let widget = Paragraph::new(format!("/{}", query))
+            .style(
+                Style::default()
+                    .fg(app.colors.text.selected)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .alignment(Alignment::Left);
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(widget, area);
+    }
+}
+
+pub fn render_annotation_prompt(app: &Home, frame: &mut Frame) {
+    if app.active_block == ActiveBlock::Annotate {
+        let area = overlay_area(frame.size());
+        let widget = Paragraph::new(format!("✎ {}_", &app.annotation_input))
+            .style(
+                Style::default()
+                    .fg(app.colors.text.selected)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .alignment(Alignment::Left);
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(widget, area);
+    }
+}
+
+pub fn render_go_to_prompt(app: &Home, frame: &mut Frame) {
+    if app.active_block == ActiveBlock::GoTo {
+        let area = overlay_area(frame.size());
+        let widget = Paragraph::new(format!(":{}_", &app.goto_input))
             .style(
                 Style::default()
                     .fg(app.colors.text.selected)
@@ -610,6 +977,36 @@ pub fn render_search(app: &Home, frame: &mut Frame) {
     }
 }
 
+pub fn render_context_menu(app: &mut Home, frame: &mut Frame) {
+    if app.active_block != ActiveBlock::ContextMenu {
+        return;
+    }
+
+    let size = frame.size();
+    let width = CONTEXT_MENU_WIDTH.min(size.width);
+    let height = (app.context_menu.items.len() as u16 + 2).min(size.height);
+    let (column, row) = app.context_menu_anchor;
+
+    // kept on screen when opened close to the right or bottom edge
+    let area = Rect::new(
+        column.min(size.width - width),
+        row.min(size.height - height),
+        width,
+        height,
+    );
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(get_border_style(true, &app.colors))
+        .border_type(app.border_type);
+    let inner_area = block.inner(area);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    render_actionable_list(&mut app.context_menu, frame, inner_area, &app.colors, true);
+}
+
 pub fn render_footer(app: &Home, frame: &mut Frame, area: Rect) {
     let general_status = match app.status_message.clone() {
         Some(text) => text,
@@ -629,7 +1026,7 @@ pub fn render_footer(app: &Home, frame: &mut Frame, area: Rect) {
                 .border_style(Style::default().fg(app.colors.surface.unselected))
                 .title("Status Bar")
                 .padding(Padding::new(1, 0, 0, 0))
-                .border_type(BorderType::Plain),
+                .border_type(app.border_type),
         );
 
     let wss_status_message = match app.wss_state {
@@ -642,7 +1039,59 @@ pub fn render_footer(app: &Home, frame: &mut Frame, area: Rect) {
         _ => "🟠 Waiting for connection".to_string(),
     };
 
-    let status_bar = Paragraph::new(format!("{} {}", general_status, wss_status_message))
+    let mut status_spans = vec![];
+
+    if let Some(alert) = &app.alert_message {
+        status_spans.push(Span::styled(
+            format!("{} ", alert),
+            Style::default()
+                .fg(app.colors.surface.error)
+                .add_modifier(Modifier::SLOW_BLINK),
+        ));
+    }
+
+    match get_match_count(app) {
+        Some(0) => status_spans.push(Span::styled(
+            "No matches ",
+            Style::default().fg(app.colors.surface.error),
+        )),
+        Some(1) => status_spans.push(Span::raw("1 match ")),
+        Some(count) => status_spans.push(Span::raw(format!("{} matches ", count))),
+        None => {}
+    }
+
+    match get_hidden_count(app) {
+        0 => {}
+        count => status_spans.push(Span::raw(format!("{} hidden ", count))),
+    }
+
+    if let Some(id) = &app.correlation_id {
+        status_spans.push(Span::raw(format!("correlated: {} ", truncate(id, 16))));
+    }
+
+    if app.frozen {
+        status_spans.push(Span::styled(
+            "FROZEN ",
+            Style::default()
+                .fg(app.colors.surface.warning)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    if let Some(since) = app.connected_since {
+        status_spans.push(Span::raw(format!(
+            "⏱ {} · {:.1}/s ",
+            format_uptime(since.elapsed()),
+            trace_rate(&app.trace_arrivals, Instant::now())
+        )));
+    }
+
+    status_spans.push(Span::raw(format!(
+        "{} {}",
+        general_status, wss_status_message
+    )));
+
+    let status_bar = Paragraph::new(Line::from(status_spans))
         .style(
             Style::default()
                 .fg(app.colors.text.selected)
@@ -655,7 +1104,7 @@ pub fn render_footer(app: &Home, frame: &mut Frame, area: Rect) {
                 .border_style(Style::default().fg(app.colors.surface.unselected))
                 .title("Status Bar")
                 .padding(Padding::new(0, 1, 0, 0))
-                .border_type(BorderType::Plain),
+                .border_type(app.border_type),
         );
 
     frame.render_widget(status_bar, area);
@@ -663,91 +1112,220 @@ pub fn render_footer(app: &Home, frame: &mut Frame, area: Rect) {
     frame.render_widget(help_text, area);
 }
 
-pub fn render_help(app: &Home, frame: &mut Frame, area: Rect) {
-    let mut entry_list: Vec<(KeyEvent, Action)> = vec![];
-    for (k, v) in app.key_map.iter() {
-        entry_list.push((*k, v.clone()));
+pub fn get_action_description(action: &Action) -> &'static str {
+    match action {
+        Action::CopyToClipBoard => "Copy selection to OS clipboard",
+        Action::FocusOnTraces => "Focus on traces section OR exit current window",
+        Action::NavigateUp(_) => "Move up and select an entry one above",
+        Action::NavigateDown(_) => "Move down and select entry below",
+        Action::NavigateLeft(_) => "Move cursor left",
+        Action::NavigateRight(_) => "Move cursor right",
+        Action::GoToRight => "Abs cursor right",
+        Action::GoToLeft => "Abs cursor left",
+        Action::NextSection => "Focus on next section",
+        Action::GoToEnd => "Move to bottom of section",
+        Action::GoToStart => "Move to top of section",
+        Action::PreviousSection => "Focus on previous section",
+        Action::Quit => "Quit",
+        Action::NewSearch => "Search traces, or the focused body or headers pane",
+        Action::ExitSearch => "Cancel Search",
+        Action::SearchHistoryPrevious => "Previous Search Query",
+        Action::SearchHistoryNext => "Next Search Query",
+        Action::NextMatch => "Go to next matching trace or body line",
+        Action::PreviousMatch => "Go to previous matching trace or body line",
+        Action::NextError => "Go to next failed trace",
+        Action::PreviousError => "Go to previous failed trace",
+        Action::ToggleShowHidden => "Toggle hidden traces",
+        Action::ToggleFreeze => "Freeze/unfreeze the trace list",
+        Action::ToggleCorrelationFilter => "Show only traces correlated with the selected one",
+        Action::ToggleFilterInvert => "Toggle exclude mode for a filter",
+        Action::ToggleTimeColumn => "Toggle relative/absolute trace time",
+        Action::ToggleCompact => "Toggle compact traces table",
+        Action::CycleDetailsSort => "Cycle the sort of headers and query params",
+        Action::ToggleCurlQueryFlags => "Toggle query params as separate cURL flags",
+        Action::ToggleRedaction => "Toggle redaction of copied secrets",
+        Action::AnnotateTrace => "Annotate trace with a note and #tags",
+        Action::OpenGoTo => "Go to trace by id, or save it with :save <path>",
+        Action::OpenLargestResponses => "List the largest responses",
+        Action::UpdateSearchQuery(_) => "Update Search Query",
+        Action::DeleteSearchQuery => "Delete Last Search Char",
+        Action::Help => "Open Help Window",
+        Action::ToggleDebug => "Toggle Debug Window",
+        Action::CycleLogLevel => "Cycle debug log level",
+        Action::ToggleLogTimestamps => "Toggle debug log timestamps",
+        Action::DeleteItem => "Delete Trace",
+        Action::ShowTraceDetails => "Focus On Trace",
+        Action::NextDetailsTab => "Focus On Next Tab",
+        Action::PreviousDetailsTab => "Go To Previous Tab",
+        Action::StartWebSocketServer => "Start the Collector Server",
+        Action::StopWebSocketServer => "Stop the Collector Server",
+        Action::Select => "Select at cursor position",
+        Action::ExpandAll => "Expand all JSON objects",
+        Action::CollapseAll => "Collapse all JSON objects",
+        Action::CollapseNode => "Collapse JSON object at cursor",
+        Action::ToggleBodyZoom => "Toggle full screen body view",
+        Action::ToggleRawBody => "Toggle between the formatted and raw body",
+        Action::ToggleFlattenBody => "Toggle flattened body view with dotted keys",
+        Action::ToggleDetails => "Hide or show the details panes",
+        Action::ToggleWrap => "Toggle body line wrapping",
+        Action::ToggleTypeHints => "Toggle number formatting and type hints",
+        Action::JumpToMatchingBracket => "Jump to matching bracket",
+        Action::GoToParent => "Go to parent node",
+        Action::ToggleLineNumbers => "Toggle body line numbers",
+        Action::IncreaseIndent => "Increase body indent spacing",
+        Action::DecreaseIndent => "Decrease body indent spacing",
+        Action::OpenSort => "Open sort screen",
+        Action::OpenFilter => "Open filter screen",
+        Action::ExportKeyMap => "Export key map to a file",
+        Action::ExportMockStub => "Export trace as a mock server stub",
+        Action::CopyAsMarkdown => {
+            "Copy trace as a Markdown summary, or every header of a headers pane"
+        }
+        Action::CopyUrl => "Copy trace URL",
+        Action::OpenExportMenu => "Copy trace as cURL, HTTPie, fetch, .http or Markdown",
+        Action::PageUp => "Move up one page",
+        Action::PageDown => "Move down one page",
+        Action::MoveDetailsTabLeft => "Move current tab left",
+        Action::MoveDetailsTabRight => "Move current tab right",
+        Action::PopOutAllDetailsTabs => "Pop out all detail tabs",
+        Action::CloseAllDetailsPanes => "Close all detail panes",
+        _ => "",
     }
+}
 
-    let key_mappings: Vec<(String, String)> = entry_list
-        .iter()
-        .map(|(key_event, action)| {
-            let description_str = match action {
-                Action::CopyToClipBoard => "Copy selection to OS clipboard",
-                Action::FocusOnTraces => "Focus on traces section OR exit current window",
-                Action::NavigateUp(_) => "Move up and select an entry one above",
-                Action::NavigateDown(_) => "Move down and select entry below",
-                Action::NavigateLeft(_) => "Move cursor left",
-                Action::NavigateRight(_) => "Move cursor right",
-                Action::GoToRight => "Abs cursor right",
-                Action::GoToLeft => "Abs cursor left",
-                Action::NextSection => "Focus on next section",
-                Action::GoToEnd => "Move to bottom of section",
-                Action::GoToStart => "Move to top of section",
-                Action::PreviousSection => "Focus on previous section",
-                Action::Quit => "Quit",
-                Action::NewSearch => "Search",
-                Action::ExitSearch => "Cancel Search",
-                Action::UpdateSearchQuery(_) => "Update Search Query",
-                Action::DeleteSearchQuery => "Delete Last Search Char",
-                Action::Help => "Open Help Window",
-                Action::ToggleDebug => "Toggle Debug Window",
-                Action::DeleteItem => "Delete Trace",
-                Action::ShowTraceDetails => "Focus On Trace",
-                Action::NextDetailsTab => "Focus On Next Tab",
-                Action::PreviousDetailsTab => "Go To Previous Tab",
-                Action::StartWebSocketServer => "Start the Collector Server",
-                Action::StopWebSocketServer => "Stop the Collector Server",
-                Action::Select => "Select at cursor position",
-                Action::ExpandAll => "Expand all JSON objects",
-                Action::CollapseAll => "Collapse all JSON objects",
-                Action::OpenSort => "Open sort screen",
-                Action::OpenFilter => "Open filter screen",
-                _ => "",
-            };
-            let description = format!("{}:", description_str);
-
-            let mut b = [0; 2];
-            let key_code_str = match key_event.code {
-                KeyCode::PageUp => "Page Up",
-                KeyCode::PageDown => "Page Down",
-                KeyCode::Down => "Down arrow",
-                KeyCode::Up => "Up arrow",
-                KeyCode::Esc => "Esc",
-                KeyCode::Tab => "Tab",
-                KeyCode::BackTab => "Tab + Shift",
-                KeyCode::Char('/') => "/{pattern}[/]<CR>",
-                KeyCode::Char(c) => c.encode_utf8(&mut b),
-                _ => "Default",
-            };
-            let key_code = format!(r#""{}""#, key_code_str);
+pub fn get_key_code_label(key_event: &KeyEvent) -> String {
+    let mut b = [0; 2];
+    let key_code_str = match key_event.code {
+        KeyCode::PageUp => "Page Up",
+        KeyCode::PageDown => "Page Down",
+        KeyCode::Down => "Down arrow",
+        KeyCode::Up => "Up arrow",
+        KeyCode::Esc => "Esc",
+        KeyCode::Enter => "Enter",
+        KeyCode::Tab => "Tab",
+        KeyCode::BackTab => "Tab + Shift",
+        KeyCode::Char('/') => "/{pattern}[/]<CR>",
+        KeyCode::Char(c) => c.encode_utf8(&mut b),
+        _ => "Default",
+    };
 
-            (description, key_code)
-        })
-        .collect();
+    key_code_str.to_string()
+}
 
+// Groups the key map by action description, the way the help screen lists it.
+pub fn get_key_map_entries(key_map: &HashMap<KeyEvent, Action>) -> Vec<(String, Vec<String>)> {
     let mut grouped: Vec<(String, Vec<String>)> =
-        key_mappings.iter().fold(vec![], |mut acc, (rk, rv)| {
+        key_map.iter().fold(vec![], |mut acc, (key_event, action)| {
+            let description = get_action_description(action).to_string();
+            let key_code = get_key_code_label(key_event);
+
             for (lk, lv) in acc.iter_mut() {
-                if lk.eq(&rk) {
-                    lv.push(rv.to_string());
+                if *lk == description {
+                    lv.push(key_code);
+                    lv.sort();
                     return acc;
                 }
             }
 
-            acc.push((rk.to_string(), vec![rv.to_string()]));
+            acc.push((description, vec![key_code]));
 
             acc
         });
 
     grouped.sort();
 
-    let debug_lines = grouped
-        .iter()
+    grouped
+}
+
+pub fn format_key_map(key_map: &HashMap<KeyEvent, Action>) -> String {
+    get_key_map_entries(key_map)
+        .iter()
+        .map(|(description, key_codes)| {
+            let key_codes = key_codes
+                .iter()
+                .map(|key_code| format!("{:?}", key_code))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            format!("{:?} = [{}]\n", description, key_codes)
+        })
+        .collect()
+}
+
+pub fn get_help_rows(app: &Home) -> Vec<(String, Vec<String>)> {
+    let filter = app.help_filter.to_lowercase();
+
+    get_key_map_entries(&app.key_map)
+        .into_iter()
+        .filter(|(description, key_codes)| {
+            description.to_lowercase().contains(&filter)
+                || key_codes
+                    .iter()
+                    .any(|key_code| key_code.to_lowercase().contains(&filter))
+        })
+        .collect()
+}
+
+// Described actions that no key is bound to. Text input actions are left out
+// since they are driven by the input itself rather than a key binding.
+pub fn get_unmapped_actions(app: &Home) -> Vec<String> {
+    let filter = app.help_filter.to_lowercase();
+
+    let mut descriptions = Action::iter()
+        .filter(|action| {
+            !matches!(
+                action,
+                Action::UpdateSearchQuery(_)
+                    | Action::DeleteSearchQuery
+                    | Action::ExitSearch
+                    | Action::SearchHistoryPrevious
+                    | Action::SearchHistoryNext
+            )
+        })
+        .filter(|action| {
+            !app.key_map
+                .values()
+                .any(|mapped| mem::discriminant(mapped) == mem::discriminant(action))
+        })
+        .map(|action| get_action_description(&action))
+        .filter(|description| !description.is_empty())
+        .filter(|description| description.to_lowercase().contains(&filter))
+        .map(String::from)
+        .collect::<Vec<_>>();
+
+    descriptions.sort();
+    descriptions.dedup();
+
+    descriptions
+}
+
+pub fn render_help(app: &Home, frame: &mut Frame, area: Rect) {
+    let grouped = get_help_rows(app)
+        .into_iter()
+        .map(|(description, key_codes)| {
+            (
+                format!("{}:", description),
+                key_codes
+                    .iter()
+                    .map(|key_code| format!(r#""{}""#, key_code))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )
+        })
+        .chain(
+            get_unmapped_actions(app)
+                .into_iter()
+                .map(|description| (format!("{}:", description), "unmapped".to_string())),
+        )
+        .skip(app.help_offset)
+        .collect::<Vec<_>>();
+
+    let debug_lines = grouped
+        .iter()
         .map(|(description, key_code)| {
             let column_a =
                 Cell::from(Line::from(vec![Span::raw(description)]).alignment(Alignment::Right));
-            let column_b = Cell::from(key_code.join(", "));
+            let column_b = Cell::from(key_code.as_str());
 
             Row::new(vec![column_a, column_b]).style(get_row_style(RowStyle::Default, &app.colors))
         })
@@ -767,18 +1345,88 @@ pub fn render_help(app: &Home, frame: &mut Frame, area: Rect) {
         Block::default()
             .borders(Borders::ALL)
             .border_style(get_border_style(true, &app.colors))
-            .title("Key Mappings")
-            .border_type(BorderType::Plain),
+            .title(format!("Key Mappings - filter: {}_", app.help_filter))
+            .border_type(app.border_type),
     )
     .column_spacing(10);
 
     frame.render_widget(list, area);
 }
 
-pub fn render_debug(app: &Home, frame: &mut Frame, area: Rect) {
-    let debug_lines = app
-        .logs
+pub fn render_largest_responses(app: &Home, frame: &mut Frame, area: Rect) {
+    let largest = get_largest_responses(app, LARGEST_RESPONSES_COUNT);
+    let selected = app
+        .largest_responses_index
+        .min(largest.len().saturating_sub(1));
+
+    let rows = largest
+        .into_iter()
+        .enumerate()
+        .map(|(position, (_, trace, size))| {
+            let http = trace.http.as_ref().unwrap();
+
+            let row_style = if position == selected {
+                RowStyle::Selected
+            } else {
+                RowStyle::Default
+            };
+
+            Row::new(vec![
+                Cell::from(format_size(size)),
+                Cell::from(http.method.to_string()),
+                Cell::from(mask_query_params(&http.uri, &app.mask_query_params)),
+            ])
+            .style(get_row_style(row_style, &app.colors))
+        })
+        .collect::<Vec<_>>();
+
+    let table = Table::new(
+        rows,
+        &[
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Min(0),
+        ],
+    )
+    .style(get_text_style(true, &app.colors))
+    .header(
+        Row::new(vec!["Size", "Method", "URL"])
+            .style(Style::default().fg(app.colors.text.accent_1))
+            .bottom_margin(1),
+    )
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(get_border_style(true, &app.colors))
+            .title(format!(
+                " Largest Responses - top {} ",
+                LARGEST_RESPONSES_COUNT
+            ))
+            .border_type(app.border_type),
+    )
+    .column_spacing(2);
+
+    frame.render_widget(table, area);
+}
+
+// Log lines at or below the selected debug level.
+pub fn get_debug_lines(app: &Home) -> Vec<String> {
+    app.logs
         .iter()
+        .filter(|(_, line)| LogLevel::from_line(line) <= app.debug_level)
+        .map(|(time, line)| {
+            if app.hide_log_timestamps {
+                line.to_string()
+            } else {
+                format!("{} {}", time.format(LOG_TIME_FORMAT), line)
+            }
+        })
+        .collect()
+}
+
+pub fn render_debug(app: &Home, frame: &mut Frame, area: Rect) {
+    let debug_lines = get_debug_lines(app)
+        .into_iter()
         .map(|item| ListItem::new(Line::from(Span::raw(item))))
         .collect::<Vec<_>>();
 
@@ -789,8 +1437,8 @@ pub fn render_debug(app: &Home, frame: &mut Frame, area: Rect) {
             Block::default()
                 .borders(Borders::ALL)
                 .border_style(get_border_style(true, &app.colors))
-                .title("Debug logs")
-                .border_type(BorderType::Plain),
+                .title(format!("Debug logs (level: {})", app.debug_level))
+                .border_type(app.border_type),
         );
 
     frame.render_widget(list, area);
@@ -800,8 +1448,8 @@ pub fn get_services_from_traces(app: &Home) -> Vec<String> {
     let services = app
         .items
         .iter()
-        .filter(|trace| trace.service_name.is_some())
-        .map(|trace| trace.service_name.as_ref().unwrap().clone())
+        .map(|trace| trace.effective_source())
+        .filter(|source| !source.is_empty())
         .collect::<HashSet<_>>();
 
     let mut services_as_vec = services.iter().cloned().collect::<Vec<String>>();
@@ -811,22 +1459,126 @@ pub fn get_services_from_traces(app: &Home) -> Vec<String> {
     services_as_vec
 }
 
+pub fn get_content_types_from_traces(app: &Home) -> Vec<String> {
+    let content_types = app
+        .items
+        .iter()
+        .filter_map(get_content_type)
+        .collect::<HashSet<_>>();
+
+    let mut content_types_as_vec = content_types.into_iter().collect::<Vec<String>>();
+
+    content_types_as_vec.sort();
+
+    content_types_as_vec
+}
+
+pub fn get_filter_preset_names(app: &Home) -> Vec<String> {
+    let mut names = app.filter_presets.keys().cloned().collect::<Vec<_>>();
+
+    names.sort();
+
+    names
+}
+
+pub fn render_filters_presets(app: &Home, frame: &mut Frame, area: Rect) {
+    let is_active = app.active_block == ActiveBlock::Filter(FilterScreen::Presets);
+    let is_saving = app.active_block == ActiveBlock::Filter(FilterScreen::SavePreset);
+
+    let layout = Layout::default()
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .direction(Direction::Vertical)
+        .split(area);
+
+    let labels = [
+        vec!["save current filters".to_string()],
+        get_filter_preset_names(app),
+    ]
+    .concat();
+
+    let rows = labels
+        .into_iter()
+        .enumerate()
+        .map(|(index, label)| {
+            let column = Cell::from(Line::from(vec![Span::raw(label)]).alignment(Alignment::Left));
+
+            let is_selected = index == app.filter_value_index;
+
+            let maybe_row_style = if is_active && is_selected {
+                Some(RowStyle::Selected)
+            } else if is_selected {
+                Some(RowStyle::Inactive)
+            } else {
+                None
+            };
+
+            if let Some(row_style) = maybe_row_style {
+                Row::new(vec![column]).style(get_row_style(row_style, &app.colors))
+            } else {
+                Row::new(vec![column])
+            }
+        })
+        .collect::<Vec<_>>();
+
+    render_table(rows, frame, layout[0], &app.colors, is_active);
+
+    if is_saving {
+        let input = Paragraph::new(Line::from(vec![
+            Span::raw("preset name: "),
+            Span::styled(
+                format!("{}_", app.preset_name),
+                Style::default().fg(app.colors.text.accent_2),
+            ),
+        ]))
+        .style(get_row_style(RowStyle::Active, &app.colors));
+
+        frame.render_widget(input, layout[1]);
+    }
+}
+
 pub fn render_filters_source(app: &Home, frame: &mut Frame, area: Rect) {
+    render_filters_applied_set(
+        app,
+        frame,
+        area,
+        get_services_from_traces(app),
+        &app.selected_filters.source,
+        app.active_block == ActiveBlock::Filter(FilterScreen::Source),
+    );
+}
+
+pub fn render_filters_content_type(app: &Home, frame: &mut Frame, area: Rect) {
+    render_filters_applied_set(
+        app,
+        frame,
+        area,
+        get_content_types_from_traces(app),
+        &app.selected_filters.content_type,
+        app.active_block == ActiveBlock::Filter(FilterScreen::ContentType),
+    );
+}
+
+fn render_filters_applied_set(
+    app: &Home,
+    frame: &mut Frame,
+    area: Rect,
+    values: Vec<String>,
+    filter: &SourceFilter,
+    is_active: bool,
+) {
     let mut services = vec!["All".to_string()];
 
-    services.extend(get_services_from_traces(app));
+    services.extend(values);
 
     let current_service = services.iter().nth(app.filter_value_index).cloned();
 
-    let is_active = app.active_block == ActiveBlock::Filter(FilterScreen::Source);
-
     let rows = services
         .iter()
         .map(|item| {
             let column_b =
                 Cell::from(Line::from(vec![Span::raw(item.clone())]).alignment(Alignment::Left));
 
-            let column_a = match &app.selected_filters.source {
+            let column_a = match filter {
                 SourceFilter::All => Cell::from(
                     Line::from(vec![Span::raw("[x]".to_string())]).alignment(Alignment::Left),
                 ),
@@ -966,6 +1718,63 @@ pub fn render_filters_method(app: &Home, frame: &mut Frame, area: Rect) {
     render_table(rows, frame, area, &app.colors, is_active);
 }
 
+pub fn render_filters_key(app: &Home, frame: &mut Frame, area: Rect) {
+    let is_active = app.active_block == ActiveBlock::Filter(FilterScreen::Key);
+
+    let input = Paragraph::new(Line::from(vec![
+        Span::raw("query param or header: "),
+        Span::styled(
+            format!(
+                "{}{}",
+                app.selected_filters.key,
+                if is_active { "_" } else { "" }
+            ),
+            Style::default().fg(app.colors.text.accent_2),
+        ),
+    ]))
+    .style(if is_active {
+        get_row_style(RowStyle::Active, &app.colors)
+    } else {
+        get_row_style(RowStyle::Default, &app.colors)
+    });
+
+    frame.render_widget(input, area);
+}
+
+pub fn render_filters_status_range(app: &Home, frame: &mut Frame, area: Rect) {
+    let is_active = app.active_block == ActiveBlock::Filter(FilterScreen::StatusRange);
+
+    let validation = match parse_status_range(&app.selected_filters.status_range) {
+        Ok(_) => Line::from(Span::raw("e.g. 404 or 400..=499,!404")),
+        Err(e) => Line::from(Span::styled(
+            e,
+            Style::default().fg(app.colors.surface.error),
+        )),
+    };
+
+    let input = Paragraph::new(vec![
+        Line::from(vec![
+            Span::raw("status codes: "),
+            Span::styled(
+                format!(
+                    "{}{}",
+                    app.selected_filters.status_range,
+                    if is_active { "_" } else { "" }
+                ),
+                Style::default().fg(app.colors.text.accent_2),
+            ),
+        ]),
+        validation,
+    ])
+    .style(if is_active {
+        get_row_style(RowStyle::Active, &app.colors)
+    } else {
+        get_row_style(RowStyle::Default, &app.colors)
+    });
+
+    frame.render_widget(input, area);
+}
+
 pub fn render_filters(app: &mut Home, frame: &mut Frame, area: Rect) {
     let filter_screen = if let ActiveBlock::Filter(screen) = app.active_block {
         screen
@@ -977,7 +1786,7 @@ pub fn render_filters(app: &mut Home, frame: &mut Frame, area: Rect) {
         .borders(Borders::ALL)
         .border_style(get_border_style(true, &app.colors))
         .title(" FILTER ")
-        .border_type(BorderType::Plain);
+        .border_type(app.border_type);
 
     let inner_area = parent_block.inner(area);
 
@@ -998,7 +1807,7 @@ pub fn render_filters(app: &mut Home, frame: &mut Frame, area: Rect) {
         .direction(Direction::Horizontal)
         .split(vertical_layout[0]);
 
-    let filter_items = vec!["method", "source", "status"];
+    let filter_items = FILTER_SOURCES.to_vec();
 
     let current_filter = filter_items.get(app.filter_source_index);
     let is_active_block = filter_screen == FilterScreen::Main;
@@ -1008,9 +1817,15 @@ pub fn render_filters(app: &mut Home, frame: &mut Frame, area: Rect) {
             let is_selected_row = current_filter == Some(item);
             let is_active = is_active_block && is_selected_row;
             let is_inactive = !is_active && is_selected_row;
-            let column = Cell::from(
-                Line::from(vec![Span::raw(item.to_string())]).alignment(Alignment::Left),
-            );
+            let label = match (*item, get_filter_screen(item)) {
+                ("combine", _) => format!("combine: {}", app.selected_filters.combination),
+                ("window", _) => format!("window: {}", app.selected_filters.time_window),
+                (_, Some(screen)) if app.selected_filters.inverted.contains(&screen) => {
+                    format!("{} (exclude)", item)
+                }
+                _ => item.to_string(),
+            };
+            let column = Cell::from(Line::from(vec![Span::raw(label)]).alignment(Alignment::Left));
 
             let maybe_row_style = if is_active {
                 Some(RowStyle::Selected)
@@ -1071,9 +1886,50 @@ pub fn render_filters(app: &mut Home, frame: &mut Frame, area: Rect) {
         .map(|v| format!("status-{}", v.name.to_lowercase()))
         .collect();
 
-    let filters = [method_filters, source_filters, status_filters]
-        .concat()
-        .join(", ");
+    let key_filters: Vec<String> = if app.selected_filters.key.is_empty() {
+        vec![]
+    } else {
+        vec![format!("key-{}", app.selected_filters.key.to_lowercase())]
+    };
+
+    let status_range_filters: Vec<String> = if app.selected_filters.status_range.is_empty() {
+        vec![]
+    } else {
+        vec![format!("range-{}", app.selected_filters.status_range)]
+    };
+
+    let content_type_filters: Vec<String> =
+        if let SourceFilter::Applied(hashset) = &app.selected_filters.content_type {
+            hashset
+                .iter()
+                .map(|s| format!("content-type-{}", s))
+                .collect()
+        } else {
+            vec![]
+        };
+
+    let filters = [
+        (FilterScreen::Method, method_filters),
+        (FilterScreen::Source, source_filters),
+        (FilterScreen::Status, status_filters),
+        (FilterScreen::Key, key_filters),
+        (FilterScreen::StatusRange, status_range_filters),
+        (FilterScreen::ContentType, content_type_filters),
+    ]
+    .into_iter()
+    .flat_map(|(screen, values)| {
+        let prefix = if app.selected_filters.inverted.contains(&screen) {
+            "!"
+        } else {
+            ""
+        };
+
+        values
+            .into_iter()
+            .map(move |value| format!("{}{}", prefix, value))
+    })
+    .collect::<Vec<_>>()
+    .join(", ");
 
     let footer_rect = footer.inner(vertical_layout[1]);
     let footer_vertical_layout = Layout::default()
@@ -1094,17 +1950,23 @@ pub fn render_filters(app: &mut Home, frame: &mut Frame, area: Rect) {
         .direction(Direction::Horizontal)
         .split(footer_vertical_layout[1]);
 
-    let footer_content = Paragraph::new(vec![Line::from(vec![Span::styled(
-        format!(
-            "filter: {}",
-            if filters.len() > 0 {
-                filters
-            } else {
-                "none".into()
-            }
+    let footer_content = Paragraph::new(vec![Line::from(vec![
+        Span::styled(
+            format!(
+                "filter: {}",
+                if filters.len() > 0 {
+                    filters
+                } else {
+                    "none".into()
+                }
+            ),
+            Style::default().fg(app.colors.text.accent_2),
         ),
-        Style::default().fg(app.colors.text.accent_2),
-    )])]);
+        Span::raw(format!(
+            " ({} matches)",
+            get_filtered_count(app, &app.selected_filters)
+        )),
+    ])]);
 
     frame.render_widget(parent_block, area);
     frame.render_widget(table, layout[0]);
@@ -1125,6 +1987,12 @@ pub fn render_filters(app: &mut Home, frame: &mut Frame, area: Rect) {
         FilterScreen::Method => render_filters_method(app, frame, layout[2]),
         FilterScreen::Source => render_filters_source(app, frame, layout[2]),
         FilterScreen::Status => render_filters_status(app, frame, layout[2]),
+        FilterScreen::Key => render_filters_key(app, frame, layout[2]),
+        FilterScreen::StatusRange => render_filters_status_range(app, frame, layout[2]),
+        FilterScreen::ContentType => render_filters_content_type(app, frame, layout[2]),
+        FilterScreen::Presets | FilterScreen::SavePreset => {
+            render_filters_presets(app, frame, layout[2])
+        }
     }
 }
 
@@ -1133,7 +2001,7 @@ pub fn render_sort(app: &mut Home, frame: &mut Frame, area: Rect) {
         .borders(Borders::ALL)
         .border_style(get_border_style(true, &app.colors))
         .title(" SORT ")
-        .border_type(BorderType::Plain);
+        .border_type(app.border_type);
 
     let inner_area = parent_block.inner(area);
 
@@ -1298,27 +2166,70 @@ fn render_actionable_list(
     let active_item_style = get_row_style(RowStyle::Active, colors);
     let default_item_style = get_row_style(RowStyle::Default, colors);
 
+    let multi_selected_item_style = Style::default()
+        .fg(colors.surface.selected)
+        .add_modifier(Modifier::BOLD);
+
     let items: Vec<ListItem> = actionable_list
         .items
         .iter()
-        .map(|item| {
-            ListItem::new(Line::from(vec![
-                Span::raw(format!("{:<15}", item.label)),
-                " ".into(),
-                Span::styled(
-                    item.value.clone().unwrap_or_default().to_string(),
-                    if active && item.action.is_some() {
-                        actionable_item_style
-                    } else if active {
-                        active_item_style
+        .enumerate()
+        .map(|(i, item)| {
+            let label = format!("{:<15} ", item.label);
+            let indent = label.width();
+            let value_style = if active && item.action.is_some() {
+                actionable_item_style
+            } else if active {
+                active_item_style
+            } else {
+                default_item_style
+            };
+
+            // actions stay in place when the values are scrolled
+            let value: String = match item.action {
+                Some(_) => item.value.clone().unwrap_or_default(),
+                None => item
+                    .value
+                    .iter()
+                    .flat_map(|value| value.chars())
+                    .skip(actionable_list.horizontal_offset)
+                    .collect(),
+            };
+
+            // long values wrap below themselves, leaving room for the scrollbar,
+            // unless they are scrolled horizontally
+            let value_width = usize::from(area.width).saturating_sub(indent + 1);
+            let chunks = if actionable_list.horizontal_offset > 0 && item.action.is_none() {
+                vec![value.chars().take(value_width).collect()]
+            } else {
+                wrap(&value, value_width)
+            };
+            let lines: Vec<Line> = chunks
+                .into_iter()
+                .enumerate()
+                .map(|(line_index, chunk)| {
+                    let prefix = if line_index == 0 {
+                        label.clone()
                     } else {
-                        default_item_style
-                    },
-                ),
-            ]))
+                        " ".repeat(indent)
+                    };
+
+                    Line::from(vec![Span::raw(prefix), Span::styled(chunk, value_style)])
+                })
+                .collect();
+
+            let list_item = ListItem::new(lines);
+
+            if actionable_list.is_selected(i) {
+                list_item.style(multi_selected_item_style)
+            } else {
+                list_item
+            }
         })
         .collect();
 
+    let height: usize = items.iter().map(ListItem::height).sum();
+
     let list = List::new(items)
         .style(Style::default().fg(if active {
             colors.text.accent_1
@@ -1331,7 +2242,15 @@ fn render_actionable_list(
             get_row_style(RowStyle::Inactive, colors)
         });
 
-    frame.render_stateful_widget(list, area, &mut actionable_list.scroll_state)
+    frame.render_stateful_widget(list, area, &mut actionable_list.scroll_state);
+
+    if height > area.height.into() {
+        frame.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight),
+            area,
+            &mut actionable_list.scrollbar_state(),
+        );
+    }
 }
 
 /// helper function to create an overlay rect `r`
@@ -1346,3 +2265,914 @@ fn overlay_area(r: Rect) -> Rect {
         .constraints([Constraint::Percentage(100), Constraint::Min(0)].as_ref())
         .split(overlay_layout[1])[0]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::error::Error;
+
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    use crate::components::actionable_list::ActionableListItem;
+    use crate::components::component::Component;
+    use crate::components::handlers;
+    use crate::config::{self, Mapping};
+    use crate::mock;
+    use crate::parser::{parse_raw_trace, parse_raw_trace_with_max_body, Payload};
+    use crate::services::websocket::HTTPTrace;
+
+    fn rendered_details(home: &mut Home) -> Result<String, Box<dyn Error>> {
+        let mut terminal = Terminal::new(TestBackend::new(120, 12))?;
+
+        terminal.draw(|f| details(home, f, f.size()))?;
+
+        Ok(terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect())
+    }
+
+    #[test]
+    fn test_format_key_map() -> Result<(), Box<dyn Error>> {
+        let raw: serde_yaml::Value = serde_yaml::from_str(include_str!("../.config/config.yml"))?;
+        let mapping: Mapping = serde_yaml::from_value(raw["mapping"].clone())?;
+
+        let exported = format_key_map(&mapping.0);
+
+        assert!(exported.lines().any(|line| line == r#""Quit" = ["q"]"#));
+        assert!(exported
+            .lines()
+            .any(|line| line == r#""Move down and select entry below" = ["Down arrow", "j"]"#));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_help_filter() -> Result<(), Box<dyn Error>> {
+        let raw: serde_yaml::Value = serde_yaml::from_str(include_str!("../.config/config.yml"))?;
+        let mapping: Mapping = serde_yaml::from_value(raw["mapping"].clone())?;
+
+        let mut home = Home {
+            key_map: mapping.0,
+            ..Home::default()
+        };
+
+        assert!(get_help_rows(&home).len() > 1);
+
+        home.help_filter = "copy".to_string();
+
+        assert_eq!(
+            vec![
+                (
+                    "Copy selection to OS clipboard".to_string(),
+                    vec!["y".to_string()]
+                ),
+                ("Copy trace URL".to_string(), vec!["o".to_string()]),
+                (
+                    "Copy trace as a Markdown summary, or every header of a headers pane"
+                        .to_string(),
+                    vec!["Y".to_string()]
+                ),
+                (
+                    "Copy trace as cURL, HTTPie, fetch, .http or Markdown".to_string(),
+                    vec!["A".to_string()]
+                )
+            ],
+            get_help_rows(&home)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_help_expand_and_collapse() -> Result<(), Box<dyn Error>> {
+        let raw: serde_yaml::Value = serde_yaml::from_str(include_str!("../.config/config.yml"))?;
+        let mapping: Mapping = serde_yaml::from_value(raw["mapping"].clone())?;
+
+        let mut home = Home {
+            key_map: mapping.0,
+            ..Home::default()
+        };
+
+        home.help_filter = "json object".to_string();
+
+        assert_eq!(
+            vec![
+                (
+                    "Collapse JSON object at cursor".to_string(),
+                    vec!["c".to_string()]
+                ),
+                (
+                    "Collapse all JSON objects".to_string(),
+                    vec!["E".to_string()]
+                ),
+                ("Expand all JSON objects".to_string(), vec!["e".to_string()]),
+            ],
+            get_help_rows(&home)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unmapped_actions() -> Result<(), Box<dyn Error>> {
+        let raw: serde_yaml::Value = serde_yaml::from_str(include_str!("../.config/config.yml"))?;
+        let mut mapping: Mapping = serde_yaml::from_value(raw["mapping"].clone())?;
+
+        mapping
+            .0
+            .retain(|_key_event, action| action != &Action::ToggleWrap);
+
+        let home = Home {
+            key_map: mapping.0,
+            ..Home::default()
+        };
+
+        let unmapped = get_unmapped_actions(&home);
+
+        assert!(unmapped.contains(&"Toggle body line wrapping".to_string()));
+        assert!(!unmapped.contains(&"Quit".to_string()));
+        assert!(!unmapped.contains(&"Move down and select entry below".to_string()));
+        assert!(!unmapped.contains(&"Update Search Query".to_string()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_curl_pane() -> Result<(), Box<dyn Error>> {
+        let trace = match parse_raw_trace(mock::TEST_JSON_1)? {
+            Payload::Trace(trace) => trace,
+            _ => panic!("expected a trace"),
+        };
+
+        let mut home = Home {
+            details_tabs: vec![],
+            details_panes: vec![Curl],
+            ..Home::default()
+        };
+
+        home.update(Action::SelectTrace(Some(trace.clone())))?;
+
+        let rendered = rendered_details(&mut home)?;
+
+        assert!(rendered.contains("CURL"));
+        assert!(rendered.contains("curl 'http://auth.restserver.com/auth?client=mock_client'"));
+        assert!(rendered.contains("close [x]"));
+
+        home.update(Action::SelectTrace(Some(Trace {
+            http: None,
+            ..trace
+        })))?;
+
+        assert!(home.curl_command.is_empty());
+        assert!(rendered_details(&mut home)?.contains("No HTTP request"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_in_flight_trace_style() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+
+        for (id, state) in [("1", State::Sent), ("2", State::Received)] {
+            home.items.insert(Trace {
+                id: id.to_string(),
+                http: Some(HTTPTrace {
+                    state,
+                    uri: format!("http://localhost/{}", id),
+                    ..HTTPTrace::default()
+                }),
+                ..Trace::default()
+            });
+        }
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 12))?;
+
+        terminal.draw(|f| render_traces(&home, f, f.size()))?;
+
+        let buffer = terminal.backend().buffer();
+
+        let row_modifier = |id: &str| {
+            let uri = format!("http://localhost/{}", id);
+
+            (0..buffer.area.height).find_map(|y| {
+                let line: String = (0..buffer.area.width)
+                    .map(|x| buffer.get(x, y).symbol())
+                    .collect();
+
+                line.find(&uri).map(|i| {
+                    let x = line[..i].chars().count() as u16;
+
+                    buffer.get(x, y).modifier
+                })
+            })
+        };
+
+        assert!(row_modifier("1").unwrap().contains(Modifier::ITALIC));
+        assert!(!row_modifier("2").unwrap().contains(Modifier::ITALIC));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_aborted_and_blocked_traces() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+
+        for (id, state) in [("1", State::Aborted), ("2", State::Blocked)] {
+            home.items.insert(Trace {
+                id: id.to_string(),
+                http: Some(HTTPTrace {
+                    state,
+                    uri: format!("http://localhost/{}", id),
+                    ..HTTPTrace::default()
+                }),
+                ..Trace::default()
+            });
+        }
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 12))?;
+
+        terminal.draw(|f| render_traces(&home, f, f.size()))?;
+
+        let buffer = terminal.backend().buffer();
+
+        let row = |id: &str| {
+            let uri = format!("http://localhost/{}", id);
+
+            (0..buffer.area.height).find_map(|y| {
+                let line: String = (0..buffer.area.width)
+                    .map(|x| buffer.get(x, y).symbol())
+                    .collect();
+
+                line.find(&uri).map(|i| {
+                    let x = line[..i].chars().count() as u16;
+
+                    (line.clone(), buffer.get(x, y).modifier)
+                })
+            })
+        };
+
+        let (aborted_line, aborted_modifier) = row("1").ok_or("aborted trace not rendered")?;
+        let (blocked_line, blocked_modifier) = row("2").ok_or("blocked trace not rendered")?;
+
+        assert!(aborted_modifier.contains(Modifier::CROSSED_OUT | Modifier::DIM));
+        assert!(!aborted_line.contains('🔒'));
+        assert!(!blocked_modifier.contains(Modifier::CROSSED_OUT));
+        assert!(blocked_line.contains('🔒'));
+
+        let explanation = |home: &Home| {
+            home.response_details_list
+                .items
+                .iter()
+                .find(|item| item.label == "state")
+                .and_then(|item| item.value.clone())
+        };
+
+        let aborted = home.items.iter().find(|trace| trace.id == "1").cloned();
+        home.update(Action::SelectTrace(aborted))?;
+
+        assert_eq!(
+            Some("aborted, the client cancelled the request".to_string()),
+            explanation(&home)
+        );
+
+        let blocked = home.items.iter().find(|trace| trace.id == "2").cloned();
+        home.update(Action::SelectTrace(blocked))?;
+
+        assert_eq!(
+            Some("blocked, the request was never sent".to_string()),
+            explanation(&home)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_graphql_operation_in_traces() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+
+        if let Payload::Trace(trace) = parse_raw_trace(mock::TEST_JSON_2)? {
+            home.items.insert(trace);
+        }
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 12))?;
+
+        terminal.draw(|f| render_traces(&home, f, f.size()))?;
+
+        let rendered: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+
+        assert!(rendered.contains("http://localhost:3000/api/graphql [People]"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_trace_kind_column() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+
+        let mut websocket = HTTPTrace {
+            uri: "http://localhost/socket".to_string(),
+            ..HTTPTrace::default()
+        };
+        websocket
+            .request_headers
+            .insert(http::header::UPGRADE, "websocket".parse()?);
+
+        let mut events = HTTPTrace {
+            uri: "http://localhost/events".to_string(),
+            ..HTTPTrace::default()
+        };
+        events
+            .response_headers
+            .insert(http::header::CONTENT_TYPE, "text/event-stream".parse()?);
+
+        let plain = HTTPTrace {
+            uri: "http://localhost/plain".to_string(),
+            ..HTTPTrace::default()
+        };
+
+        let rendered = |home: &Home| -> Result<Vec<String>, Box<dyn Error>> {
+            let mut terminal = Terminal::new(TestBackend::new(120, 12))?;
+
+            terminal.draw(|f| render_traces(home, f, f.size()))?;
+
+            let buffer = terminal.backend().buffer();
+
+            Ok((0..buffer.area.height)
+                .map(|y| {
+                    (0..buffer.area.width)
+                        .map(|x| buffer.get(x, y).symbol())
+                        .collect::<String>()
+                })
+                .collect())
+        };
+
+        home.items.insert(Trace {
+            id: "3".to_string(),
+            http: Some(plain),
+            ..Trace::default()
+        });
+
+        assert!(!rendered(&home)?.iter().any(|line| line.contains("Kind")));
+
+        for (id, http) in [("1", websocket), ("2", events)] {
+            home.items.insert(Trace {
+                id: id.to_string(),
+                http: Some(http),
+                ..Trace::default()
+            });
+        }
+
+        let lines = rendered(&home)?;
+        let kind_of = |uri: &str| {
+            lines
+                .iter()
+                .find(|line| line.contains(uri))
+                .and_then(|line| line.trim_start_matches('│').split_whitespace().next())
+                .map(|kind| kind.to_string())
+        };
+
+        assert!(lines.iter().any(|line| line.contains("Kind")));
+        assert_eq!(Some("WS".to_string()), kind_of("/socket"));
+        assert_eq!(Some("SSE".to_string()), kind_of("/events"));
+        assert_eq!(Some("HTTP".to_string()), kind_of("/plain"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mask_query_params_in_traces() -> Result<(), Box<dyn Error>> {
+        let mut home = Home {
+            mask_query_params: vec!["apikey".to_string(), "token".to_string()],
+            ..Home::default()
+        };
+
+        if let Payload::Trace(trace) = parse_raw_trace(mock::TEST_JSON_8)? {
+            home.items.insert(trace);
+        }
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 12))?;
+
+        terminal.draw(|f| render_traces(&home, f, f.size()))?;
+
+        let rendered: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+
+        assert!(rendered.contains("https://hits.webstats.com:433/?apikey=***"));
+        assert!(!rendered.contains("c82e66bd"));
+        assert_eq!(
+            "https://hits.webstats.com:433/?apikey=c82e66bd-4d5b-4bb7-b439-896936c94eb2",
+            home.items.first().unwrap().http.as_ref().unwrap().uri
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_traces_column_widths() {
+        let header = ["Method", "Status", "Request", "Duration", "Time"];
+        let row = |method: &str, uri: &str| {
+            [method, "200", uri, "0.120 s", "3s ago"]
+                .iter()
+                .map(|cell| cell.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let rows = vec![
+            row("GET", "http://localhost/a"),
+            row("OPTIONS", "http://localhost/a/much/longer/path"),
+        ];
+
+        // widest cell or title, the request column takes the rest
+        assert_eq!(
+            vec![7, 6, 72, 8, 6],
+            get_traces_column_widths(&header, &rows, Some(2), 103)
+        );
+
+        // the request column keeps its minimum on narrow terminals
+        assert_eq!(
+            vec![7, 6, TRACES_REQUEST_MIN_WIDTH, 8, 6],
+            get_traces_column_widths(&header, &rows, Some(2), 40)
+        );
+
+        // capped at the maximum
+        let rows = vec![row(&"X".repeat(40), "/")];
+
+        assert_eq!(
+            TRACES_COLUMN_MAX_WIDTH,
+            get_traces_column_widths(&header, &rows, Some(2), 120)[0]
+        );
+    }
+
+    #[test]
+    fn test_configured_trace_columns() -> Result<(), Box<dyn Error>> {
+        let (trace_columns, warnings) = config::parse_trace_columns(
+            &["time", "method", "bogus", "url"]
+                .iter()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>(),
+        );
+
+        assert_eq!(
+            vec![TraceColumn::Time, TraceColumn::Method, TraceColumn::Url],
+            trace_columns
+        );
+        assert_eq!(
+            vec!["unknown trace list column \"bogus\" ignored".to_string()],
+            warnings
+        );
+
+        let mut home = Home {
+            trace_columns,
+            ..Home::default()
+        };
+
+        if let Payload::Trace(trace) = parse_raw_trace(mock::TEST_JSON_3)? {
+            home.items.insert(trace);
+        }
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 12))?;
+
+        terminal.draw(|f| render_traces(&home, f, f.size()))?;
+
+        let buffer = terminal.backend().buffer();
+
+        let header: String = (0..buffer.area.width)
+            .map(|x| buffer.get(x, 1).symbol())
+            .collect();
+
+        assert_eq!(
+            vec!["Time", "Method", "Request"],
+            header
+                .trim_matches(|c: char| c == '│' || c.is_whitespace())
+                .split_whitespace()
+                .collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_column_widths_do_not_change_while_scrolling() -> Result<(), Box<dyn Error>> {
+        let mut home = Home {
+            trace_columns: vec![TraceColumn::Host, TraceColumn::Url],
+            ..Home::default()
+        };
+
+        for index in 0..30 {
+            let host = if index == 0 {
+                "api.example.com"
+            } else {
+                "a.io"
+            };
+
+            home.items.insert(Trace {
+                id: index.to_string(),
+                timestamp: index,
+                http: Some(HTTPTrace {
+                    uri: format!("http://{}/items", host),
+                    ..HTTPTrace::default()
+                }),
+                ..Trace::default()
+            });
+        }
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 12))?;
+        let mut request_column = |home: &Home| -> Result<Option<usize>, Box<dyn Error>> {
+            terminal.draw(|f| render_traces(home, f, f.size()))?;
+
+            let buffer = terminal.backend().buffer();
+            let header: String = (0..buffer.area.width)
+                .map(|x| buffer.get(x, 1).symbol())
+                .collect();
+
+            Ok(header.find("Request"))
+        };
+
+        let at_start = request_column(&home)?;
+
+        home.main.offset = 25;
+
+        assert!(at_start > Some("api.example.com".len()));
+        assert_eq!(at_start, request_column(&home)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_size_column_of_truncated_and_binary_bodies() -> Result<(), Box<dyn Error>> {
+        let mut home = Home {
+            trace_columns: vec![TraceColumn::Size, TraceColumn::Url],
+            ..Home::default()
+        };
+
+        for (raw, max_body_bytes) in [(mock::TEST_JSON_1, 16), (mock::TEST_JSON_20, 0)] {
+            if let Payload::Trace(trace) = parse_raw_trace_with_max_body(raw, max_body_bytes)? {
+                home.items.insert(trace);
+            }
+        }
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 12))?;
+
+        terminal.draw(|f| render_traces(&home, f, f.size()))?;
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+
+        for trace in &home.items {
+            let size = trace.http.as_ref().unwrap().response_body_size;
+
+            assert!(screen.contains(&format_size(size)));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_host_as_source_fallback() -> Result<(), Box<dyn Error>> {
+        let mut home = Home::default();
+
+        for raw in [mock::TEST_JSON_1, mock::TEST_JSON_2] {
+            if let Payload::Trace(trace) = parse_raw_trace(raw)? {
+                home.items.insert(trace);
+            }
+        }
+
+        home.items.insert(Trace {
+            id: "unnamed".to_string(),
+            http: Some(HTTPTrace {
+                uri: "http://localhost:8080/health".to_string(),
+                ..HTTPTrace::default()
+            }),
+            ..Trace::default()
+        });
+
+        assert_eq!(
+            vec!["localhost:8080", "mockData"],
+            get_services_from_traces(&home)
+        );
+
+        home.filters.source = SourceFilter::Applied(HashSet::from(["localhost:8080".to_string()]));
+
+        let rendered: Vec<&str> = get_rendered_items(&home)
+            .iter()
+            .map(|trace| trace.id.as_str())
+            .collect();
+
+        assert_eq!(vec!["unnamed"], rendered);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_debug_level_filter() -> Result<(), Box<dyn Error>> {
+        let mut home = Home {
+            logs: [
+                "ERROR failed to bind",
+                "[warn] unknown column",
+                "connected",
+                "debug: tick",
+            ]
+            .iter()
+            .map(|line| (chrono::Local::now(), line.to_string()))
+            .collect(),
+            hide_log_timestamps: true,
+            active_block: ActiveBlock::Debug,
+            ..Home::default()
+        };
+
+        assert_eq!(4, get_debug_lines(&home).len());
+
+        home.debug_level = "error".parse()?;
+
+        assert_eq!(vec!["ERROR failed to bind"], get_debug_lines(&home));
+
+        handlers::handle_cycle_log_level(&mut home);
+
+        assert_eq!(LogLevel::Warn, home.debug_level);
+        assert_eq!(
+            vec!["ERROR failed to bind", "[warn] unknown column"],
+            get_debug_lines(&home)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_debug_log_timestamps() -> Result<(), Box<dyn Error>> {
+        let mut home = Home {
+            active_block: ActiveBlock::Debug,
+            ..Home::default()
+        };
+        let before = chrono::Local::now();
+
+        home.update(Action::AddTrace(Trace {
+            id: "1".to_string(),
+            http: Some(HTTPTrace::default()),
+            warnings: vec!["unknown state".to_string()],
+            ..Trace::default()
+        }))?;
+
+        let (time, line) = home.logs.last().ok_or("nothing logged")?.clone();
+
+        assert_eq!("WARN trace 1: unknown state", line);
+        assert!(time >= before && time <= chrono::Local::now());
+        assert_eq!(
+            vec![format!(
+                "{} WARN trace 1: unknown state",
+                time.format(LOG_TIME_FORMAT)
+            )],
+            get_debug_lines(&home)
+        );
+
+        let prefix = regex::Regex::new(r"^\d{2}:\d{2}:\d{2}\.\d{3} WARN")?;
+
+        assert!(prefix.is_match(&get_debug_lines(&home)[0]));
+
+        home.update(Action::ToggleLogTimestamps)?;
+
+        assert_eq!(vec!["WARN trace 1: unknown state"], get_debug_lines(&home));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_long_value_wraps() -> Result<(), Box<dyn Error>> {
+        let mut terminal = Terminal::new(TestBackend::new(40, 6))?;
+        let token = "x".repeat(60);
+        let mut list = ActionableList::with_items(vec![
+            ActionableListItem::with_labelled_value("authorization", &token),
+            ActionableListItem::with_labelled_value("accept", "*/*"),
+        ]);
+
+        terminal
+            .draw(|f| render_actionable_list(&mut list, f, f.size(), &Colors::default(), true))?;
+
+        let rows: Vec<String> = terminal
+            .backend()
+            .buffer()
+            .content()
+            .chunks(40)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect())
+            .collect();
+
+        assert_eq!(
+            format!("authorization   {}", "x".repeat(23)),
+            rows[0].trim_end()
+        );
+        assert_eq!(
+            format!("{}{}", " ".repeat(16), "x".repeat(23)),
+            rows[1].trim_end()
+        );
+        assert_eq!(
+            format!("{}{}", " ".repeat(16), "x".repeat(14)),
+            rows[2].trim_end()
+        );
+        assert_eq!("accept          */*", rows[3].trim_end());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_details_horizontal_scroll() -> Result<(), Box<dyn Error>> {
+        let mut request_headers = http::HeaderMap::new();
+
+        request_headers.insert(
+            "cookie",
+            http::HeaderValue::from_static("session=0123456789abcdef"),
+        );
+
+        let mut home = Home::default();
+
+        home.update(Action::SelectTrace(Some(Trace {
+            id: "1".to_string(),
+            http: Some(HTTPTrace {
+                request_headers,
+                ..HTTPTrace::default()
+            }),
+            ..Trace::default()
+        })))?;
+
+        home.active_block = ActiveBlock::Details;
+        home.details_block = DetailsPane::RequestHeaders;
+
+        let right = KeyEvent::from(KeyCode::Char('l'));
+
+        home.update(Action::NavigateRight(Some(right)))?;
+        home.update(Action::NavigateRight(Some(right)))?;
+
+        assert_eq!(8, home.request_headers_list.horizontal_offset);
+
+        // narrower than the value, which is cut instead of wrapped
+        let mut terminal = Terminal::new(TestBackend::new(30, 2))?;
+
+        terminal.draw(|f| {
+            render_actionable_list(
+                &mut home.request_headers_list,
+                f,
+                f.size(),
+                &Colors::default(),
+                true,
+            )
+        })?;
+
+        let rows: Vec<String> = terminal
+            .backend()
+            .buffer()
+            .content()
+            .chunks(30)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect())
+            .collect();
+
+        assert_eq!("cookie          0123456789abc", rows[0].trim_end());
+        assert_eq!("actions         close [x]", rows[1].trim_end());
+
+        home.update(Action::NavigateLeft(Some(KeyEvent::from(KeyCode::Char(
+            'h',
+        )))))?;
+
+        assert_eq!(4, home.request_headers_list.horizontal_offset);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pop_out_all_renders_every_pane() -> Result<(), Box<dyn Error>> {
+        let mut trace = match parse_raw_trace(mock::TEST_JSON_1)? {
+            Payload::Trace(trace) => trace,
+            _ => panic!("expected a trace"),
+        };
+
+        // lists the extra pane too
+        trace.extra.insert("traceId".to_string(), "4bf92f35".into());
+
+        let mut home = Home {
+            details_tabs: DetailsPane::iter().collect(),
+            ..Home::default()
+        };
+
+        home.update(Action::SelectTrace(Some(trace)))?;
+        home.update(Action::PopOutAllDetailsTabs)?;
+
+        let rendered = rendered_details(&mut home)?;
+
+        for pane in DetailsPane::iter() {
+            assert!(
+                rendered.contains(&format!("  {}  ", pane)),
+                "{} pane is not rendered",
+                pane
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_details_tab_header_count() -> Result<(), Box<dyn Error>> {
+        let trace = match parse_raw_trace(mock::TEST_JSON_1)? {
+            Payload::Trace(trace) => trace,
+            _ => panic!("expected a trace"),
+        };
+        let header_count = trace.http.clone().unwrap_or_default().request_headers.len();
+
+        let mut home = Home {
+            details_tabs: DetailsPane::iter().collect(),
+            ..Home::default()
+        };
+
+        home.update(Action::SelectTrace(Some(trace)))?;
+
+        assert!(header_count > 0);
+        assert_eq!(
+            format!("REQUEST HEADERS ({})", header_count),
+            details_tab_title(&home, RequestHeaders)
+        );
+        assert_eq!("TIMING", details_tab_title(&home, Timing));
+        assert!(
+            rendered_details(&mut home)?.contains(&format!("REQUEST HEADERS ({})", header_count))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_request_summary() -> Result<(), Box<dyn Error>> {
+        let trace = match parse_raw_trace(mock::TEST_JSON_1)? {
+            Payload::Trace(trace) => trace,
+            _ => panic!("expected a trace"),
+        };
+
+        let mut home = Home {
+            details_tabs: DetailsPane::iter().collect(),
+            ..Home::default()
+        };
+
+        assert!(rendered_details(&mut home)?.starts_with("No trace selected"));
+
+        home.update(Action::SelectTrace(Some(trace)))?;
+
+        assert!(rendered_details(&mut home)?
+            .starts_with("GET http://auth.restserver.com/auth?client=mock_client 200 200ms"));
+        assert_eq!(
+            "GET http://auth.…mock_client 200 200ms",
+            get_request_summary(&home, 38)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_traces_empty_state() -> Result<(), Box<dyn Error>> {
+        let rendered = |home: &Home| -> Result<String, Box<dyn Error>> {
+            let mut terminal = Terminal::new(TestBackend::new(120, 12))?;
+
+            terminal.draw(|f| render_traces(home, f, f.size()))?;
+
+            Ok(terminal
+                .backend()
+                .buffer()
+                .content()
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect())
+        };
+
+        let mut home = Home::default();
+
+        assert!(rendered(&home)?.contains("Waiting for traces"));
+
+        if let Payload::Trace(trace) = parse_raw_trace(mock::TEST_JSON_1)? {
+            home.items.insert(trace);
+        }
+
+        assert!(!rendered(&home)?.contains("Waiting for traces"));
+        assert!(!rendered(&home)?.contains("No traces match current filters"));
+
+        home.search_query = "nothing-matches-this".to_string();
+
+        assert!(rendered(&home)?.contains("No traces match current filters"));
+
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@ use crate::parser::HTTPTimings;
 use crate::parser::{parse_raw_trace, Payload};
 use crate::wss::WebSocket;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::error::Error;
 use std::fmt::Display;
 use std::hash::{Hash, Hasher};
@@ -27,6 +28,20 @@ pub enum State {
     Error,
 }
 
+impl State {
+    // What happened to a request that didn't get a response.
+    pub fn explanation(&self) -> Option<&'static str> {
+        match self {
+            State::Received => None,
+            State::Sent => Some("in flight, waiting for the response"),
+            State::Aborted => Some("aborted, the client cancelled the request"),
+            State::Blocked => Some("blocked, the request was never sent"),
+            State::Timeout => Some("timed out waiting for the response"),
+            State::Error => Some("failed, the request errored"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct HTTPTrace {
     #[serde(skip_serializing, skip_deserializing)]
@@ -51,17 +66,51 @@ pub struct HTTPTrace {
     pub timings: Option<HTTPTimings>,
     pub port: String,
     pub path: String,
+    pub remote_address: Option<String>,
+    pub tls_version: Option<String>,
+    pub graphql_operation: Option<String>,
+    // Entries of the response body's `errors` array.
+    #[serde(default)]
+    pub response_errors: usize,
+    // Byte length of the response body as received, before any truncation.
+    #[serde(default)]
+    pub response_body_size: usize,
+    // The stored request body is cut short, so exports don't replay it.
+    #[serde(default)]
+    pub request_body_truncated: bool,
     pub raw: String,
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, strum_macros::Display)]
+pub enum TraceKind {
+    #[default]
+    #[strum(serialize = "HTTP")]
+    Http,
+    #[strum(serialize = "WS")]
+    WebSocket,
+    #[strum(serialize = "SSE")]
+    Sse,
+    #[strum(serialize = "gRPC")]
+    Grpc,
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Trace {
     pub id: String,
     pub timestamp: i64,
     pub service_name: Option<String>,
     pub http: Option<HTTPTrace>,
+    // app specific `data` fields the collector attached, e.g. `traceId`
+    #[serde(default)]
+    pub extra: Map<String, Value>,
+    // problems found while parsing that didn't fail the trace
+    #[serde(skip)]
+    pub warnings: Vec<String>,
 }
 
+// Traces are identified by their id only, so a later version of a trace
+// (e.g. once its response arrives) replaces the earlier one in a set. Display
+// order is decided by the active sort, not by this ordering.
 impl PartialEq<Trace> for Trace {
     fn eq(&self, other: &Trace) -> bool {
         self.id == *other.id
@@ -70,9 +119,120 @@ impl PartialEq<Trace> for Trace {
 
 impl Eq for Trace {}
 
+impl Trace {
+    // The reporting service, or the host the request went to when the
+    // collector did not name one.
+    pub fn effective_source(&self) -> String {
+        if let Some(name) = self.service_name.as_ref().filter(|name| !name.is_empty()) {
+            return name.clone();
+        }
+
+        self.http
+            .as_ref()
+            .and_then(|http| http.uri.parse::<http::Uri>().ok())
+            .and_then(|uri| {
+                uri.authority().map(|authority| match authority.port_u16() {
+                    Some(port) => format!("{}:{}", authority.host(), port),
+                    None => authority.host().to_string(),
+                })
+            })
+            .unwrap_or_default()
+    }
+
+    // WebSocket upgrades and event streams are captured as HTTP requests, so
+    // their kind is told apart by the upgrade request and the response type.
+    pub fn kind(&self) -> TraceKind {
+        let http = match &self.http {
+            Some(http) => http,
+            None => return TraceKind::Http,
+        };
+
+        let is_upgrade = http
+            .request_headers
+            .get(http::header::UPGRADE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.eq_ignore_ascii_case("websocket"));
+
+        if is_upgrade || http.uri.starts_with("ws://") || http.uri.starts_with("wss://") {
+            return TraceKind::WebSocket;
+        }
+
+        let is_event_stream = http
+            .response_headers
+            .get(http::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("text/event-stream"));
+
+        if is_event_stream {
+            return TraceKind::Sse;
+        }
+
+        if is_grpc_content_type(&http.request_headers)
+            || is_grpc_content_type(&http.response_headers)
+        {
+            TraceKind::Grpc
+        } else {
+            TraceKind::Http
+        }
+    }
+
+    // gRPC calls are posted to `/<package>.<Service>/<Method>`.
+    pub fn grpc_method(&self) -> Option<String> {
+        let http = self.http.as_ref()?;
+        let uri = http.uri.parse::<http::Uri>().ok()?;
+        let method = uri.path().trim_start_matches('/');
+
+        (!method.is_empty()).then(|| method.to_string())
+    }
+
+    // The `grpc-status` code with its name, e.g. "5 NOT_FOUND".
+    pub fn grpc_status(&self) -> Option<String> {
+        let code = self
+            .http
+            .as_ref()?
+            .response_headers
+            .get("grpc-status")?
+            .to_str()
+            .ok()?
+            .trim()
+            .to_string();
+
+        let name = match code.as_str() {
+            "0" => "OK",
+            "1" => "CANCELLED",
+            "2" => "UNKNOWN",
+            "3" => "INVALID_ARGUMENT",
+            "4" => "DEADLINE_EXCEEDED",
+            "5" => "NOT_FOUND",
+            "6" => "ALREADY_EXISTS",
+            "7" => "PERMISSION_DENIED",
+            "8" => "RESOURCE_EXHAUSTED",
+            "9" => "FAILED_PRECONDITION",
+            "10" => "ABORTED",
+            "11" => "OUT_OF_RANGE",
+            "12" => "UNIMPLEMENTED",
+            "13" => "INTERNAL",
+            "14" => "UNAVAILABLE",
+            "15" => "DATA_LOSS",
+            "16" => "UNAUTHENTICATED",
+            _ => return Some(code),
+        };
+
+        Some(format!("{} {}", code, name))
+    }
+}
+
+// Covers `application/grpc` and its `+proto`/`+json` variants.
+pub fn is_grpc_content_type(headers: &http::HeaderMap) -> bool {
+    headers
+        .get(http::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/grpc"))
+}
+
 impl PartialOrd for Trace {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(other.id.cmp(&self.id))
+        Some(self.cmp(other))
     }
 }
 
@@ -200,6 +360,8 @@ impl Client {
             mock::TEST_JSON_17,
             mock::TEST_JSON_18,
             mock::TEST_JSON_19,
+            mock::TEST_JSON_20,
+            mock::TEST_JSON_21,
         ];
 
         for json_string in json_strings {
@@ -211,3 +373,63 @@ impl Client {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::BTreeSet;
+
+    use pretty_assertions::assert_eq;
+
+    fn trace(id: &str, timestamp: i64, state: State) -> Trace {
+        Trace {
+            id: id.to_string(),
+            timestamp,
+            http: Some(HTTPTrace {
+                state,
+                ..HTTPTrace::default()
+            }),
+            ..Trace::default()
+        }
+    }
+
+    #[test]
+    fn test_replace_trace_by_id() {
+        let mut items = BTreeSet::new();
+
+        items.insert(trace("1", 100, State::Sent));
+        items.replace(trace("1", 100, State::Received));
+
+        assert_eq!(1, items.len());
+        assert_eq!(
+            State::Received,
+            items.first().unwrap().http.as_ref().unwrap().state
+        );
+    }
+
+    #[test]
+    fn test_equal_timestamps_do_not_collide() {
+        let mut items = BTreeSet::new();
+
+        items.insert(trace("1", 100, State::Sent));
+        items.replace(trace("2", 100, State::Sent));
+
+        assert_eq!(2, items.len());
+        assert!(items.iter().any(|trace| trace.id == "1"));
+        assert!(items.iter().any(|trace| trace.id == "2"));
+    }
+
+    #[test]
+    fn test_equal_timestamps_ordered_by_id() {
+        let mut items = BTreeSet::new();
+
+        for id in ["b", "c", "a"] {
+            items.insert(trace(id, 100, State::Sent));
+        }
+
+        let ids: Vec<&str> = items.iter().map(|trace| trace.id.as_str()).collect();
+
+        assert_eq!(vec!["c", "b", "a"], ids);
+    }
+}
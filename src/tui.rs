@@ -1,5 +1,5 @@
 use crossterm::{
-    event::KeyEvent,
+    event::{DisableMouseCapture, EnableMouseCapture, KeyEvent, MouseEvent},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -16,6 +16,7 @@ pub type Rect = ratatui::layout::Rect;
 pub enum Event {
     Error,
     Key(KeyEvent),
+    Mouse(MouseEvent),
     Render,
     Tick,
     OnMount,
@@ -33,7 +34,7 @@ pub struct Tui {
 
 impl Tui {
     pub fn new() -> Self {
-        let tick_rate = 4.0;
+        let tick_rate = 10.0;
         let frame_rate = 60.0;
         // NOTE: Internal message channel. Publishes events native to tui. Consumer can map
         // internal events to app events.
@@ -53,7 +54,7 @@ impl Tui {
 
     pub fn enter(&mut self) -> Result<(), Box<dyn Error>> {
         enable_raw_mode()?;
-        execute!(stdout(), EnterAlternateScreen)?;
+        execute!(stdout(), EnterAlternateScreen, EnableMouseCapture)?;
         self.terminal.clear()?;
         self.start();
         Ok(())
@@ -61,7 +62,11 @@ impl Tui {
 
     pub fn exit(&mut self) -> Result<(), Box<dyn Error>> {
         disable_raw_mode()?;
-        execute!(self.terminal.backend_mut(), LeaveAlternateScreen,)?;
+        execute!(
+            self.terminal.backend_mut(),
+            LeaveAlternateScreen,
+            DisableMouseCapture
+        )?;
         self.terminal.show_cursor()?;
         Ok(())
     }
@@ -93,6 +98,9 @@ impl Tui {
                                             _tx.send(Event::Key(key)).unwrap();
                                         }
                                     },
+                                    crossterm::event::Event::Mouse(mouse) => {
+                                        _tx.send(Event::Mouse(mouse)).unwrap();
+                                    },
                                     _ => {},
                                 }
                             }
@@ -102,7 +110,7 @@ impl Tui {
                             None => {},
                         }
                     },
-                    // TODO: What is this used for?
+                    // drives animations, e.g. the spinner of in-flight requests
                     _ = tick_delay => {
                         _tx.send(Event::Tick).unwrap();
                     },
@@ -0,0 +1,162 @@
+use std::error::Error;
+use std::fs;
+use std::os::unix::fs::FileTypeExt;
+
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::net::UnixListener;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::app::Action;
+use crate::parser::parse_raw_trace_with_max_body;
+
+// The socket path given with `--uds <path>`, if any.
+pub fn parse_uds_arg(mut args: impl Iterator<Item = String>) -> Option<String> {
+    while let Some(arg) = args.next() {
+        if arg == "--uds" {
+            return args.next();
+        }
+
+        if let Some(path) = arg.strip_prefix("--uds=") {
+            return Some(path.to_string());
+        }
+    }
+
+    None
+}
+
+// Accepts collectors on a Unix domain socket, each sending one trace JSON
+// per line.
+pub async fn listen(
+    path: &str,
+    tx: UnboundedSender<Action>,
+    max_body_bytes: usize,
+) -> Result<(), Box<dyn Error>> {
+    // a socket file left behind by a previous run would fail the bind, but
+    // anything else at that path is not ours to remove
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => fs::remove_file(path)?,
+        Ok(_) => return Err(format!("{} exists and is not a socket", path).into()),
+        Err(_) => {}
+    }
+
+    let listener = UnixListener::bind(path)?;
+
+    while let Ok((stream, _addr)) = listener.accept().await {
+        tokio::spawn(handle_stream(stream, tx.clone(), max_body_bytes));
+    }
+
+    Ok(())
+}
+
+pub async fn handle_stream<R: AsyncRead + Unpin>(
+    stream: R,
+    tx: UnboundedSender<Action>,
+    max_body_bytes: usize,
+) {
+    let mut lines = BufReader::new(stream).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match parse_raw_trace_with_max_body(&line, max_body_bytes) {
+            Ok(payload) => {
+                for trace in payload.into_traces() {
+                    let _ = tx.send(Action::AddTrace(trace));
+                }
+            }
+            Err(err) => {
+                let _ = tx.send(Action::Error(format!("Trace NOT parsed: {:?}", err)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+    use serde_json::Value;
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::UnixStream;
+    use tokio::sync::mpsc;
+    use tokio::time::{sleep, timeout};
+
+    use crate::mock;
+
+    #[test]
+    fn test_parse_uds_arg() {
+        let args = |raw: &[&str]| raw.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            Some("/tmp/envy.sock".to_string()),
+            parse_uds_arg(args(&["envy", "--uds", "/tmp/envy.sock"]).into_iter())
+        );
+        assert_eq!(
+            Some("/tmp/envy.sock".to_string()),
+            parse_uds_arg(args(&["envy", "--uds=/tmp/envy.sock"]).into_iter())
+        );
+        assert_eq!(None, parse_uds_arg(args(&["envy"]).into_iter()));
+    }
+
+    #[tokio::test]
+    async fn test_trace_from_socket() -> Result<(), Box<dyn Error>> {
+        let path = std::env::temp_dir().join(format!("envy-test-{}.sock", std::process::id()));
+        let path = path.to_str().unwrap().to_string();
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        let listen_path = path.clone();
+        tokio::spawn(async move {
+            let _ = listen(&listen_path, tx, 0).await;
+        });
+
+        let mut stream = timeout(Duration::from_secs(1), async {
+            loop {
+                match UnixStream::connect(&path).await {
+                    Ok(stream) => break stream,
+                    Err(_) => sleep(Duration::from_millis(10)).await,
+                }
+            }
+        })
+        .await?;
+
+        // one trace per line
+        let line = serde_json::from_str::<Value>(mock::TEST_JSON_3)?.to_string();
+
+        stream.write_all(format!("{}\n", line).as_bytes()).await?;
+
+        let action = timeout(Duration::from_secs(1), rx.recv()).await?;
+
+        fs::remove_file(&path)?;
+
+        match action {
+            Some(Action::AddTrace(trace)) => assert_eq!("3", trace.id),
+            _ => panic!("expected a trace"),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_listen_keeps_other_files() -> Result<(), Box<dyn Error>> {
+        let path = std::env::temp_dir().join(format!("envy-test-{}.txt", std::process::id()));
+
+        fs::write(&path, "notes")?;
+
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let result = listen(path.to_str().unwrap(), tx, 0).await;
+        let contents = fs::read_to_string(&path)?;
+
+        fs::remove_file(&path)?;
+
+        assert!(result.is_err());
+        assert_eq!("notes", contents);
+
+        Ok(())
+    }
+}
@@ -1,10 +1,25 @@
 use core::str::FromStr;
-use http::Uri;
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, Utc};
+use http::{HeaderMap, Uri};
 use regex::Regex;
+use serde_json::Value;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
-use crate::app::{SortDirection, SortSource, SourceFilter, TraceSort};
+use crate::app::{
+    ActiveBlock, DetailsPane, DetailsSort, ExportFormat, FilterCombination, FilterScreen,
+    SortDirection, SortSource, SourceFilter, StatusRange, TimeWindow, TraceFilter, TraceSort,
+};
 use crate::components::home::Home;
-use crate::services::websocket::Trace;
+use crate::consts::{SPINNER_FRAMES, TRACE_RATE_WINDOW};
+use crate::parser::{
+    generate_curl_command, generate_fetch_snippet, generate_http_file, generate_httpie_command,
+    generate_markdown_summary, is_redacted, pretty_parse_body, redact_uri, REDACTED_VALUE,
+};
+use crate::services::websocket::{State, Trace};
 
 // NOTE: [stackoverflow](https://stackoverflow.com/questions/38461429/how-can-i-truncate-a-string-to-have-at-most-n-characters)
 pub fn truncate(s: &str, max_chars: usize) -> String {
@@ -20,6 +35,139 @@ pub fn truncate(s: &str, max_chars: usize) -> String {
     }
 }
 
+// Keeps both ends of the string, which for URLs are the host and the
+// most specific part of the path.
+pub fn truncate_middle(s: &str, max_chars: usize) -> String {
+    let length = s.chars().count();
+
+    if length <= max_chars {
+        return s.to_owned();
+    }
+
+    let kept = max_chars.saturating_sub(1);
+    let head: String = s.chars().take(kept - kept / 2).collect();
+    let tail: String = s.chars().skip(length - kept / 2).collect();
+
+    format!("{head}…{tail}")
+}
+
+// Splits the string into lines of at most `width` columns, always returning
+// at least one line.
+pub fn wrap(s: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = vec![String::new()];
+    let mut line_width = 0;
+
+    for c in s.chars() {
+        let char_width = c.width().unwrap_or(0);
+
+        if line_width + char_width > width && line_width > 0 {
+            lines.push(String::new());
+            line_width = 0;
+        }
+
+        if let Some(line) = lines.last_mut() {
+            line.push(c);
+        }
+
+        line_width += char_width;
+    }
+
+    lines
+}
+
+// Collectors report milliseconds, but older ones sent seconds; anything
+// below this is far too small to be a millisecond timestamp.
+const MILLISECOND_TIMESTAMP_THRESHOLD: i64 = 100_000_000_000;
+
+pub fn timestamp_millis(timestamp: i64) -> i64 {
+    if timestamp.abs() < MILLISECOND_TIMESTAMP_THRESHOLD {
+        timestamp.saturating_mul(1000)
+    } else {
+        timestamp
+    }
+}
+
+pub fn timestamp_to_datetime(timestamp: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(timestamp_millis(timestamp)).unwrap_or_default()
+}
+
+pub fn format_timestamp(timestamp: i64, format: &str) -> String {
+    timestamp_to_datetime(timestamp).format(format).to_string()
+}
+
+pub fn get_trace_age(trace: &Trace, now: SystemTime) -> Duration {
+    let now_millis = now
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as i64);
+
+    let age = now_millis.saturating_sub(timestamp_millis(trace.timestamp));
+
+    Duration::from_millis(age.max(0) as u64)
+}
+
+pub fn format_size(bytes: usize) -> String {
+    match bytes {
+        0..=999 => format!("{} B", bytes),
+        1_000..=999_999 => format!("{:.1} kB", bytes as f64 / 1_000.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_000_000.0),
+    }
+}
+
+pub fn get_spinner_glyph(tick: usize) -> &'static str {
+    SPINNER_FRAMES[tick % SPINNER_FRAMES.len()]
+}
+
+pub fn format_relative_time(age: Duration) -> String {
+    let seconds = age.as_secs();
+
+    let (days, hours, minutes, seconds) = (
+        seconds / 86_400,
+        seconds % 86_400 / 3_600,
+        seconds % 3_600 / 60,
+        seconds % 60,
+    );
+
+    if days > 0 {
+        format!("{}d{}h ago", days, hours)
+    } else if hours > 0 {
+        format!("{}h{}m ago", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m{}s ago", minutes, seconds)
+    } else {
+        format!("{}s ago", seconds)
+    }
+}
+
+pub fn format_uptime(uptime: Duration) -> String {
+    let seconds = uptime.as_secs();
+
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3_600,
+        seconds % 3_600 / 60,
+        seconds % 60
+    )
+}
+
+// Traces per second over the last `TRACE_RATE_WINDOW`.
+pub fn trace_rate(arrivals: &VecDeque<Instant>, now: Instant) -> f64 {
+    let recent = arrivals
+        .iter()
+        .filter(|arrival| now.saturating_duration_since(**arrival) <= TRACE_RATE_WINDOW)
+        .count();
+
+    recent as f64 / TRACE_RATE_WINDOW.as_secs_f64()
+}
+
+pub fn sort_details<T>(items: &mut [T], sort: DetailsSort, name: impl Fn(&T) -> String) {
+    match sort {
+        DetailsSort::NameAsc => items.sort_by_key(|item| name(item)),
+        DetailsSort::NameDesc => items.sort_by_key(|item| std::cmp::Reverse(name(item))),
+        DetailsSort::Unsorted => {}
+    }
+}
+
 pub fn parse_query_params(url: String) -> Vec<(String, String)> {
     let uri = url.parse::<Uri>();
 
@@ -27,13 +175,11 @@ pub fn parse_query_params(url: String) -> Vec<(String, String)> {
         Ok(value) => match value.query().map(|v| (v).split('&')) {
             Some(v) => v
                 .map(|query_param_entry| {
-                    let query_param_entry_in_vector =
-                        query_param_entry.split('=').collect::<Vec<&str>>();
+                    let (key, value) = query_param_entry
+                        .split_once('=')
+                        .unwrap_or((query_param_entry, ""));
 
-                    (
-                        String::from(query_param_entry_in_vector[0]),
-                        String::from(query_param_entry_in_vector[1]),
-                    )
+                    (String::from(key), String::from(value))
                 })
                 .collect(),
             _ => vec![],
@@ -42,6 +188,38 @@ pub fn parse_query_params(url: String) -> Vec<(String, String)> {
     }
 }
 
+// Replaces the values of the `masked` query params, matched
+// case-insensitively, for display.
+pub fn mask_query_params(uri: &str, masked: &[String]) -> String {
+    let base = match uri.split_once('?') {
+        Some((base, _)) if !masked.is_empty() => base,
+        _ => return uri.to_string(),
+    };
+
+    let params = parse_query_params(uri.to_string());
+
+    if !params
+        .iter()
+        .any(|(key, _)| masked.iter().any(|m| m.eq_ignore_ascii_case(key)))
+    {
+        return uri.to_string();
+    }
+
+    let query = params
+        .iter()
+        .map(|(key, value)| {
+            if masked.iter().any(|m| m.eq_ignore_ascii_case(key)) {
+                format!("{}=***", key)
+            } else {
+                format!("{}={}", key, value)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("&");
+
+    format!("{}?{}", base, query)
+}
+
 fn fuzzy_regex(query: String) -> Regex {
     let mut fuzzy_query = String::new();
 
@@ -52,79 +230,427 @@ fn fuzzy_regex(query: String) -> Regex {
     return Regex::from_str(&fuzzy_query).unwrap();
 }
 
-pub fn get_rendered_items(app: &Home) -> Vec<&Trace> {
-    let mut maybe_re: Option<Regex> = None;
-    if !app.search_query.is_empty() {
-        maybe_re = Some(fuzzy_regex(app.search_query.clone()));
+pub fn search_regex(query: &str) -> Option<Regex> {
+    if query.is_empty() {
+        None
+    } else {
+        Some(fuzzy_regex(query.to_string()))
     }
+}
 
-    let no_applied_method_filter = app
-        .filters
-        .method
-        .iter()
-        .filter(|(_key, method_filter)| method_filter.selected == true)
-        .collect::<Vec<_>>()
-        .is_empty();
+pub fn matches_search(trace: &Trace, maybe_re: Option<&Regex>) -> bool {
+    match (maybe_re, &trace.http) {
+        (Some(re), Some(http)) => re.is_match(&http.uri),
+        (Some(_), None) => false,
+        (None, _) => true,
+    }
+}
 
-    let no_applied_status_filter = app
-        .filters
-        .status
-        .iter()
-        .filter(|(_key, method_filter)| method_filter.selected == true)
-        .collect::<Vec<_>>()
-        .is_empty();
+// Arrived since the traces list was last focused.
+pub fn is_new_trace(app: &Home, trace: &Trace) -> bool {
+    app.last_viewed_timestamp
+        .is_some_and(|timestamp| trace.timestamp > timestamp)
+}
 
-    let mut items_as_vector = app
-        .items
+pub fn is_hidden(app: &Home, trace: &Trace) -> bool {
+    if app.show_hidden {
+        return false;
+    }
+
+    match &trace.http {
+        Some(http) => app.hide_patterns.iter().any(|re| re.is_match(&http.uri)),
+        None => false,
+    }
+}
+
+pub fn get_hidden_count(app: &Home) -> usize {
+    app.items
         .iter()
-        .filter(|trace| {
-            if let Some(re) = &maybe_re {
-                re.is_match(&trace.http.as_ref().unwrap().uri)
-            } else {
-                true
-            }
-        })
-        .filter(|trace| match (&app.filters.source, &trace.service_name) {
-            (SourceFilter::All, _) => true,
-            (SourceFilter::Applied(sources), Some(trace_source)) => sources.contains(trace_source),
-            (SourceFilter::Applied(_), None) => false,
-        })
-        .filter(|trace| {
-            let method = &trace.http.as_ref().unwrap().status;
+        .filter(|trace| is_hidden(app, trace))
+        .count()
+}
+
+pub fn has_key(trace: &Trace, key: &str) -> bool {
+    match &trace.http {
+        Some(http) => {
+            parse_query_params(http.uri.clone())
+                .iter()
+                .any(|(name, _)| name.eq_ignore_ascii_case(key))
+                || http
+                    .request_headers
+                    .keys()
+                    .chain(http.response_headers.keys())
+                    .any(|name| name.as_str().eq_ignore_ascii_case(key))
+        }
+        None => false,
+    }
+}
+
+// Parses a comma separated list of status codes (`404`),
+// inclusive ranges (`400..=499`) and exclusions (`!404`).
+pub fn parse_status_range(input: &str) -> Result<Option<StatusRange>, String> {
+    if input.trim().is_empty() {
+        return Ok(None);
+    }
+
+    let mut status_range = StatusRange::default();
 
-            if method.is_none() {
-                return true;
+    let parse_code = |raw: &str| -> Result<u16, String> {
+        match raw.trim().parse::<u16>() {
+            Ok(code) if (100..=999).contains(&code) => Ok(code),
+            _ => Err(format!("Invalid status code: {}", raw.trim())),
+        }
+    };
+
+    for part in input
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+    {
+        if let Some(excluded) = part.strip_prefix('!') {
+            status_range.exclude.push(parse_code(excluded)?);
+        } else if let Some((min, max)) = part.split_once("..=") {
+            let (min, max) = (parse_code(min)?, parse_code(max)?);
+
+            if min > max {
+                return Err(format!("Invalid status range: {}", part));
             }
 
-            let method_as_string = method.as_ref().unwrap().clone().as_u16().to_string();
+            status_range.include.push(min..=max);
+        } else {
+            let code = parse_code(part)?;
 
-            let first_char = method_as_string.chars().nth(0).unwrap();
+            status_range.include.push(code..=code);
+        }
+    }
 
-            let matcher = match first_char {
-                '1' => "1xx",
-                '2' => "2xx",
-                '3' => "3xx",
-                '4' => "4xx",
-                '5' => "5xx",
-                _ => "",
-            };
+    Ok(Some(status_range))
+}
+
+pub fn get_content_type(trace: &Trace) -> Option<String> {
+    let http = trace.http.as_ref()?;
+
+    let content_type = http
+        .response_headers
+        .get(http::header::CONTENT_TYPE)
+        .or(http.request_headers.get(http::header::CONTENT_TYPE))?
+        .to_str()
+        .ok()?;
+
+    content_type
+        .split(';')
+        .next()
+        .map(|media_type| media_type.trim().to_lowercase())
+}
+
+// Whether a response media type satisfies an `Accept` header, media ranges
+// such as `text/*` included and ranges refused with `q=0` left out.
+pub fn is_accepted(accept: &str, content_type: &str) -> bool {
+    let media_type = |raw: &str| {
+        raw.split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase()
+    };
+
+    let content_type = media_type(content_type);
+    let (kind, subtype) = content_type.split_once('/').unwrap_or((&content_type, ""));
 
-            match (no_applied_status_filter, app.filters.status.get(matcher)) {
-                (true, _) => true,
-                (_, Some(status_filter)) => status_filter.selected.clone(),
-                (_, _) => false,
+    accept.split(',').any(|range| {
+        let is_refused = range.split(';').skip(1).any(|param| {
+            param
+                .trim()
+                .strip_prefix("q=")
+                .and_then(|q| q.parse::<f32>().ok())
+                == Some(0.0)
+        });
+
+        let range = media_type(range);
+
+        !is_refused
+            && match range.split_once('/') {
+                Some(("*", "*")) => true,
+                Some((range_kind, "*")) => range_kind == kind,
+                Some((range_kind, range_subtype)) => range_kind == kind && range_subtype == subtype,
+                None => false,
             }
-        })
-        .filter(|trace| {
-            match (
-                no_applied_method_filter,
-                app.filters.method.get(&trace.http.as_ref().unwrap().method),
-            ) {
-                (true, _) => true,
-                (_, Some(method_filter)) => method_filter.selected.clone(),
-                (_, _) => false,
+    })
+}
+
+// A warning when the response `content-type` isn't one the request's
+// `Accept` header asked for.
+pub fn content_type_mismatch(trace: &Trace) -> Option<String> {
+    let http = trace.http.as_ref()?;
+
+    let accept = http
+        .request_headers
+        .get(http::header::ACCEPT)?
+        .to_str()
+        .ok()?;
+    let content_type = http
+        .response_headers
+        .get(http::header::CONTENT_TYPE)?
+        .to_str()
+        .ok()?;
+
+    if is_accepted(accept, content_type) {
+        return None;
+    }
+
+    let media_type = content_type.split(';').next().unwrap_or_default().trim();
+
+    Some(format!("{} not in accept: {}", media_type, accept))
+}
+
+// Summarizes response caching: the `x-cache` verdict or, for responses
+// carrying an `age`, a hit with that age, otherwise the `cache-control`
+// directives.
+pub fn cache_status(headers: &HeaderMap) -> Option<String> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+
+    let verdict = header("x-cache").map(|x_cache| {
+        let upper = x_cache.to_uppercase();
+
+        if upper.contains("HIT") {
+            "HIT".to_string()
+        } else if upper.contains("MISS") {
+            "MISS".to_string()
+        } else {
+            x_cache
+        }
+    });
+
+    match (verdict, header("age")) {
+        (Some(verdict), Some(age)) => Some(format!("{} (age {}s)", verdict, age)),
+        (Some(verdict), None) => Some(verdict),
+        (None, Some(age)) => Some(format!("HIT (age {}s)", age)),
+        (None, None) => {
+            let cache_control = header("cache-control")?;
+
+            if cache_control.to_lowercase().contains("no-store") {
+                Some("no-store".to_string())
+            } else {
+                Some(cache_control)
             }
-        })
+        }
+    }
+}
+
+pub fn get_match_count(app: &Home) -> Option<usize> {
+    let re = search_regex(&app.search_query)?;
+
+    Some(
+        app.items
+            .iter()
+            .filter(|trace| matches_search(trace, Some(&re)))
+            .count(),
+    )
+}
+
+pub fn get_filter_screen(source: &str) -> Option<FilterScreen> {
+    match source {
+        "method" => Some(FilterScreen::Method),
+        "source" => Some(FilterScreen::Source),
+        "status" => Some(FilterScreen::Status),
+        "key" => Some(FilterScreen::Key),
+        "range" => Some(FilterScreen::StatusRange),
+        "content-type" => Some(FilterScreen::ContentType),
+        "presets" => Some(FilterScreen::Presets),
+        _ => None,
+    }
+}
+
+// Id shared by the traces of one request across services: a `traceId` or
+// `correlationId` the collector attached, else one sent in the request headers.
+pub fn correlation_id(trace: &Trace) -> Option<String> {
+    let attached =
+        ["traceId", "correlationId"]
+            .iter()
+            .find_map(|key| match trace.extra.get(*key)? {
+                Value::String(id) if !id.is_empty() => Some(id.clone()),
+                Value::Number(id) => Some(id.to_string()),
+                _ => None,
+            });
+
+    if attached.is_some() {
+        return attached;
+    }
+
+    let headers = &trace.http.as_ref()?.request_headers;
+
+    if let Some(id) = headers
+        .get("x-correlation-id")
+        .and_then(|value| value.to_str().ok())
+    {
+        return Some(id.to_string());
+    }
+
+    // version-traceid-parentid-flags, only the trace id is shared between spans
+    headers
+        .get("traceparent")?
+        .to_str()
+        .ok()?
+        .split('-')
+        .nth(1)
+        .map(|id| id.to_string())
+}
+
+// A 4xx/5xx response, or a request that errored or timed out.
+pub fn is_error_trace(trace: &Trace) -> bool {
+    trace.http.as_ref().is_some_and(|http| {
+        matches!(http.state, State::Error | State::Timeout)
+            || http
+                .status
+                .is_some_and(|status| status.is_client_error() || status.is_server_error())
+    })
+}
+
+// Each applied filter dimension yields whether the trace matches it;
+// dimensions that are not applied are skipped, and inverted ones are negated.
+pub fn matches_filters(trace: &Trace, filters: &TraceFilter) -> bool {
+    let http = match &trace.http {
+        Some(http) => http,
+        None => return true,
+    };
+
+    let applied_methods = filters
+        .method
+        .values()
+        .filter(|method_filter| method_filter.selected)
+        .collect::<Vec<_>>();
+
+    let applied_statuses = filters
+        .status
+        .values()
+        .filter(|status_filter| status_filter.selected)
+        .collect::<Vec<_>>();
+
+    let method = if applied_methods.is_empty() {
+        None
+    } else {
+        Some(
+            filters
+                .method
+                .get(&http.method)
+                .map_or(false, |method_filter| method_filter.selected),
+        )
+    };
+
+    let status = match (applied_statuses.is_empty(), http.status) {
+        (true, _) | (_, None) => None,
+        (false, Some(status)) => {
+            let matcher = format!("{}xx", status.as_u16() / 100);
+
+            Some(
+                filters
+                    .status
+                    .get(&matcher)
+                    .map_or(false, |status_filter| status_filter.selected),
+            )
+        }
+    };
+
+    let source = match &filters.source {
+        SourceFilter::All => None,
+        SourceFilter::Applied(sources) => Some(sources.contains(&trace.effective_source())),
+    };
+
+    let key = if filters.key.is_empty() {
+        None
+    } else {
+        Some(has_key(trace, &filters.key))
+    };
+
+    let status_range = match parse_status_range(&filters.status_range).unwrap_or(None) {
+        Some(range) => Some(
+            http.status
+                .map_or(false, |status| range.contains(status.as_u16())),
+        ),
+        None => None,
+    };
+
+    let content_type = match (&filters.content_type, get_content_type(trace)) {
+        (SourceFilter::All, _) => None,
+        (SourceFilter::Applied(content_types), Some(content_type)) => {
+            Some(content_types.contains(&content_type))
+        }
+        (SourceFilter::Applied(_), None) => Some(false),
+    };
+
+    let results = [
+        (FilterScreen::Method, method),
+        (FilterScreen::Status, status),
+        (FilterScreen::Source, source),
+        (FilterScreen::Key, key),
+        (FilterScreen::StatusRange, status_range),
+        (FilterScreen::ContentType, content_type),
+    ]
+    .into_iter()
+    .filter_map(|(screen, result)| result.map(|r| r != filters.inverted.contains(&screen)))
+    .collect::<Vec<bool>>();
+
+    match filters.combination {
+        FilterCombination::And => results.iter().all(|&result| result),
+        FilterCombination::Or => results.is_empty() || results.iter().any(|&result| result),
+    }
+}
+
+// The time window narrows every other filter, so it is not combined with them.
+pub fn is_within_time_window(trace: &Trace, window: TimeWindow, now: SystemTime) -> bool {
+    match window {
+        TimeWindow::All => true,
+        TimeWindow::LastSeconds(seconds) => {
+            get_trace_age(trace, now) <= Duration::from_secs(seconds)
+        }
+    }
+}
+
+pub fn get_filtered_count(app: &Home, filters: &TraceFilter) -> usize {
+    let maybe_re = search_regex(&app.search_query);
+    let now = SystemTime::now();
+
+    app.items
+        .iter()
+        .filter(|trace| matches_search(trace, maybe_re.as_ref()))
+        .filter(|trace| !is_hidden(app, trace))
+        .filter(|trace| matches_filters(trace, filters))
+        .filter(|trace| is_within_time_window(trace, filters.time_window, now))
+        .count()
+}
+
+pub fn get_rendered_items(app: &Home) -> Vec<&Trace> {
+    if app.frozen {
+        let items_by_id: HashMap<&str, &Trace> = app
+            .items
+            .iter()
+            .map(|trace| (trace.id.as_str(), trace))
+            .collect();
+
+        return app
+            .frozen_ids
+            .iter()
+            .filter_map(|id| items_by_id.get(id.as_str()).copied())
+            .collect();
+    }
+
+    let maybe_re = search_regex(&app.search_query);
+    let now = SystemTime::now();
+
+    let mut items_as_vector = app
+        .items
+        .iter()
+        .filter(|trace| matches_search(trace, maybe_re.as_ref()))
+        .filter(|trace| !is_hidden(app, trace))
+        .filter(|trace| matches_filters(trace, &app.filters))
+        .filter(|trace| is_within_time_window(trace, app.filters.time_window, now))
+        .filter(|trace| app.correlation_id.is_none() || correlation_id(trace) == app.correlation_id)
         .collect::<Vec<&Trace>>();
 
     items_as_vector.sort_by(|a, b| match &app.sort {
@@ -237,8 +763,8 @@ pub fn get_rendered_items(app: &Home) -> Vec<&Trace> {
             source: SortSource::Source,
             direction: SortDirection::Ascending,
         } => {
-            let a_has = &a.service_name;
-            let b_has = &b.service_name;
+            let a_has = a.effective_source();
+            let b_has = b.effective_source();
 
             a_has.cmp(&b_has)
         }
@@ -246,16 +772,56 @@ pub fn get_rendered_items(app: &Home) -> Vec<&Trace> {
             source: SortSource::Source,
             direction: SortDirection::Descending,
         } => {
-            let a_has = &a.service_name;
-            let b_has = &b.service_name;
+            let a_has = a.effective_source();
+            let b_has = b.effective_source();
 
             b_has.cmp(&a_has)
         }
+        TraceSort {
+            source: SortSource::HeaderCount,
+            direction: SortDirection::Ascending,
+        } => header_count(a)
+            .cmp(&header_count(b))
+            .then_with(|| a.timestamp.cmp(&b.timestamp)),
+        TraceSort {
+            source: SortSource::HeaderCount,
+            direction: SortDirection::Descending,
+        } => header_count(b)
+            .cmp(&header_count(a))
+            .then_with(|| b.timestamp.cmp(&a.timestamp)),
     });
 
     items_as_vector
 }
 
+fn header_count(trace: &Trace) -> usize {
+    trace.http.as_ref().map_or(0, |http| {
+        http.request_headers.len() + http.response_headers.len()
+    })
+}
+
+// Rendered traces with a response body, largest first. Each entry carries the
+// trace's index in the rendered list so it can be selected from there.
+pub fn get_largest_responses(app: &Home, count: usize) -> Vec<(usize, &Trace, usize)> {
+    let mut responses = get_rendered_items(app)
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, trace)| {
+            let http = trace.http.as_ref()?;
+
+            http.response_body.as_ref()?;
+
+            Some((index, trace, http.response_body_size))
+        })
+        .collect::<Vec<_>>();
+
+    // stable, so equally sized responses keep the list order
+    responses.sort_by_key(|&(_, _, size)| Reverse(size));
+    responses.truncate(count);
+
+    responses
+}
+
 pub fn get_currently_selected_trace(app: &Home) -> Option<Trace> {
     let items_as_vector = get_rendered_items(app);
 
@@ -264,16 +830,83 @@ pub fn get_currently_selected_trace(app: &Home) -> Option<Trace> {
     trace.cloned()
 }
 
+// The selected trace's URL with the redacted query params replaced.
+pub fn get_selected_trace_url(app: &Home) -> Option<String> {
+    let http = app.selected_trace.as_ref()?.http.as_ref()?;
+
+    Some(redact_uri(&http.uri, app.redacted_keys()))
+}
+
+// The selected trace in the given format, redacted like the other copies.
+pub fn get_selected_trace_export(app: &Home, format: ExportFormat) -> Option<String> {
+    let trace = app.selected_trace.as_ref()?;
+    let redacted = app.redacted_keys();
+
+    let text = match format {
+        ExportFormat::Curl => generate_curl_command(trace, app.curl_query_flags, redacted),
+        ExportFormat::Httpie => generate_httpie_command(trace, redacted),
+        ExportFormat::Fetch => generate_fetch_snippet(trace, redacted),
+        ExportFormat::HttpFile => generate_http_file(trace, redacted),
+        ExportFormat::Markdown => generate_markdown_summary(trace, redacted),
+    };
+
+    Some(text)
+}
+
+// The response body as copied, formatted when it is JSON and as shown
+// otherwise, e.g. the placeholder of a binary body.
+pub fn get_copied_response_body(trace: &Trace) -> Option<String> {
+    let body = trace.http.as_ref()?.response_body.as_ref()?;
+
+    Some(pretty_parse_body(body).unwrap_or(body.clone()))
+}
+
+// The focused headers pane as `Name: Value` lines, leaving out its action row.
+pub fn get_focused_headers(app: &Home) -> Option<String> {
+    if app.active_block != ActiveBlock::Details {
+        return None;
+    }
+
+    let list = match app.details_block {
+        DetailsPane::RequestHeaders => &app.request_headers_list,
+        DetailsPane::ResponseHeaders => &app.response_headers_list,
+        _ => return None,
+    };
+
+    let headers = list
+        .items
+        .iter()
+        .filter(|item| item.action.is_none())
+        .map(|item| {
+            let value = if is_redacted(&item.label, app.redacted_keys()) {
+                REDACTED_VALUE
+            } else {
+                item.value.as_deref().unwrap_or_default()
+            };
+
+            format!("{}: {}", item.label, value)
+        })
+        .collect::<Vec<_>>();
+
+    Some(headers.join("\n"))
+}
+
+// Maps an offset in `0..=overflown_number_count` onto the scrollbar positions
+// `0..content_length`, so the first and last offsets land exactly on the ends
+// of the track.
 pub fn calculate_scrollbar_position(
     content_length: u16,
     offset: usize,
     overflown_number_count: u16,
 ) -> u16 {
-    let content_length_as_float = content_length as f32;
-    let overflown_number_count_as_float = overflown_number_count as f32;
-    let offset_as_float = offset as f32;
+    if overflown_number_count == 0 {
+        return 0;
+    }
 
-    ({ (content_length_as_float / overflown_number_count_as_float) * offset_as_float } as u16)
+    let last_position = content_length.saturating_sub(1) as usize;
+    let offset = offset.min(overflown_number_count as usize);
+
+    (last_position * offset / overflown_number_count as usize) as u16
 }
 
 pub struct ContentLength {
@@ -330,7 +963,7 @@ pub fn get_content_length(app: &Home) -> ContentLengthElements {
         let response_lines = response_lines.unwrap();
 
         let response_longest = response_lines.lines().fold(0, |longest: u16, lines: &str| {
-            let len = lines.len() as u16;
+            let len = lines.width() as u16;
 
             len.max(longest)
         });
@@ -352,7 +985,7 @@ pub fn get_content_length(app: &Home) -> ContentLengthElements {
         let request_lines = request_lines.unwrap();
 
         let request_longest = request_lines.lines().fold(0, |longest: u16, lines: &str| {
-            let len = lines.len() as u16;
+            let len = lines.width() as u16;
 
             len.max(longest)
         });
@@ -376,26 +1009,8 @@ pub fn get_content_length(app: &Home) -> ContentLengthElements {
 pub fn set_content_length(app: &mut Home) {
     let content_length_elements = get_content_length(app);
 
-    let response_details_content_length = content_length_elements
-        .response_headers
-        .unwrap_or(ContentLength {
-            vertical: 0,
-            horizontal: 0,
-        })
-        .vertical;
-
     let res = content_length_elements.response_body;
 
-    app.request_details.scroll_state = app
-        .request_details
-        .scroll_state
-        .content_length(content_length_elements.request_headers.vertical.into());
-
-    app.response_details.scroll_state = app
-        .response_details
-        .scroll_state
-        .content_length(response_details_content_length.into());
-
     if res.is_some() {
         let res = res.unwrap();
 
@@ -426,3 +1041,308 @@ pub fn set_content_length(app: &mut Home) {
             .content_length(req.horizontal.into());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::mock;
+    use crate::parser::{parse_raw_trace, Payload};
+    use crate::services::websocket::HTTPTrace;
+
+    #[test]
+    fn test_format_relative_time() {
+        assert_eq!("0s ago", format_relative_time(Duration::from_millis(400)));
+        assert_eq!("12s ago", format_relative_time(Duration::from_secs(12)));
+        assert_eq!("1m30s ago", format_relative_time(Duration::from_secs(90)));
+        assert_eq!("2h5m ago", format_relative_time(Duration::from_secs(7_500)));
+        assert_eq!(
+            "1d1h ago",
+            format_relative_time(Duration::from_secs(90_000))
+        );
+    }
+
+    #[test]
+    fn test_format_uptime() {
+        assert_eq!("00:00:09", format_uptime(Duration::from_millis(9_800)));
+        assert_eq!("00:02:05", format_uptime(Duration::from_secs(125)));
+        assert_eq!("26:00:01", format_uptime(Duration::from_secs(93_601)));
+    }
+
+    #[test]
+    fn test_trace_rate() {
+        let now = Instant::now() + Duration::from_secs(60);
+        let arrivals: VecDeque<Instant> = [30, 9, 5, 2, 1, 0]
+            .iter()
+            .map(|seconds_ago| now - Duration::from_secs(*seconds_ago))
+            .collect();
+
+        // the arrival 30 seconds ago is outside the window
+        assert_eq!(0.5, trace_rate(&arrivals, now));
+        assert_eq!(0.0, trace_rate(&VecDeque::new(), now));
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!("512 B", format_size(512));
+        assert_eq!("1.5 kB", format_size(1_500));
+        assert_eq!("2.3 MB", format_size(2_300_000));
+    }
+
+    #[test]
+    fn test_spinner_glyph() {
+        assert_eq!("⠋", get_spinner_glyph(0));
+        assert_eq!("⠙", get_spinner_glyph(1));
+        assert_eq!("⠏", get_spinner_glyph(9));
+        // wraps around
+        assert_eq!("⠋", get_spinner_glyph(10));
+        assert_eq!("⠹", get_spinner_glyph(22));
+    }
+
+    #[test]
+    fn test_truncate_middle() {
+        assert_eq!("https://a.io", truncate_middle("https://a.io", 12));
+        assert_eq!("https…/b/c", truncate_middle("https://a.io/b/c", 10));
+    }
+
+    #[test]
+    fn test_content_length_uses_display_width() {
+        let body = "{\n  \"city\": \"東京都\"\n}";
+        let app = Home {
+            selected_trace: Some(Trace {
+                http: Some(HTTPTrace {
+                    pretty_response_body: Some(body.to_string()),
+                    pretty_request_body: Some(body.to_string()),
+                    ..HTTPTrace::default()
+                }),
+                ..Trace::default()
+            }),
+            ..Home::default()
+        };
+
+        let content_length = get_content_length(&app);
+
+        // 12 columns for the key and quotes, 2 for each of the 3 CJK characters,
+        // while the line is 21 bytes long.
+        assert_eq!(18, content_length.response_body.unwrap().horizontal);
+        assert_eq!(18, content_length.request_body.unwrap().horizontal);
+    }
+
+    #[test]
+    fn test_timestamp_to_datetime() {
+        let expected = DateTime::from_timestamp(1694891653, 0).unwrap();
+
+        assert_eq!(expected, timestamp_to_datetime(1694891653));
+        assert_eq!(
+            expected + chrono::Duration::milliseconds(602),
+            timestamp_to_datetime(1694891653602)
+        );
+    }
+
+    #[test]
+    fn test_sort_by_header_count() {
+        let trace_with_headers = |id: &str, timestamp: i64, count: usize| {
+            let mut http = HTTPTrace::default();
+
+            for i in 0..count {
+                http.response_headers.insert(
+                    http::HeaderName::from_bytes(format!("x-header-{}", i).as_bytes()).unwrap(),
+                    http::HeaderValue::from_static("value"),
+                );
+            }
+
+            Trace {
+                id: id.to_string(),
+                timestamp,
+                http: Some(http),
+                ..Trace::default()
+            }
+        };
+
+        let mut home = Home::default();
+
+        home.items.insert(trace_with_headers("a", 1, 3));
+        home.items.insert(trace_with_headers("b", 2, 1));
+        home.items.insert(trace_with_headers("c", 3, 5));
+        home.items.insert(trace_with_headers("d", 4, 1));
+
+        let ids = |home: &Home| {
+            get_rendered_items(home)
+                .iter()
+                .map(|trace| trace.id.clone())
+                .collect::<Vec<_>>()
+        };
+
+        home.sort = TraceSort {
+            source: SortSource::HeaderCount,
+            direction: SortDirection::Ascending,
+        };
+
+        assert_eq!(vec!["b", "d", "a", "c"], ids(&home));
+
+        home.sort.direction = SortDirection::Descending;
+
+        assert_eq!(vec!["c", "a", "d", "b"], ids(&home));
+    }
+
+    #[test]
+    fn test_time_window_filter() {
+        let now = SystemTime::now();
+        let now_millis = now
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as i64);
+        let mut app = Home::default();
+
+        for (id, timestamp) in [
+            ("1", now_millis - 200_000),
+            ("2", now_millis - 45_000),
+            // older collectors report seconds
+            ("3", now_millis / 1000 - 10),
+        ] {
+            app.items.insert(Trace {
+                id: id.to_string(),
+                timestamp,
+                ..Trace::default()
+            });
+        }
+
+        let ids = |app: &Home| {
+            let mut ids = get_rendered_items(app)
+                .iter()
+                .map(|trace| trace.id.clone())
+                .collect::<Vec<_>>();
+
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(vec!["1", "2", "3"], ids(&app));
+
+        app.filters.time_window = TimeWindow::LastSeconds(60);
+
+        assert_eq!(vec!["2", "3"], ids(&app));
+        assert_eq!(2, get_filtered_count(&app, &app.filters));
+
+        app.filters.time_window = TimeWindow::LastSeconds(30);
+
+        assert_eq!(vec!["3"], ids(&app));
+        assert_eq!("last 30s", app.filters.time_window.to_string());
+        assert_eq!(TimeWindow::LastSeconds(60), app.filters.time_window.next());
+        assert_eq!(TimeWindow::All, TimeWindow::LastSeconds(300).next());
+    }
+
+    #[test]
+    fn test_content_type_mismatch() -> Result<(), Box<dyn std::error::Error>> {
+        let trace = |raw: &str| match parse_raw_trace(raw) {
+            Ok(Payload::Trace(trace)) => trace,
+            _ => panic!("expected a trace"),
+        };
+
+        assert_eq!(
+            Some("application/xml not in accept: application/json".to_string()),
+            content_type_mismatch(&trace(mock::TEST_JSON_8))
+        );
+        // application/json; charset=utf-8
+        assert_eq!(None, content_type_mismatch(&trace(mock::TEST_JSON_3)));
+        // */*
+        assert_eq!(None, content_type_mismatch(&trace(mock::TEST_JSON_5)));
+
+        assert!(is_accepted("text/*, application/json;q=0.9", "text/html"));
+        assert!(is_accepted("application/xml,*/*;q=0.8", "image/png"));
+        assert!(!is_accepted("application/json, text/*;q=0", "text/html"));
+        assert!(!is_accepted("application/json", "application/problem+json"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cache_status() -> Result<(), Box<dyn std::error::Error>> {
+        let response_headers = |raw: &str| match parse_raw_trace(raw) {
+            Ok(Payload::Trace(trace)) => trace.http.unwrap_or_default().response_headers,
+            _ => panic!("expected a trace"),
+        };
+
+        assert_eq!(
+            Some("no-store".to_string()),
+            cache_status(&response_headers(mock::TEST_JSON_1))
+        );
+        assert_eq!(
+            Some("no-store".to_string()),
+            cache_status(&response_headers(mock::TEST_JSON_2))
+        );
+        assert_eq!(None, cache_status(&response_headers(mock::TEST_JSON_3)));
+
+        let mut headers = HeaderMap::new();
+
+        headers.insert("cache-control", "public, max-age=60".parse()?);
+
+        assert_eq!(
+            Some("public, max-age=60".to_string()),
+            cache_status(&headers)
+        );
+
+        headers.insert("x-cache", "Miss from cloudfront".parse()?);
+
+        assert_eq!(Some("MISS".to_string()), cache_status(&headers));
+
+        headers.insert("x-cache", "TCP_HIT".parse()?);
+        headers.insert("age", "42".parse()?);
+
+        assert_eq!(Some("HIT (age 42s)".to_string()), cache_status(&headers));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_largest_responses() {
+        let trace_with_body = |id: &str, timestamp: i64, body: Option<&str>| Trace {
+            id: id.to_string(),
+            timestamp,
+            http: Some(HTTPTrace {
+                response_body: body.map(|body| body.to_string()),
+                response_body_size: body.map_or(0, str::len),
+                ..HTTPTrace::default()
+            }),
+            ..Trace::default()
+        };
+
+        let mut home = Home::default();
+
+        home.items.insert(trace_with_body("a", 1, Some("{}")));
+        home.items
+            .insert(trace_with_body("b", 2, Some(&"x".repeat(2_000))));
+        home.items.insert(trace_with_body("c", 3, None));
+        home.items
+            .insert(trace_with_body("d", 4, Some(&"x".repeat(300))));
+        home.items
+            .insert(trace_with_body("e", 5, Some(&"x".repeat(50_000))));
+        home.items.insert(trace_with_body("f", 6, Some("")));
+
+        // truncated bodies rank by the size they were received with
+        let mut truncated = trace_with_body("g", 7, Some("(truncated, 5000 bytes)"));
+        truncated.http.as_mut().unwrap().response_body_size = 5_000;
+        home.items.insert(truncated);
+
+        let largest = get_largest_responses(&home, 3);
+
+        assert_eq!(
+            vec![("e", 50_000), ("g", 5_000), ("b", 2_000)],
+            largest
+                .iter()
+                .map(|(_, trace, size)| (trace.id.as_str(), *size))
+                .collect::<Vec<_>>()
+        );
+
+        // indexes point into the rendered list
+        let rendered = get_rendered_items(&home);
+
+        for (index, trace, _) in &largest {
+            assert_eq!(trace.id, rendered[*index].id);
+        }
+
+        // in flight responses are left out
+        assert_eq!(6, get_largest_responses(&home, 10).len());
+    }
+}
@@ -2,20 +2,18 @@ use std::collections::HashMap;
 use std::error::Error;
 use std::net::SocketAddr;
 use std::sync::Arc;
-use std::time::Duration;
 
 use futures_channel::mpsc::{unbounded, UnboundedSender};
 use futures_util::{future, pin_mut, StreamExt, TryStreamExt};
 use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::Mutex;
 use tokio::task::AbortHandle;
-use tokio::time::sleep;
 use tungstenite::connect;
 use tungstenite::handshake::server::{Callback, ErrorResponse, Request, Response};
 use url::Url;
 
 use crate::app::{Action, WebSocketInternalState};
-use crate::parser::parse_raw_trace;
+use crate::parser::parse_raw_trace_with_max_body;
 
 use tungstenite::Message;
 
@@ -146,6 +144,7 @@ impl WebSocket {
 
 pub async fn client(
     tx: Option<tokio::sync::mpsc::UnboundedSender<Action>>,
+    max_body_bytes: usize,
 ) -> Result<(), Box<dyn Error>> {
     let (mut socket, _response) =
         connect(Url::parse("ws://127.0.0.1:9999/inner_client").unwrap()).expect("Can't connect");
@@ -157,33 +156,24 @@ pub async fn client(
             Ok(message) => {
                 match message {
                     tungstenite::Message::Text(s) => {
-                        match parse_raw_trace(&s) {
-                            Ok(request) => match request {
-                                crate::parser::Payload::Trace(trace) => {
-                                    let mut should_persist = true;
+                        match parse_raw_trace_with_max_body(&s, max_body_bytes) {
+                            Ok(request) => {
+                                for trace in request.into_traces() {
+                                    let http_trace = match trace.http.as_ref() {
+                                        Some(http_trace) => http_trace,
+                                        None => continue,
+                                    };
 
-                                    let http_trace = trace.http.as_ref().unwrap();
-
-                                    if &http_trace.port == "9999" {
-                                        should_persist = false;
-                                    }
+                                    let should_persist = http_trace.port != "9999";
 
                                     if let Some(s) = tx.clone() {
-                                        let id = trace.id.clone();
-                                        let s1 = s.clone();
-                                        tokio::spawn(async move {
-                                            sleep(Duration::from_millis(5000)).await;
-                                            s1.send(Action::MarkTraceAsTimedOut(id)).unwrap();
-                                        });
-
                                         if should_persist {
                                             let s2 = s.clone();
                                             s2.send(Action::AddTrace(trace)).unwrap();
                                         }
                                     }
                                 }
-                                _ => {}
-                            },
+                            }
                             Err(err) => {
                                 println!("Trace NOT parsed!! {:?}", err)
                             }